@@ -0,0 +1,31 @@
+//! Runtime API definition for orml asset registry pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use parity_scale_codec::Codec;
+use sp_std::prelude::Vec;
+use xcm::VersionedLocation;
+
+sp_api::decl_runtime_apis! {
+	pub trait AssetRegistryApi<AssetId, Metadata> where
+		AssetId: Codec,
+		Metadata: Codec,
+	{
+		/// The metadata of an asset.
+		fn metadata(asset_id: AssetId) -> Option<Metadata>;
+		/// The asset at `location`, which can be of any supported XCM
+		/// version, and its metadata.
+		fn metadata_by_location(location: VersionedLocation) -> Option<(AssetId, Metadata)>;
+		/// Up to `limit` assets and their metadata, following `start_after`
+		/// (or from the beginning if `None`) in storage order.
+		fn assets(start_after: Option<AssetId>, limit: u32) -> Vec<(AssetId, Metadata)>;
+		/// The location of an asset, converted to XCM `version`. `None` if
+		/// the asset has no location, or it can't be represented in
+		/// `version`.
+		fn location(asset_id: AssetId, version: u32) -> Option<VersionedLocation>;
+	}
+}
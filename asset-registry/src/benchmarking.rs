@@ -0,0 +1,109 @@
+pub use crate::*;
+
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+/// Helper trait for benchmarking.
+pub trait BenchmarkHelper<CustomMetadata> {
+	/// Returns the custom metadata of an asset registered by the
+	/// benchmarks.
+	fn custom_metadata() -> Option<CustomMetadata>;
+}
+
+impl<CustomMetadata> BenchmarkHelper<CustomMetadata> for () {
+	fn custom_metadata() -> Option<CustomMetadata> {
+		None
+	}
+}
+
+fn max_string<T: Config>() -> BoundedVec<u8, T::StringLimit> {
+	vec![b'a'; T::StringLimit::get() as usize]
+		.try_into()
+		.expect("has the maximum length; qed")
+}
+
+fn owned_metadata<T: Config>() -> Result<DefaultAssetMetadata<T>, BenchmarkError> {
+	Ok(AssetMetadata {
+		decimals: 0,
+		name: max_string::<T>(),
+		symbol: max_string::<T>(),
+		existential_deposit: Zero::zero(),
+		location: None,
+		additional: T::BenchmarkHelper::custom_metadata().ok_or(BenchmarkError::Skip)?,
+	})
+}
+
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, DepositBalanceOf::<T>::max_value() / 2u32.into());
+	caller
+}
+
+/// Registers an asset owned by `owner`, returning its id.
+fn register_owned<T: Config>(owner: T::AccountId) -> Result<T::AssetId, BenchmarkError> {
+	Pallet::<T>::do_register_owned_asset(owner.clone(), owned_metadata::<T>()?)?;
+	let (asset_id, _) = AssetOwners::<T>::iter()
+		.find(|(_, (o, _))| *o == owner)
+		.ok_or(BenchmarkError::Stop("owned asset not registered"))?;
+	Ok(asset_id)
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn register_owned_asset() -> Result<(), BenchmarkError> {
+		let caller = funded_caller::<T>();
+		let metadata = owned_metadata::<T>()?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), metadata);
+
+		assert!(AssetOwners::<T>::iter().any(|(_, (owner, _))| owner == caller));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn update_owned_asset() -> Result<(), BenchmarkError> {
+		let caller = funded_caller::<T>();
+		let asset_id = register_owned::<T>(caller.clone())?;
+		let additional = T::BenchmarkHelper::custom_metadata().ok_or(BenchmarkError::Skip)?;
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller),
+			asset_id.clone(),
+			Some(max_string::<T>()),
+			Some(max_string::<T>()),
+			Some(additional),
+		);
+
+		assert!(Metadata::<T>::contains_key(&asset_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn deregister_owned_asset() -> Result<(), BenchmarkError> {
+		let caller = funded_caller::<T>();
+		let asset_id = register_owned::<T>(caller.clone())?;
+		// the worst case: governance gave the asset aliases, a fee and a pending update
+		for i in 0..T::MaxAliases::get() {
+			let alias = VersionedLocation::from(Location::new(0, [GeneralIndex(i.into())]));
+			Pallet::<T>::do_add_location_alias(asset_id.clone(), alias)?;
+		}
+		FeePerSecond::<T>::insert(&asset_id, 1);
+		let enact_at = frame_system::Pallet::<T>::block_number() + 1u32.into();
+		Pallet::<T>::do_schedule_update(asset_id.clone(), enact_at, Default::default())?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), asset_id.clone());
+
+		assert!(!Metadata::<T>::contains_key(&asset_id));
+		assert!(T::Currency::reserved_balance(&caller).is_zero());
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::para_ext(1, None), crate::mock::para::Runtime);
+}
@@ -3,13 +3,14 @@ use frame_support::{pallet_prelude::*, weights::constants::WEIGHT_REF_TIME_PER_S
 use orml_traits::{
 	asset_registry::{
 		AssetMetadata, AssetProcessor, FixedConversionRateProvider, Inspect, Mutate, WeightToFeeConverter,
+		XcmCustomMetadata,
 	},
 	GetByKey,
 };
 use sp_runtime::FixedPointNumber;
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, Bounded, CheckedAdd, One},
-	ArithmeticError, FixedU128,
+	traits::{AtLeast32BitUnsigned, Bounded, CheckedAdd, Convert, MaybeEquivalence, One},
+	ArithmeticError, FixedU128, Perquintill,
 };
 use sp_std::prelude::*;
 use xcm::VersionedLocation;
@@ -65,6 +66,21 @@ impl<P: FixedConversionRateProvider> WeightToFeeConverter for FixedRateAssetRegi
 	}
 }
 
+/// A `FixedConversionRateProvider` backed by the `FeePerSecond` storage of
+/// the registry. The location could be any alias of the asset.
+pub struct AssetRegistryFeePerSecond<T>(PhantomData<T>);
+impl<T: Config> FixedConversionRateProvider for AssetRegistryFeePerSecond<T> {
+	fn get_fee_per_second(location: &Location) -> Option<u128> {
+		let asset_id = <Pallet<T> as Inspect>::asset_id(location)?;
+		Pallet::<T>::fee_per_second(asset_id)
+	}
+}
+
+/// A `WeightTrader` charging the `FeePerSecond` of the registry, and
+/// refunding unused weight. Revenue is handled by `R`.
+pub type AssetRegistryFeeTrader<T, R> =
+	AssetRegistryTrader<FixedRateAssetRegistryTrader<AssetRegistryFeePerSecond<T>>, R>;
+
 /// Helper struct for the AssetRegistryTrader that stores the data about
 /// bought weight.
 pub struct BoughtWeight {
@@ -144,7 +160,10 @@ impl<W: WeightToFeeConverter, R: TakeRevenue> WeightTrader for AssetRegistryTrad
 		match self.bought_weight {
 			Some(ref mut bought) => {
 				let new_weight = bought.weight.saturating_sub(weight);
-				let new_amount = W::convert_weight_to_fee(&bought.asset_location, new_weight)?;
+				// if the asset is no longer accepted, refund at the price it was bought for
+				let new_amount = W::convert_weight_to_fee(&bought.asset_location, new_weight).unwrap_or_else(|| {
+					Perquintill::from_rational(new_weight.ref_time(), bought.weight.ref_time()).mul_ceil(bought.amount)
+				});
 				let refunded_amount = bought.amount.saturating_sub(new_amount);
 
 				bought.weight = new_weight;
@@ -180,6 +199,95 @@ impl<T: Config> GetByKey<T::AssetId, T::Balance> for ExistentialDeposits<T> {
 	}
 }
 
+/// Registry-backed `ExistentialDeposits` for `orml_tokens`, keyed by the
+/// currency id of the runtime. `M` maps currency ids to asset ids, and
+/// currencies which aren't registered assets are looked up in `Other`.
+pub struct AssetRegistryExistentialDeposits<T, M, Other>(PhantomData<(T, M, Other)>);
+impl<T, CurrencyId, M, Other> GetByKey<CurrencyId, T::Balance> for AssetRegistryExistentialDeposits<T, M, Other>
+where
+	T: Config,
+	M: MaybeEquivalence<CurrencyId, T::AssetId>,
+	Other: GetByKey<CurrencyId, T::Balance>,
+{
+	fn get(currency_id: &CurrencyId) -> T::Balance {
+		match M::convert(currency_id) {
+			Some(asset_id) => ExistentialDeposits::<T>::get(&asset_id),
+			None => Other::get(currency_id),
+		}
+	}
+}
+
+/// Registry-backed conversion of the currency ids of the runtime to
+/// locations, as used by `orml_xtokens::Config::CurrencyIdConvert`. `M`
+/// maps currency ids to asset ids, and currencies which aren't registered
+/// assets are converted by `Other`.
+pub struct AssetRegistryCurrencyIdToLocation<T, M, Other>(PhantomData<(T, M, Other)>);
+impl<T, CurrencyId, M, Other> Convert<CurrencyId, Option<Location>> for AssetRegistryCurrencyIdToLocation<T, M, Other>
+where
+	T: Config,
+	M: MaybeEquivalence<CurrencyId, T::AssetId>,
+	Other: Convert<CurrencyId, Option<Location>>,
+{
+	fn convert(currency_id: CurrencyId) -> Option<Location> {
+		match M::convert(&currency_id) {
+			Some(asset_id) => <Pallet<T> as Inspect>::location(&asset_id).ok().flatten(),
+			None => Other::convert(currency_id),
+		}
+	}
+}
+
+/// Registry-backed conversion of locations and assets to the currency ids
+/// of the runtime, as used by the XCM asset transactor. Locations are
+/// converted by `Other` first, then looked up in the registry (including
+/// aliases), with `M` mapping asset ids back to currency ids.
+pub struct AssetRegistryLocationToCurrencyId<T, M, Other>(PhantomData<(T, M, Other)>);
+impl<T, CurrencyId, M, Other> Convert<Location, Option<CurrencyId>> for AssetRegistryLocationToCurrencyId<T, M, Other>
+where
+	T: Config,
+	M: MaybeEquivalence<CurrencyId, T::AssetId>,
+	Other: Convert<Location, Option<CurrencyId>>,
+{
+	fn convert(location: Location) -> Option<CurrencyId> {
+		Other::convert(location.clone()).or_else(|| M::convert_back(&<Pallet<T> as Inspect>::asset_id(&location)?))
+	}
+}
+impl<T, CurrencyId, M, Other> Convert<Asset, Option<CurrencyId>> for AssetRegistryLocationToCurrencyId<T, M, Other>
+where
+	T: Config,
+	M: MaybeEquivalence<CurrencyId, T::AssetId>,
+	Other: Convert<Location, Option<CurrencyId>>,
+{
+	fn convert(asset: Asset) -> Option<CurrencyId> {
+		match asset {
+			Asset {
+				id: AssetId(location),
+				fun: Fungible(_),
+			} => Self::convert(location),
+			_ => None,
+		}
+	}
+}
+
+/// Registry-backed `MinXcmFee` for `orml_xtokens`, keyed by the location of
+/// a chain. `FeeAsset` gives the location of the asset the chain charges fees
+/// in, and the minimum is the `min_xcm_fee` in the custom metadata of that
+/// asset, or else given by `Other`.
+pub struct AssetRegistryMinXcmFee<T, FeeAsset, Other>(PhantomData<(T, FeeAsset, Other)>);
+impl<T, FeeAsset, Other> GetByKey<Location, Option<u128>> for AssetRegistryMinXcmFee<T, FeeAsset, Other>
+where
+	T: Config,
+	T::CustomMetadata: XcmCustomMetadata,
+	FeeAsset: GetByKey<Location, Option<Location>>,
+	Other: GetByKey<Location, Option<u128>>,
+{
+	fn get(location: &Location) -> Option<u128> {
+		FeeAsset::get(location)
+			.and_then(|fee_asset| <Pallet<T> as Inspect>::metadata_by_location(&fee_asset))
+			.and_then(|metadata| metadata.additional.min_xcm_fee())
+			.or_else(|| Other::get(location))
+	}
+}
+
 impl<T: Config> Inspect for Pallet<T> {
 	type AssetId = T::AssetId;
 	type Balance = T::Balance;
@@ -203,6 +311,13 @@ impl<T: Config> Inspect for Pallet<T> {
 	fn location(asset_id: &Self::AssetId) -> Result<Option<Location>, DispatchError> {
 		Pallet::<T>::location(asset_id).map(|l| l.and_then(|l| l.into_versioned().try_into().ok()))
 	}
+
+	fn location_aliases(asset_id: &Self::AssetId) -> Vec<Location> {
+		Pallet::<T>::aliases(asset_id)
+			.into_iter()
+			.filter_map(|l| l.into_versioned().try_into().ok())
+			.collect()
+	}
 }
 
 impl<T: Config> Mutate for Pallet<T> {
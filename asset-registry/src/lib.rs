@@ -4,22 +4,31 @@
 #![allow(clippy::large_enum_variant)]
 #![allow(clippy::too_many_arguments)]
 
-use frame_support::{pallet_prelude::*, traits::EnsureOriginWithArg};
+use frame_support::{
+	pallet_prelude::*,
+	storage::with_storage_layer,
+	traits::{Currency, EnsureOriginWithArg, ReservableCurrency},
+	DefaultNoBound,
+};
 use frame_system::pallet_prelude::*;
 pub use orml_traits::asset_registry::AssetMetadata;
-use orml_traits::asset_registry::AssetProcessor;
+use orml_traits::{asset_registry::AssetProcessor, GetByKey};
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, Member},
+	traits::{AtLeast32BitUnsigned, Member, Zero},
 	DispatchResult,
 };
-use sp_std::prelude::*;
-use xcm::{v3::prelude::*, VersionedLocation};
+use sp_std::{fmt::Debug, prelude::*};
+use xcm::{v3::prelude::*, IntoVersion, VersionedLocation};
 
+#[cfg(feature = "runtime-benchmarks")]
+pub use benchmarking::BenchmarkHelper;
 pub use impls::*;
 use module::*;
 pub use weights::WeightInfo;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 mod impls;
 mod weights;
 
@@ -28,6 +37,55 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+/// A change of the metadata of an asset, scheduled by `update_asset` to be
+/// applied at a future block. `None` fields are left unchanged.
+#[derive(
+	TypeInfo,
+	Encode,
+	Decode,
+	CloneNoBound,
+	EqNoBound,
+	PartialEqNoBound,
+	RuntimeDebugNoBound,
+	DefaultNoBound,
+	MaxEncodedLen,
+	DecodeWithMemTracking,
+)]
+#[codec(mel_bound(skip_type_params(StringLimit)))]
+#[scale_info(skip_type_params(StringLimit))]
+pub struct AssetUpdate<Balance, CustomMetadata, StringLimit: Get<u32>>
+where
+	Balance: Clone + Debug + Eq + PartialEq,
+	CustomMetadata: Parameter + Member + TypeInfo,
+{
+	pub decimals: Option<u32>,
+	pub name: Option<BoundedVec<u8, StringLimit>>,
+	pub symbol: Option<BoundedVec<u8, StringLimit>>,
+	pub existential_deposit: Option<Balance>,
+	pub location: Option<Option<VersionedLocation>>,
+	pub additional: Option<CustomMetadata>,
+}
+
+/// The metadata of assets registered with `register_owned_asset` which is
+/// controlled by governance instead of their owner.
+pub trait OwnedAssetMetadata<Balance, CustomMetadata> {
+	/// The decimals of an owned asset at registration.
+	fn decimals() -> u32;
+
+	/// The existential deposit of an owned asset at registration.
+	fn existential_deposit() -> Balance;
+
+	/// `additional` set by the owner, with the fields controlled by
+	/// governance, like XCM fees, taken from `governed`: the current
+	/// custom metadata of the asset, or `None` at registration.
+	fn filter_additional(additional: CustomMetadata, governed: Option<&CustomMetadata>) -> CustomMetadata;
+}
+
+pub type DepositBalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+pub type AssetUpdateOf<T> =
+	AssetUpdate<<T as Config>::Balance, <T as Config>::CustomMetadata, <T as Config>::StringLimit>;
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -57,8 +115,37 @@ pub mod module {
 		#[pallet::constant]
 		type StringLimit: Get<u32>;
 
+		/// The total issuance of an asset. An asset can only be deregistered
+		/// when it's zero.
+		type TotalIssuance: GetByKey<Self::AssetId, Self::Balance>;
+
+		/// The maximum number of alias locations of an asset, besides its
+		/// canonical location.
+		#[pallet::constant]
+		type MaxAliases: Get<u32>;
+
+		/// The maximum number of pending updates applied at the same block.
+		#[pallet::constant]
+		type MaxPendingUpdatesPerBlock: Get<u32>;
+
+		/// The currency in which registration deposits are reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved from the registrant of an asset registered
+		/// with `register_owned_asset`.
+		#[pallet::constant]
+		type RegistrationDeposit: Get<DepositBalanceOf<Self>>;
+
+		/// The decimals, existential deposit and governance-controlled
+		/// custom metadata of assets registered with
+		/// `register_owned_asset`, which their owner can't set.
+		type OwnedAssetMetadata: OwnedAssetMetadata<Self::Balance, Self::CustomMetadata>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
+
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::CustomMetadata>;
 	}
 
 	#[pallet::error]
@@ -76,6 +163,24 @@ pub mod module {
 		ConflictingAssetId,
 		/// Name or symbol is too long.
 		InvalidAssetString,
+		/// The asset still has issuance, so it can't be deregistered.
+		NonZeroIssuance,
+		/// The asset has reached `MaxAliases`.
+		TooManyAliases,
+		/// The location is not an alias of the asset.
+		AliasNotFound,
+		/// The enactment block of an update is not in the future.
+		InvalidEnactmentBlock,
+		/// The asset already has a pending update.
+		PendingUpdateExists,
+		/// The asset has no pending update.
+		NoPendingUpdate,
+		/// The enactment block has reached `MaxPendingUpdatesPerBlock`.
+		TooManyPendingUpdates,
+		/// The location of an owned asset can only be set by `AuthorityOrigin`.
+		LocationNotAllowed,
+		/// The origin is not the owner of the asset.
+		NotAssetOwner,
 	}
 
 	#[pallet::event]
@@ -89,6 +194,51 @@ pub mod module {
 			asset_id: T::AssetId,
 			metadata: AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
 		},
+		DeregisteredAsset {
+			asset_id: T::AssetId,
+			metadata: AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
+		},
+		RelocatedAsset {
+			asset_id: T::AssetId,
+			old_location: Option<VersionedLocation>,
+			new_location: VersionedLocation,
+		},
+		LocationAliasAdded {
+			asset_id: T::AssetId,
+			alias: VersionedLocation,
+		},
+		LocationAliasRemoved {
+			asset_id: T::AssetId,
+			alias: VersionedLocation,
+		},
+		CanonicalLocationChanged {
+			asset_id: T::AssetId,
+			old_location: Option<VersionedLocation>,
+			new_location: VersionedLocation,
+		},
+		FeePerSecondUpdated {
+			asset_id: T::AssetId,
+			fee_per_second: Option<u128>,
+		},
+		/// An update of the asset metadata is scheduled at `enact_at`.
+		PendingAssetUpdate {
+			asset_id: T::AssetId,
+			enact_at: BlockNumberFor<T>,
+			update: AssetUpdateOf<T>,
+		},
+		PendingAssetUpdateCancelled {
+			asset_id: T::AssetId,
+			enact_at: BlockNumberFor<T>,
+		},
+		/// A pending update couldn't be applied at its enactment block, and
+		/// was dropped.
+		PendingAssetUpdateFailed { asset_id: T::AssetId, error: DispatchError },
+		/// An asset is registered by `owner`, who reserved `deposit`.
+		OwnedAssetRegistered {
+			asset_id: T::AssetId,
+			owner: T::AccountId,
+			deposit: DepositBalanceOf<T>,
+		},
 	}
 
 	/// The metadata of an asset, indexed by asset id.
@@ -108,6 +258,45 @@ pub mod module {
 	#[pallet::getter(fn location_to_asset_id)]
 	pub type LocationToAssetId<T: Config> = StorageMap<_, Twox64Concat, Location, T::AssetId, OptionQuery>;
 
+	/// The alias locations of an asset, besides the canonical location in
+	/// its metadata. They are all mapped in `LocationToAssetId`, but only
+	/// the canonical location is used for outbound XCM.
+	#[pallet::storage]
+	#[pallet::getter(fn location_aliases)]
+	pub type LocationAliases<T: Config> =
+		StorageMap<_, Twox64Concat, T::AssetId, BoundedVec<VersionedLocation, T::MaxAliases>, ValueQuery>;
+
+	/// The units of an asset charged per second of execution weight, for
+	/// assets which can pay XCM fees. See `AssetRegistryFeePerSecond`.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_per_second)]
+	pub type FeePerSecond<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, u128, OptionQuery>;
+
+	/// The pending metadata update of an asset, and the block at which it
+	/// is applied.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_update)]
+	pub type PendingUpdates<T: Config> =
+		StorageMap<_, Twox64Concat, T::AssetId, (BlockNumberFor<T>, AssetUpdateOf<T>), OptionQuery>;
+
+	/// The assets with a pending update, indexed by enactment block.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_updates_at)]
+	pub type PendingUpdatesAt<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<T::AssetId, T::MaxPendingUpdatesPerBlock>,
+		ValueQuery,
+	>;
+
+	/// The owner of an asset registered with `register_owned_asset`, and
+	/// the deposit reserved from them.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_owner)]
+	pub type AssetOwners<T: Config> =
+		StorageMap<_, Twox64Concat, T::AssetId, (T::AccountId, DepositBalanceOf<T>), OptionQuery>;
+
 	/// The last processed asset id - used when assigning a sequential id.
 	#[pallet::storage]
 	#[pallet::getter(fn last_asset_id)]
@@ -147,6 +336,30 @@ pub mod module {
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let asset_ids = PendingUpdatesAt::<T>::take(now);
+			let count = asset_ids.len() as u64;
+			for asset_id in asset_ids {
+				if let Some((_, update)) = PendingUpdates::<T>::take(&asset_id) {
+					// revert partial changes if the update is no longer valid
+					let result = with_storage_layer(|| Self::do_apply_update(asset_id.clone(), update));
+					if let Err(error) = result {
+						Self::deposit_event(Event::<T>::PendingAssetUpdateFailed { asset_id, error });
+					}
+				}
+			}
+
+			// each update takes its pending update, and changes the metadata and at worst
+			// both the old and the new location of the asset
+			T::DbWeight::get().reads_writes(
+				count.saturating_mul(4).saturating_add(1),
+				count.saturating_mul(4).saturating_add(1),
+			)
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::call_index(0)]
@@ -161,6 +374,9 @@ pub mod module {
 			Self::do_register_asset(metadata, asset_id)
 		}
 
+		/// Update the metadata of an asset. If `enact_at` is given, the
+		/// update is scheduled and applied at the beginning of that block
+		/// instead. An asset has at most one pending update.
 		#[pallet::call_index(1)]
 		#[pallet::weight(T::WeightInfo::update_asset())]
 		pub fn update_asset(
@@ -172,21 +388,198 @@ pub mod module {
 			existential_deposit: Option<T::Balance>,
 			location: Option<Option<VersionedLocation>>,
 			additional: Option<T::CustomMetadata>,
+			enact_at: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin, &Some(asset_id.clone()))?;
+
+			match enact_at {
+				Some(enact_at) => Self::do_schedule_update(
+					asset_id,
+					enact_at,
+					AssetUpdate {
+						decimals,
+						name,
+						symbol,
+						existential_deposit,
+						location,
+						additional,
+					},
+				),
+				None => Self::do_update_asset(
+					asset_id,
+					decimals,
+					name,
+					symbol,
+					existential_deposit,
+					location,
+					additional,
+				),
+			}
+		}
+
+		/// Remove an asset and its location from the registry. It fails if
+		/// the asset still has issuance.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::deregister_asset())]
+		pub fn deregister_asset(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin, &Some(asset_id.clone()))?;
+
+			Self::do_deregister_asset(asset_id)
+		}
+
+		/// Move an asset to `new_location`, replacing the mapping of its old
+		/// location.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::relocate_asset())]
+		pub fn relocate_asset(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			new_location: Box<VersionedLocation>,
+		) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin, &Some(asset_id.clone()))?;
+
+			Self::do_relocate_asset(asset_id, *new_location)
+		}
+
+		/// Add an alias location of an asset, so it's also recognized
+		/// through `alias`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::add_location_alias())]
+		pub fn add_location_alias(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			alias: Box<VersionedLocation>,
+		) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin, &Some(asset_id.clone()))?;
+
+			Self::do_add_location_alias(asset_id, *alias)
+		}
+
+		/// Remove an alias location of an asset.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::remove_location_alias())]
+		pub fn remove_location_alias(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			alias: Box<VersionedLocation>,
+		) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin, &Some(asset_id.clone()))?;
+
+			Self::do_remove_location_alias(asset_id, *alias)
+		}
+
+		/// Make an alias location of an asset its canonical location. The
+		/// previous canonical location becomes an alias.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::set_canonical_location())]
+		pub fn set_canonical_location(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			alias: Box<VersionedLocation>,
+		) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin, &Some(asset_id.clone()))?;
+
+			Self::do_set_canonical_location(asset_id, *alias)
+		}
+
+		/// Set the units of an asset charged per second of execution weight,
+		/// or `None` to stop accepting it for XCM fees.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::set_fee_per_second())]
+		pub fn set_fee_per_second(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			fee_per_second: Option<u128>,
 		) -> DispatchResult {
 			T::AuthorityOrigin::ensure_origin(origin, &Some(asset_id.clone()))?;
+			ensure!(Metadata::<T>::contains_key(&asset_id), Error::<T>::AssetNotFound);
 
-			Self::do_update_asset(
+			FeePerSecond::<T>::set(&asset_id, fee_per_second);
+			Self::deposit_event(Event::<T>::FeePerSecondUpdated {
 				asset_id,
-				decimals,
-				name,
-				symbol,
-				existential_deposit,
-				location,
-				additional,
-			)?;
+				fee_per_second,
+			});
+
+			Ok(())
+		}
+
+		/// Cancel the pending metadata update of an asset.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::cancel_pending_update())]
+		pub fn cancel_pending_update(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin, &Some(asset_id.clone()))?;
+
+			let enact_at = Self::do_cancel_pending_update(&asset_id)?;
+			Self::deposit_event(Event::<T>::PendingAssetUpdateCancelled { asset_id, enact_at });
 
 			Ok(())
 		}
+
+		/// Register an asset without a location, reserving
+		/// `RegistrationDeposit` from the origin. The origin becomes the
+		/// owner of the asset, and can update its name, symbol and
+		/// additional metadata. Its decimals and existential deposit are set
+		/// by `OwnedAssetMetadata`, and, like its location and the
+		/// governance-controlled fields of the additional metadata, only
+		/// updated by `AuthorityOrigin`. The deposit is returned when the
+		/// asset is deregistered, by its owner with `deregister_owned_asset`
+		/// or by `AuthorityOrigin`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::register_owned_asset())]
+		pub fn register_owned_asset(
+			origin: OriginFor<T>,
+			metadata: AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_register_owned_asset(who, metadata)
+		}
+
+		/// Update the name, symbol or additional metadata of an asset owned
+		/// by the origin. The governance-controlled fields of the additional
+		/// metadata are kept.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::update_owned_asset())]
+		pub fn update_owned_asset(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			name: Option<BoundedVec<u8, T::StringLimit>>,
+			symbol: Option<BoundedVec<u8, T::StringLimit>>,
+			additional: Option<T::CustomMetadata>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				matches!(AssetOwners::<T>::get(&asset_id), Some((owner, _)) if owner == who),
+				Error::<T>::NotAssetOwner
+			);
+			let additional = match additional {
+				Some(additional) => {
+					let metadata = Metadata::<T>::get(&asset_id).ok_or(Error::<T>::AssetNotFound)?;
+					Some(T::OwnedAssetMetadata::filter_additional(
+						additional,
+						Some(&metadata.additional),
+					))
+				}
+				None => None,
+			};
+
+			Self::do_update_asset(asset_id, None, name, symbol, None, None, additional)
+		}
+
+		/// Remove an asset owned by the origin from the registry, returning
+		/// its registration deposit. It fails if the asset still has
+		/// issuance.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::deregister_owned_asset())]
+		pub fn deregister_owned_asset(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				matches!(AssetOwners::<T>::get(&asset_id), Some((owner, _)) if owner == who),
+				Error::<T>::NotAssetOwner
+			);
+
+			Self::do_deregister_asset(asset_id)
+		}
 	}
 }
 
@@ -278,6 +671,218 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Register an asset owned by `who`, reserving `RegistrationDeposit`.
+	/// The fields controlled by governance are set by `OwnedAssetMetadata`.
+	pub fn do_register_owned_asset(
+		who: T::AccountId,
+		metadata: AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
+	) -> DispatchResult {
+		ensure!(metadata.location.is_none(), Error::<T>::LocationNotAllowed);
+		let metadata = AssetMetadata {
+			decimals: T::OwnedAssetMetadata::decimals(),
+			existential_deposit: T::OwnedAssetMetadata::existential_deposit(),
+			additional: T::OwnedAssetMetadata::filter_additional(metadata.additional, None),
+			..metadata
+		};
+
+		let deposit = T::RegistrationDeposit::get();
+		T::Currency::reserve(&who, deposit)?;
+
+		let (asset_id, metadata) = T::AssetProcessor::pre_register(None, metadata)?;
+		Self::do_register_asset_without_asset_processor(metadata.clone(), asset_id.clone())?;
+		AssetOwners::<T>::insert(&asset_id, (who.clone(), deposit));
+		T::AssetProcessor::post_register(asset_id.clone(), metadata)?;
+
+		Self::deposit_event(Event::<T>::OwnedAssetRegistered {
+			asset_id,
+			owner: who,
+			deposit,
+		});
+
+		Ok(())
+	}
+
+	/// Schedule an update of the metadata of an asset at `enact_at`.
+	pub fn do_schedule_update(
+		asset_id: T::AssetId,
+		enact_at: BlockNumberFor<T>,
+		update: AssetUpdateOf<T>,
+	) -> DispatchResult {
+		ensure!(
+			enact_at > frame_system::Pallet::<T>::block_number(),
+			Error::<T>::InvalidEnactmentBlock
+		);
+		ensure!(Metadata::<T>::contains_key(&asset_id), Error::<T>::AssetNotFound);
+		ensure!(
+			!PendingUpdates::<T>::contains_key(&asset_id),
+			Error::<T>::PendingUpdateExists
+		);
+		if let Some(Some(ref location)) = update.location {
+			let _: Location = location.clone().try_into().map_err(|()| Error::<T>::BadVersion)?;
+		}
+
+		PendingUpdatesAt::<T>::try_mutate(enact_at, |asset_ids| {
+			asset_ids
+				.try_push(asset_id.clone())
+				.map_err(|_| Error::<T>::TooManyPendingUpdates)
+		})?;
+		PendingUpdates::<T>::insert(&asset_id, (enact_at, update.clone()));
+
+		Self::deposit_event(Event::<T>::PendingAssetUpdate {
+			asset_id,
+			enact_at,
+			update,
+		});
+
+		Ok(())
+	}
+
+	/// Remove the pending update of an asset, returning its enactment
+	/// block.
+	pub fn do_cancel_pending_update(asset_id: &T::AssetId) -> Result<BlockNumberFor<T>, DispatchError> {
+		let (enact_at, _) = PendingUpdates::<T>::take(asset_id).ok_or(Error::<T>::NoPendingUpdate)?;
+		PendingUpdatesAt::<T>::mutate_exists(enact_at, |maybe_asset_ids| {
+			if let Some(asset_ids) = maybe_asset_ids {
+				asset_ids.retain(|id| id != asset_id);
+				if asset_ids.is_empty() {
+					*maybe_asset_ids = None;
+				}
+			}
+		});
+
+		Ok(enact_at)
+	}
+
+	fn do_apply_update(asset_id: T::AssetId, update: AssetUpdateOf<T>) -> DispatchResult {
+		Self::do_update_asset(
+			asset_id,
+			update.decimals,
+			update.name,
+			update.symbol,
+			update.existential_deposit,
+			update.location,
+			update.additional,
+		)
+	}
+
+	pub fn do_deregister_asset(asset_id: T::AssetId) -> DispatchResult {
+		ensure!(T::TotalIssuance::get(&asset_id).is_zero(), Error::<T>::NonZeroIssuance);
+
+		let metadata = Metadata::<T>::take(&asset_id).ok_or(Error::<T>::AssetNotFound)?;
+		Self::do_update_location(asset_id.clone(), metadata.location.clone(), None)?;
+		for alias in LocationAliases::<T>::take(&asset_id) {
+			Self::do_update_location(asset_id.clone(), Some(alias), None)?;
+		}
+		FeePerSecond::<T>::remove(&asset_id);
+		// a pending update of a deregistered asset would fail anyway
+		let _ = Self::do_cancel_pending_update(&asset_id);
+		if let Some((owner, deposit)) = AssetOwners::<T>::take(&asset_id) {
+			T::Currency::unreserve(&owner, deposit);
+		}
+
+		Self::deposit_event(Event::<T>::DeregisteredAsset { asset_id, metadata });
+
+		Ok(())
+	}
+
+	pub fn do_relocate_asset(asset_id: T::AssetId, new_location: VersionedLocation) -> DispatchResult {
+		let location: Location = new_location.clone().try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+		Metadata::<T>::try_mutate(&asset_id, |maybe_metadata| -> DispatchResult {
+			let metadata = maybe_metadata.as_mut().ok_or(Error::<T>::AssetNotFound)?;
+			// an alias must be made canonical with `set_canonical_location` instead
+			ensure!(
+				metadata.location.as_ref() == Some(&new_location) || !LocationToAssetId::<T>::contains_key(location),
+				Error::<T>::ConflictingLocation
+			);
+			let old_location = metadata.location.replace(new_location.clone());
+			Self::do_update_location(asset_id.clone(), old_location.clone(), Some(new_location.clone()))?;
+
+			Self::deposit_event(Event::<T>::RelocatedAsset {
+				asset_id: asset_id.clone(),
+				old_location,
+				new_location,
+			});
+
+			Ok(())
+		})
+	}
+
+	pub fn do_add_location_alias(asset_id: T::AssetId, alias: VersionedLocation) -> DispatchResult {
+		ensure!(Metadata::<T>::contains_key(&asset_id), Error::<T>::AssetNotFound);
+
+		LocationAliases::<T>::try_mutate(&asset_id, |aliases| -> DispatchResult {
+			aliases
+				.try_push(alias.clone())
+				.map_err(|_| Error::<T>::TooManyAliases)?;
+			Self::do_insert_location(asset_id.clone(), alias.clone())
+		})?;
+
+		Self::deposit_event(Event::<T>::LocationAliasAdded { asset_id, alias });
+
+		Ok(())
+	}
+
+	pub fn do_remove_location_alias(asset_id: T::AssetId, alias: VersionedLocation) -> DispatchResult {
+		LocationAliases::<T>::try_mutate_exists(&asset_id, |maybe_aliases| -> DispatchResult {
+			let aliases = maybe_aliases.as_mut().ok_or(Error::<T>::AliasNotFound)?;
+			let index = aliases
+				.iter()
+				.position(|a| *a == alias)
+				.ok_or(Error::<T>::AliasNotFound)?;
+			aliases.remove(index);
+			if aliases.is_empty() {
+				*maybe_aliases = None;
+			}
+			Self::do_update_location(asset_id.clone(), Some(alias.clone()), None)
+		})?;
+
+		Self::deposit_event(Event::<T>::LocationAliasRemoved { asset_id, alias });
+
+		Ok(())
+	}
+
+	pub fn do_set_canonical_location(asset_id: T::AssetId, alias: VersionedLocation) -> DispatchResult {
+		Metadata::<T>::try_mutate(&asset_id, |maybe_metadata| -> DispatchResult {
+			let metadata = maybe_metadata.as_mut().ok_or(Error::<T>::AssetNotFound)?;
+			LocationAliases::<T>::try_mutate_exists(&asset_id, |maybe_aliases| -> DispatchResult {
+				let aliases = maybe_aliases.as_mut().ok_or(Error::<T>::AliasNotFound)?;
+				let index = aliases
+					.iter()
+					.position(|a| *a == alias)
+					.ok_or(Error::<T>::AliasNotFound)?;
+
+				// both stay mapped in `LocationToAssetId`, only their roles are swapped
+				let old_location = metadata.location.replace(alias.clone());
+				match old_location.clone() {
+					Some(old_location) => aliases[index] = old_location,
+					None => {
+						aliases.remove(index);
+					}
+				}
+				if aliases.is_empty() {
+					*maybe_aliases = None;
+				}
+
+				Self::deposit_event(Event::<T>::CanonicalLocationChanged {
+					asset_id: asset_id.clone(),
+					old_location,
+					new_location: alias,
+				});
+
+				Ok(())
+			})
+		})
+	}
+
+	/// The alias locations of an asset, besides its canonical location.
+	pub fn aliases(asset_id: &T::AssetId) -> Vec<Location> {
+		LocationAliases::<T>::get(asset_id)
+			.into_iter()
+			.filter_map(|alias| alias.try_into().ok())
+			.collect()
+	}
+
 	pub fn fetch_metadata_by_location(
 		location: &Location,
 	) -> Option<AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>> {
@@ -295,6 +900,31 @@ impl<T: Config> Pallet<T> {
 			.transpose()
 	}
 
+	/// The asset at `location`, of any supported XCM version, and its
+	/// metadata.
+	pub fn metadata_by_versioned_location(
+		location: VersionedLocation,
+	) -> Option<(T::AssetId, DefaultAssetMetadata<T>)> {
+		let location: Location = location.try_into().ok()?;
+		let asset_id = LocationToAssetId::<T>::get(location)?;
+		Metadata::<T>::get(&asset_id).map(|metadata| (asset_id, metadata))
+	}
+
+	/// Up to `limit` assets and their metadata, following `start_after` (or
+	/// from the beginning if `None`) in storage order.
+	pub fn assets(start_after: Option<T::AssetId>, limit: u32) -> Vec<(T::AssetId, DefaultAssetMetadata<T>)> {
+		let iter = match start_after {
+			Some(asset_id) => Metadata::<T>::iter_from(Metadata::<T>::hashed_key_for(asset_id)),
+			None => Metadata::<T>::iter(),
+		};
+		iter.take(limit as usize).collect()
+	}
+
+	/// The location of an asset, converted to XCM `version`.
+	pub fn versioned_location(asset_id: &T::AssetId, version: xcm::Version) -> Option<VersionedLocation> {
+		Metadata::<T>::get(asset_id)?.location?.into_version(version).ok()
+	}
+
 	/// update LocationToAssetId mapping if the location changed
 	fn do_update_location(
 		asset_id: T::AssetId,
@@ -303,10 +933,15 @@ impl<T: Config> Pallet<T> {
 	) -> DispatchResult {
 		// Update `LocationToAssetId` only if location changed
 		if new_location != old_location {
-			// remove the old location lookup if it exists
+			// remove the old location lookup if it exists, and still points to
+			// this asset
 			if let Some(ref old_location) = old_location {
 				let location: Location = old_location.clone().try_into().map_err(|()| Error::<T>::BadVersion)?;
-				LocationToAssetId::<T>::remove(location);
+				LocationToAssetId::<T>::mutate_exists(location, |maybe_asset_id| {
+					if maybe_asset_id.as_ref() == Some(&asset_id) {
+						*maybe_asset_id = None;
+					}
+				});
 			}
 
 			// insert new location
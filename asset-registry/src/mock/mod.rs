@@ -2,16 +2,15 @@
 
 use super::*;
 
-use mock::para::AssetRegistry;
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 use sp_core::bounded::BoundedVec;
 use sp_io::TestExternalities;
-use sp_runtime::{traits::Convert, AccountId32, BuildStorage};
-use xcm::{
-	v3,
-	v5::{Asset, Location},
+use sp_runtime::{
+	traits::{Convert, MaybeEquivalence},
+	AccountId32, BuildStorage,
 };
+use xcm::{v3, v5::Location};
 use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain, TestExt};
 
 pub mod para;
@@ -55,8 +54,9 @@ pub enum CurrencyId {
 	RegisteredAsset(u32),
 }
 
-pub struct CurrencyIdConvert;
-impl Convert<CurrencyId, Option<Location>> for CurrencyIdConvert {
+/// Converts the currencies which aren't registered assets.
+pub struct NativeCurrencyIdConvert;
+impl Convert<CurrencyId, Option<Location>> for NativeCurrencyIdConvert {
 	fn convert(id: CurrencyId) -> Option<Location> {
 		let loc: Option<v3::Location> = match id {
 			CurrencyId::R => Some(Parent.try_into().unwrap()),
@@ -108,12 +108,12 @@ impl Convert<CurrencyId, Option<Location>> for CurrencyIdConvert {
 				)
 					.into(),
 			),
-			CurrencyId::RegisteredAsset(id) => AssetRegistry::location(&id).unwrap_or_default(),
+			CurrencyId::RegisteredAsset(_) => None,
 		};
 		loc.and_then(|l| l.into_versioned().try_into().ok())
 	}
 }
-impl Convert<Location, Option<CurrencyId>> for CurrencyIdConvert {
+impl Convert<Location, Option<CurrencyId>> for NativeCurrencyIdConvert {
 	fn convert(l: Location) -> Option<CurrencyId> {
 		use xcm::v5::Junction::*;
 
@@ -147,27 +147,29 @@ impl Convert<Location, Option<CurrencyId>> for CurrencyIdConvert {
 			},
 			_ => None,
 		};
-		currency_id.or_else(|| {
-			let loc = v3::Location::try_from(l.into_versioned()).ok()?;
-			AssetRegistry::location_to_asset_id(&loc).map(CurrencyId::RegisteredAsset)
-		})
+		currency_id
 	}
 }
-impl Convert<Asset, Option<CurrencyId>> for CurrencyIdConvert {
-	fn convert(a: Asset) -> Option<CurrencyId> {
-		use xcm::v5::prelude::*;
-		if let Asset {
-			fun: Fungible(_),
-			id: AssetId(id),
-		} = a
-		{
-			Self::convert(id)
-		} else {
-			Option::None
+/// Maps `CurrencyId::RegisteredAsset` to the ids of the asset registry.
+pub struct RegisteredAssetId;
+impl MaybeEquivalence<CurrencyId, u32> for RegisteredAssetId {
+	fn convert(currency_id: &CurrencyId) -> Option<u32> {
+		match currency_id {
+			CurrencyId::RegisteredAsset(asset_id) => Some(*asset_id),
+			_ => None,
 		}
 	}
+
+	fn convert_back(asset_id: &u32) -> Option<CurrencyId> {
+		Some(CurrencyId::RegisteredAsset(*asset_id))
+	}
 }
 
+pub type CurrencyIdConvert =
+	AssetRegistryLocationToCurrencyId<para::Runtime, RegisteredAssetId, NativeCurrencyIdConvert>;
+pub type CurrencyIdToLocation =
+	AssetRegistryCurrencyIdToLocation<para::Runtime, RegisteredAssetId, NativeCurrencyIdConvert>;
+
 pub type Balance = u128;
 pub type Amount = i128;
 
@@ -214,6 +216,7 @@ decl_test_parachain! {
 				location: None,
 				additional: para::CustomMetadata {
 					fee_per_second: 1_000_000_000_000,
+					min_xcm_fee: None,
 				},
 			})),
 			(
@@ -226,6 +229,7 @@ decl_test_parachain! {
 				location: None,
 				additional: para::CustomMetadata {
 					fee_per_second: 1_000_000_000_000,
+					min_xcm_fee: None,
 				},
 			}))], 5
 		))),
@@ -1,8 +1,12 @@
-use super::{Amount, Balance, CurrencyId, CurrencyIdConvert, ParachainXcmRouter};
+use super::{
+	Amount, Balance, CurrencyId, CurrencyIdConvert, CurrencyIdToLocation, ParachainXcmRouter, RegisteredAssetId,
+};
 
 use crate as orml_asset_registry;
 
-use crate::impls::{AssetRegistryTrader, FixedRateAssetRegistryTrader};
+use crate::impls::{
+	AssetRegistryExistentialDeposits, AssetRegistryMinXcmFee, AssetRegistryTrader, FixedRateAssetRegistryTrader,
+};
 use cumulus_primitives_core::AggregateMessageOrigin;
 use frame_support::{
 	construct_runtime, derive_impl, ord_parameter_types, parameter_types,
@@ -10,8 +14,10 @@ use frame_support::{
 	PalletId,
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
-use orml_traits::{parameter_type_with_key, FixedConversionRateProvider, MultiCurrency};
-use orml_xcm_support::{IsNativeConcrete, MultiCurrencyAdapter, MultiNativeAsset};
+use orml_traits::{
+	asset_registry::XcmCustomMetadata, parameter_type_with_key, FixedConversionRateProvider, MultiCurrency,
+};
+use orml_xcm_support::{DisabledAssetRoutes, IsNativeConcrete, MultiCurrencyAdapter, MultiNativeAsset};
 use orml_xtokens::{AbsoluteReserveProvider, RelativeReserveProvider};
 use pallet_xcm::XcmPassthrough;
 use parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
@@ -19,7 +25,7 @@ use polkadot_parachain_primitives::primitives::Sibling;
 use sp_core::Get;
 use sp_runtime::{
 	traits::{AccountIdConversion, Convert, IdentityLookup},
-	AccountId32,
+	AccountId32, BoundedVec,
 };
 use xcm::v5::{prelude::*, Weight};
 use xcm_builder::{
@@ -56,16 +62,14 @@ impl pallet_balances::Config for Runtime {
 	type DoneSlashHandler = ();
 }
 
-use orml_asset_registry::impls::ExistentialDeposits as AssetRegistryExistentialDeposits;
 parameter_type_with_key! {
-	pub ExistentialDeposits: |currency_id: CurrencyId| -> Balance {
-		match currency_id {
-			CurrencyId::RegisteredAsset(asset_id) => AssetRegistryExistentialDeposits::<Runtime>::get(asset_id),
-			_ => Default::default()
-		}
+	pub NativeExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
 	};
 }
 
+pub type ExistentialDeposits = AssetRegistryExistentialDeposits<Runtime, RegisteredAssetId, NativeExistentialDeposits>;
+
 impl orml_tokens::Config for Runtime {
 	type Balance = Balance;
 	type Amount = Amount;
@@ -84,6 +88,13 @@ impl orml_tokens::Config for Runtime {
 #[derive(scale_info::TypeInfo, Encode, Decode, Clone, Eq, PartialEq, Debug, MaxEncodedLen, DecodeWithMemTracking)]
 pub struct CustomMetadata {
 	pub fee_per_second: u128,
+	pub min_xcm_fee: Option<u128>,
+}
+
+impl XcmCustomMetadata for CustomMetadata {
+	fn min_xcm_fee(&self) -> Option<u128> {
+		self.min_xcm_fee
+	}
 }
 
 const ADMIN_ASSET_TWO: AccountId = AccountId32::new([42u8; 32]);
@@ -118,6 +129,12 @@ impl EnsureOriginWithArg<RuntimeOrigin, Option<u32>> for AssetAuthority {
 
 pub type ParaAssetId = u32;
 
+parameter_type_with_key! {
+	pub AssetTotalIssuance: |asset_id: ParaAssetId| -> Balance {
+		Tokens::total_issuance(CurrencyId::RegisteredAsset(*asset_id))
+	};
+}
+
 parameter_types! {
 	pub const StringLimit: u32 = 50;
 }
@@ -129,7 +146,46 @@ impl orml_asset_registry::Config for Runtime {
 	type CustomMetadata = CustomMetadata;
 	type AssetProcessor = orml_asset_registry::SequentialId<Runtime>;
 	type StringLimit = StringLimit;
+	type TotalIssuance = AssetTotalIssuance;
+	type MaxAliases = ConstU32<2>;
+	type MaxPendingUpdatesPerBlock = ConstU32<2>;
+	type Currency = Balances;
+	type RegistrationDeposit = ConstU128<100>;
+	type OwnedAssetMetadata = ParaOwnedAssetMetadata;
 	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ParaBenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct ParaBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl orml_asset_registry::BenchmarkHelper<CustomMetadata> for ParaBenchmarkHelper {
+	fn custom_metadata() -> Option<CustomMetadata> {
+		Some(CustomMetadata {
+			fee_per_second: 0,
+			min_xcm_fee: None,
+		})
+	}
+}
+
+pub struct ParaOwnedAssetMetadata;
+impl orml_asset_registry::OwnedAssetMetadata<Balance, CustomMetadata> for ParaOwnedAssetMetadata {
+	fn decimals() -> u32 {
+		18
+	}
+
+	fn existential_deposit() -> Balance {
+		10
+	}
+
+	// all fields of the custom metadata are XCM fees
+	fn filter_additional(_additional: CustomMetadata, governed: Option<&CustomMetadata>) -> CustomMetadata {
+		governed.cloned().unwrap_or(CustomMetadata {
+			fee_per_second: 0,
+			min_xcm_fee: None,
+		})
+	}
 }
 
 parameter_types! {
@@ -297,6 +353,7 @@ impl Contains<Location> for ParentOrParachains {
 			location.unpack(),
 			(0, [Junction::AccountId32 { .. }])
 				| (1, [Junction::AccountId32 { .. }])
+				| (1, [Parachain(_)])
 				| (1, [Parachain(1), Junction::AccountId32 { .. }])
 				| (1, [Parachain(2), Junction::AccountId32 { .. }])
 				| (1, [Parachain(3), Junction::AccountId32 { .. }])
@@ -316,14 +373,25 @@ parameter_type_with_key! {
 	};
 }
 
+parameter_type_with_key! {
+	pub ChainFeeAssets: |location: Location| -> Option<Location> {
+		match location.unpack() {
+			// parachain 2 charges fees in the first asset registered with a location of this chain
+			(1, [Parachain(2)]) => Some(Location::new(0, [Junction::from(BoundedVec::try_from(vec![0]).unwrap())])),
+			_ => None,
+		}
+	};
+}
+
 impl orml_xtokens::Config for Runtime {
 	type Balance = Balance;
 	type CurrencyId = CurrencyId;
-	type CurrencyIdConvert = CurrencyIdConvert;
+	type CurrencyIdConvert = CurrencyIdToLocation;
 	type AccountIdToLocation = AccountIdToLocation;
 	type SelfLocation = SelfLocation;
 	type LocationsFilter = ParentOrParachains;
-	type MinXcmFee = ParachainMinFee;
+	type TeleportFilter = ();
+	type MinXcmFee = AssetRegistryMinXcmFee<Runtime, ChainFeeAssets, ParachainMinFee>;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
 	type BaseXcmWeight = BaseXcmWeight;
@@ -332,10 +400,26 @@ impl orml_xtokens::Config for Runtime {
 	type ReserveProvider = RelativeReserveProvider;
 	type RateLimiter = ();
 	type RateLimiterId = ();
+	type AssetRoutes = DisabledAssetRoutes;
+	type FeeConverter = ();
+	type FeeSwap = ();
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+parameter_types! {
+	pub const TransactDescendOrigin: InteriorLocation = Here;
 }
 
 impl orml_xcm::Config for Runtime {
 	type SovereignOrigin = EnsureRoot<AccountId>;
+	type ResponseOrigin = pallet_xcm::EnsureResponse<Everything>;
+	type TransactDescendOrigin = TransactDescendOrigin;
+	type RuntimeCall = RuntimeCall;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 }
 
 impl orml_xcm_mock_message_queue::Config for Runtime {
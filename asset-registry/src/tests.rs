@@ -4,20 +4,30 @@ use super::mock;
 use crate as orml_asset_registry;
 use crate::{
 	tests::para::{AdminAssetTwo, AssetRegistry, CustomMetadata, RuntimeOrigin, Tokens, TreasuryAccount},
-	Error, LastAssetId, Metadata,
+	AssetRegistryFeePerSecond, AssetRegistryFeeTrader, AssetUpdate, Error, LastAssetId, Metadata,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement},
+	weights::constants::WEIGHT_REF_TIME_PER_SECOND,
 };
-use frame_support::{assert_noop, assert_ok, pallet_prelude::*};
 use mock::{para::RuntimeCall, *};
-use orml_traits::asset_registry::AssetMetadata;
-use orml_traits::MultiCurrency;
+use orml_asset_registry_runtime_api::AssetRegistryApi;
+use orml_traits::asset_registry::{AssetMetadata, FixedConversionRateProvider, Inspect};
+use orml_traits::{GetByKey, MultiCurrency};
 use polkadot_parachain_primitives::primitives::Sibling;
 use scale_info::TypeInfo;
 
 use sp_runtime::{
-	traits::{AccountIdConversion, BadOrigin, Dispatchable},
+	traits::{AccountIdConversion, BadOrigin, Convert, Dispatchable},
 	AccountId32,
 };
-use xcm::{v3, v5::prelude::*, VersionedLocation};
+use xcm::{v3, v5::prelude::*, VersionedLocation, VersionedXcm};
+use xcm_executor::{
+	traits::{TransferType, WeightTrader},
+	AssetsInHolding,
+};
 use xcm_simulator::TestExt;
 
 fn treasury_account() -> AccountId32 {
@@ -64,10 +74,45 @@ fn dummy_metadata() -> AssetMetadata<
 		location: Some(loc),
 		additional: CustomMetadata {
 			fee_per_second: 1_000_000_000_000,
+			min_xcm_fee: None,
 		},
 	}
 }
 
+type ParaAssetMetadata = AssetMetadata<
+	<para::Runtime as orml_asset_registry::Config>::Balance,
+	CustomMetadata,
+	<para::Runtime as orml_asset_registry::Config>::StringLimit,
+>;
+
+/// Register an asset with `dummy_metadata` at `location`, and return its id.
+fn register_asset_at(location: Option<VersionedLocation>) -> u32 {
+	assert_ok!(AssetRegistry::register_asset(
+		RuntimeOrigin::root(),
+		AssetMetadata {
+			location,
+			..dummy_metadata()
+		},
+		None
+	));
+	LastAssetId::<para::Runtime>::get()
+}
+
+/// Schedule an update of the decimals of `asset_id` at `enact_at`.
+fn schedule_decimals(asset_id: u32, decimals: u32, enact_at: u64) -> DispatchResult {
+	AssetRegistry::update_asset(
+		RuntimeOrigin::root(),
+		asset_id,
+		Some(decimals),
+		None,
+		None,
+		None,
+		None,
+		None,
+		Some(enact_at),
+	)
+}
+
 #[test]
 fn genesis_issuance_should_work() {
 	TestNet::reset();
@@ -81,6 +126,7 @@ fn genesis_issuance_should_work() {
 			location: None,
 			additional: CustomMetadata {
 				fee_per_second: 1_000_000_000_000,
+				min_xcm_fee: None,
 			},
 		};
 		let metadata2 = AssetMetadata {
@@ -91,6 +137,7 @@ fn genesis_issuance_should_work() {
 			location: None,
 			additional: CustomMetadata {
 				fee_per_second: 1_000_000_000_000,
+				min_xcm_fee: None,
 			},
 		};
 		assert_eq!(AssetRegistry::metadata(4).unwrap(), metadata1);
@@ -366,7 +413,9 @@ fn test_fixed_rate_asset_trader() {
 			None,
 			Some(CustomMetadata {
 				fee_per_second: metadata.additional.fee_per_second * 2,
+				min_xcm_fee: None,
 			}),
+			None,
 		)
 		.unwrap();
 	});
@@ -409,6 +458,217 @@ fn test_fixed_rate_asset_trader() {
 	});
 }
 
+/// A tenth of a second of execution weight.
+fn tenth_of_second() -> Weight {
+	Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND / 10, 0)
+}
+
+fn payment(location: &Location, amount: u128) -> AssetsInHolding {
+	Asset::from((location.clone(), amount)).into()
+}
+
+fn treasury_fee_balance() -> Balance {
+	ParaTokens::free_balance(CurrencyId::RegisteredAsset(1), &treasury_account())
+}
+
+type FeeTrader = AssetRegistryFeeTrader<para::Runtime, para::ToTreasury>;
+
+/// Register asset 1 with an alias, charging 1_000 per second of execution
+/// weight, and return its canonical location and alias.
+fn register_fee_asset() -> (Location, Location) {
+	let location = dummy_metadata().location.unwrap();
+	register_asset_at(Some(location.clone()));
+	assert_ok!(AssetRegistry::add_location_alias(
+		RuntimeOrigin::root(),
+		1,
+		Box::new(alias_location(1))
+	));
+	assert_ok!(AssetRegistry::set_fee_per_second(RuntimeOrigin::root(), 1, Some(1_000)));
+	(location.try_into().unwrap(), alias_location(1).try_into().unwrap())
+}
+
+#[test]
+fn set_fee_per_second_requires_authority_and_registered_asset() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		register_asset_at(dummy_metadata().location);
+
+		assert_noop!(
+			AssetRegistry::set_fee_per_second(RuntimeOrigin::signed(ALICE), 1, Some(1_000)),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetRegistry::set_fee_per_second(RuntimeOrigin::root(), 4, Some(1_000)),
+			Error::<para::Runtime>::AssetNotFound
+		);
+	});
+}
+
+#[test]
+fn fee_per_second_applies_to_all_locations_of_asset() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let (location, alias) = register_fee_asset();
+		para::System::assert_last_event(para::RuntimeEvent::AssetRegistry(
+			orml_asset_registry::Event::FeePerSecondUpdated {
+				asset_id: 1,
+				fee_per_second: Some(1_000),
+			},
+		));
+		assert_eq!(
+			AssetRegistryFeePerSecond::<para::Runtime>::get_fee_per_second(&location),
+			Some(1_000)
+		);
+		assert_eq!(
+			AssetRegistryFeePerSecond::<para::Runtime>::get_fee_per_second(&alias),
+			Some(1_000)
+		);
+
+		assert_ok!(AssetRegistry::set_fee_per_second(RuntimeOrigin::root(), 1, None));
+		para::System::assert_last_event(para::RuntimeEvent::AssetRegistry(
+			orml_asset_registry::Event::FeePerSecondUpdated {
+				asset_id: 1,
+				fee_per_second: None,
+			},
+		));
+		assert_eq!(AssetRegistry::fee_per_second(1), None);
+		assert_eq!(
+			AssetRegistryFeePerSecond::<para::Runtime>::get_fee_per_second(&alias),
+			None
+		);
+	});
+}
+
+#[test]
+fn fee_trader_only_accepts_assets_with_fee_per_second() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let location: Location = dummy_metadata().location.unwrap().try_into().unwrap();
+		register_asset_at(dummy_metadata().location);
+		let ctx = XcmContext::with_message_id([0; 32]);
+
+		let mut trader = FeeTrader::new();
+		assert!(matches!(
+			trader.buy_weight(tenth_of_second(), payment(&location, 1_000), &ctx),
+			Err(XcmError::TooExpensive)
+		));
+
+		assert_ok!(AssetRegistry::set_fee_per_second(RuntimeOrigin::root(), 1, Some(1_000)));
+		let unused = trader
+			.buy_weight(tenth_of_second(), payment(&location, 1_000), &ctx)
+			.unwrap();
+		assert_eq!(unused.fungible.get(&AssetId(location)), Some(&900));
+	});
+}
+
+#[test]
+fn fee_trader_charges_fee_per_second_and_refunds_unused_weight() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let (location, _) = register_fee_asset();
+		let ctx = XcmContext::with_message_id([0; 32]);
+
+		let mut trader = FeeTrader::new();
+		let unused = trader
+			.buy_weight(tenth_of_second(), payment(&location, 1_000), &ctx)
+			.unwrap();
+		assert_eq!(unused.fungible.get(&AssetId(location.clone())), Some(&900));
+
+		// half of the weight is unused
+		assert_eq!(
+			trader.refund_weight(Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND / 20, 0), &ctx),
+			Some((location, 50u128).into())
+		);
+
+		// the rest is taken as revenue
+		drop(trader);
+		assert_eq!(treasury_fee_balance(), 50);
+	});
+}
+
+#[test]
+fn fee_trader_rejects_insufficient_payment() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let (location, _) = register_fee_asset();
+		let ctx = XcmContext::with_message_id([0; 32]);
+
+		let mut trader = FeeTrader::new();
+		assert!(matches!(
+			trader.buy_weight(tenth_of_second(), payment(&location, 99), &ctx),
+			Err(XcmError::TooExpensive)
+		));
+
+		// the exact fee is enough
+		let unused = trader
+			.buy_weight(tenth_of_second(), payment(&location, 100), &ctx)
+			.unwrap();
+		assert!(unused.fungible.is_empty());
+
+		drop(trader);
+		assert_eq!(treasury_fee_balance(), 100);
+	});
+}
+
+#[test]
+fn fee_trader_accepts_payment_through_alias() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let (_, alias) = register_fee_asset();
+		let ctx = XcmContext::with_message_id([0; 32]);
+
+		let mut trader = FeeTrader::new();
+		let unused = trader
+			.buy_weight(tenth_of_second(), payment(&alias, 1_000), &ctx)
+			.unwrap();
+		assert_eq!(unused.fungible.get(&AssetId(alias.clone())), Some(&900));
+
+		// refunds are made in the location paid with
+		assert_eq!(
+			trader.refund_weight(Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND / 20, 0), &ctx),
+			Some((alias, 50u128).into())
+		);
+
+		drop(trader);
+		assert_eq!(treasury_fee_balance(), 50);
+	});
+}
+
+#[test]
+fn fee_trader_refunds_at_bought_price_after_fee_is_unset() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let (location, _) = register_fee_asset();
+		let ctx = XcmContext::with_message_id([0; 32]);
+
+		let mut trader = FeeTrader::new();
+		assert_ok!(trader.buy_weight(tenth_of_second(), payment(&location, 1_000), &ctx));
+
+		assert_ok!(AssetRegistry::set_fee_per_second(RuntimeOrigin::root(), 1, None));
+		assert_eq!(
+			trader.refund_weight(Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND / 20, 0), &ctx),
+			Some((location.clone(), 50u128).into())
+		);
+
+		drop(trader);
+		assert_eq!(treasury_fee_balance(), 50);
+
+		// but the asset isn't accepted anymore
+		let mut trader = FeeTrader::new();
+		assert!(matches!(
+			trader.buy_weight(tenth_of_second(), payment(&location, 1_000), &ctx),
+			Err(XcmError::TooExpensive)
+		));
+	});
+}
+
 #[test]
 fn test_register_duplicate_location_returns_error() {
 	TestNet::reset();
@@ -475,6 +735,7 @@ fn test_update_metadata_works() {
 			),
 			additional: CustomMetadata {
 				fee_per_second: 2_000_000_000_000,
+				min_xcm_fee: None,
 			},
 		};
 		assert_ok!(AssetRegistry::update_asset(
@@ -485,7 +746,8 @@ fn test_update_metadata_works() {
 			Some(new_metadata.symbol.clone()),
 			Some(new_metadata.existential_deposit),
 			Some(new_metadata.location.clone()),
-			Some(new_metadata.additional.clone())
+			Some(new_metadata.additional.clone()),
+			None
 		));
 
 		let old_location: v3::Location = old_metadata.location.unwrap().try_into().unwrap();
@@ -508,46 +770,1132 @@ fn test_update_metadata_fails_with_unknown_asset() {
 		assert_ok!(AssetRegistry::register_asset(RuntimeOrigin::root(), old_metadata, None));
 
 		assert_noop!(
-			AssetRegistry::update_asset(RuntimeOrigin::root(), 4, None, None, None, None, None, None,),
+			AssetRegistry::update_asset(RuntimeOrigin::root(), 4, None, None, None, None, None, None, None),
 			Error::<para::Runtime>::AssetNotFound
 		);
 	});
 }
 
 #[test]
-fn test_existential_deposits() {
+fn scheduled_update_is_applied_at_enactment_block() {
 	TestNet::reset();
 
 	ParaA::execute_with(|| {
-		let metadata = AssetMetadata {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(
+			RuntimeOrigin::root(),
+			metadata.clone(),
+			None
+		));
+
+		assert_ok!(AssetRegistry::update_asset(
+			RuntimeOrigin::root(),
+			1,
+			Some(18),
+			None,
+			None,
+			Some(100),
+			None,
+			None,
+			Some(5)
+		));
+		let update = AssetUpdate {
+			decimals: Some(18),
+			existential_deposit: Some(100),
+			..Default::default()
+		};
+		para::System::assert_last_event(para::RuntimeEvent::AssetRegistry(
+			orml_asset_registry::Event::PendingAssetUpdate {
+				asset_id: 1,
+				enact_at: 5,
+				update: update.clone(),
+			},
+		));
+		assert_eq!(AssetRegistry::pending_update(1), Some((5, update)));
+		assert_eq!(AssetRegistry::pending_updates_at(5).into_inner(), vec![1]);
+
+		// nothing changes until the enactment block
+		AssetRegistry::on_initialize(4);
+		assert_eq!(AssetRegistry::metadata(1), Some(metadata.clone()));
+
+		para::System::set_block_number(5);
+		AssetRegistry::on_initialize(5);
+		let new_metadata = AssetMetadata {
+			decimals: 18,
 			existential_deposit: 100,
-			..dummy_metadata()
+			..metadata
 		};
-		assert_ok!(AssetRegistry::register_asset(RuntimeOrigin::root(), metadata, None));
+		para::System::assert_last_event(para::RuntimeEvent::AssetRegistry(
+			orml_asset_registry::Event::UpdatedAsset {
+				asset_id: 1,
+				metadata: new_metadata.clone(),
+			},
+		));
+		assert_eq!(AssetRegistry::metadata(1), Some(new_metadata));
+		assert_eq!(AssetRegistry::pending_update(1), None);
+		assert!(AssetRegistry::pending_updates_at(5).is_empty());
+	});
+}
 
-		assert_ok!(Tokens::set_balance(
+#[test]
+fn update_cannot_be_scheduled_before_next_block_or_for_unknown_asset() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		register_asset_at(dummy_metadata().location);
+
+		assert_noop!(
+			schedule_decimals(1, 18, 1),
+			Error::<para::Runtime>::InvalidEnactmentBlock
+		);
+		assert_noop!(schedule_decimals(5, 18, 5), Error::<para::Runtime>::AssetNotFound);
+		assert_ok!(schedule_decimals(1, 18, 2));
+	});
+}
+
+#[test]
+fn asset_has_at_most_one_pending_update() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		register_asset_at(dummy_metadata().location);
+
+		assert_ok!(schedule_decimals(1, 18, 5));
+		assert_noop!(schedule_decimals(1, 6, 6), Error::<para::Runtime>::PendingUpdateExists);
+
+		// it can be rescheduled once cancelled
+		assert_ok!(AssetRegistry::cancel_pending_update(RuntimeOrigin::root(), 1));
+		assert_ok!(schedule_decimals(1, 6, 6));
+		assert_eq!(AssetRegistry::pending_update(1).map(|(enact_at, _)| enact_at), Some(6));
+	});
+}
+
+#[test]
+fn pending_updates_per_block_are_bounded() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		for key in 1..=4 {
+			register_asset_at(Some(alias_location(key)));
+		}
+
+		// asset 2 is left out as only its admin can update it
+		assert_ok!(schedule_decimals(1, 6, 5));
+		assert_ok!(schedule_decimals(3, 6, 5));
+		assert_noop!(
+			schedule_decimals(4, 6, 5),
+			Error::<para::Runtime>::TooManyPendingUpdates
+		);
+		assert_ok!(schedule_decimals(4, 6, 6));
+	});
+}
+
+#[test]
+fn failed_scheduled_update_is_dropped_without_blocking_others() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		register_asset_at(metadata.location.clone());
+		// asset 2 is left out as only its admin can update it
+		register_asset_at(None);
+		register_asset_at(Some(alias_location(3)));
+		register_asset_at(Some(alias_location(4)));
+
+		assert_ok!(AssetRegistry::update_asset(
 			RuntimeOrigin::root(),
-			ALICE,
-			CurrencyId::RegisteredAsset(1),
-			1_000,
-			0
+			1,
+			Some(18),
+			None,
+			None,
+			None,
+			Some(Some(alias_location(2))),
+			None,
+			Some(5)
 		));
+		assert_ok!(schedule_decimals(3, 6, 5));
 
-		// transferring at existential_deposit succeeds
-		assert_ok!(Tokens::transfer(
-			Some(ALICE).into(),
-			BOB,
-			CurrencyId::RegisteredAsset(1),
-			100
+		// the new location of asset 1 is taken before the update is applied
+		assert_ok!(AssetRegistry::relocate_asset(
+			RuntimeOrigin::root(),
+			4,
+			Box::new(alias_location(2))
 		));
-		// transferring below existential_deposit fails
+
+		para::System::set_block_number(5);
+		AssetRegistry::on_initialize(5);
+		para::System::assert_has_event(para::RuntimeEvent::AssetRegistry(
+			orml_asset_registry::Event::PendingAssetUpdateFailed {
+				asset_id: 1,
+				error: Error::<para::Runtime>::ConflictingLocation.into(),
+			},
+		));
+		// the failed update is dropped as a whole
+		assert_eq!(AssetRegistry::metadata(1), Some(metadata));
+		assert_eq!(AssetRegistry::pending_update(1), None);
+		// other updates at the same block are still applied
+		assert_eq!(AssetRegistry::metadata(3).unwrap().decimals, 6);
+	});
+}
+
+#[test]
+fn cancelled_update_is_not_applied() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		register_asset_at(metadata.location.clone());
+		assert_ok!(schedule_decimals(1, 18, 5));
+
 		assert_noop!(
-			Tokens::transfer(Some(ALICE).into(), CHARLIE, CurrencyId::RegisteredAsset(1), 50),
-			orml_tokens::Error::<para::Runtime>::ExistentialDeposit
+			AssetRegistry::cancel_pending_update(RuntimeOrigin::signed(ALICE), 1),
+			BadOrigin
+		);
+		assert_ok!(AssetRegistry::cancel_pending_update(RuntimeOrigin::root(), 1));
+		para::System::assert_last_event(para::RuntimeEvent::AssetRegistry(
+			orml_asset_registry::Event::PendingAssetUpdateCancelled {
+				asset_id: 1,
+				enact_at: 5,
+			},
+		));
+		assert_eq!(AssetRegistry::pending_update(1), None);
+		assert!(AssetRegistry::pending_updates_at(5).is_empty());
+		assert_noop!(
+			AssetRegistry::cancel_pending_update(RuntimeOrigin::root(), 1),
+			Error::<para::Runtime>::NoPendingUpdate
+		);
+
+		para::System::set_block_number(5);
+		AssetRegistry::on_initialize(5);
+		assert_eq!(AssetRegistry::metadata(1), Some(metadata));
+	});
+}
+
+#[test]
+fn deregistering_asset_drops_its_pending_update() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		register_asset_at(dummy_metadata().location);
+		assert_ok!(schedule_decimals(1, 18, 5));
+
+		assert_ok!(AssetRegistry::deregister_asset(RuntimeOrigin::root(), 1));
+		assert_eq!(AssetRegistry::pending_update(1), None);
+		assert!(AssetRegistry::pending_updates_at(5).is_empty());
+	});
+}
+
+/// Metadata of an asset registered by its owner, who can't set a location.
+fn owned_metadata() -> ParaAssetMetadata {
+	AssetMetadata {
+		location: None,
+		..dummy_metadata()
+	}
+}
+
+#[test]
+fn owned_asset_reserves_deposit_and_takes_governance_defaults() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		para::Balances::make_free_balance_be(&ALICE, 1_000);
+
+		assert_ok!(AssetRegistry::register_owned_asset(
+			RuntimeOrigin::signed(ALICE),
+			owned_metadata()
+		));
+		para::System::assert_last_event(para::RuntimeEvent::AssetRegistry(
+			orml_asset_registry::Event::OwnedAssetRegistered {
+				asset_id: 1,
+				owner: ALICE,
+				deposit: 100,
+			},
+		));
+		// decimals, existential deposit and fees are set by governance
+		assert_eq!(
+			AssetRegistry::metadata(1),
+			Some(AssetMetadata {
+				decimals: 18,
+				existential_deposit: 10,
+				additional: CustomMetadata {
+					fee_per_second: 0,
+					min_xcm_fee: None,
+				},
+				..owned_metadata()
+			})
+		);
+		assert_eq!(AssetRegistry::asset_owner(1), Some((ALICE, 100)));
+		assert_eq!(para::Balances::reserved_balance(&ALICE), 100);
+	});
+}
+
+#[test]
+fn owned_asset_registration_requires_deposit() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_noop!(
+			AssetRegistry::register_owned_asset(RuntimeOrigin::signed(ALICE), owned_metadata()),
+			pallet_balances::Error::<para::Runtime>::InsufficientBalance
 		);
 	});
 }
 
+#[test]
+fn owned_assets_cannot_claim_a_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		para::Balances::make_free_balance_be(&ALICE, 1_000);
+		assert_noop!(
+			AssetRegistry::register_owned_asset(RuntimeOrigin::signed(ALICE), dummy_metadata()),
+			Error::<para::Runtime>::LocationNotAllowed
+		);
+	});
+}
+
+#[test]
+fn owner_cannot_change_metadata_set_by_governance() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		para::Balances::make_free_balance_be(&ALICE, 1_000);
+		assert_ok!(AssetRegistry::register_owned_asset(
+			RuntimeOrigin::signed(ALICE),
+			owned_metadata()
+		));
+
+		let additional = CustomMetadata {
+			fee_per_second: 1_000,
+			min_xcm_fee: Some(100),
+		};
+		assert_ok!(AssetRegistry::update_asset(
+			RuntimeOrigin::root(),
+			1,
+			Some(12),
+			None,
+			None,
+			Some(1),
+			None,
+			Some(additional.clone()),
+			None
+		));
+
+		let name: BoundedVec<u8, _> = b"New Name".to_vec().try_into().unwrap();
+		assert_ok!(AssetRegistry::update_owned_asset(
+			RuntimeOrigin::signed(ALICE),
+			1,
+			Some(name.clone()),
+			None,
+			Some(CustomMetadata {
+				fee_per_second: 1,
+				min_xcm_fee: None,
+			})
+		));
+		assert_eq!(
+			AssetRegistry::metadata(1),
+			Some(AssetMetadata {
+				decimals: 12,
+				name,
+				existential_deposit: 1,
+				additional,
+				..owned_metadata()
+			})
+		);
+	});
+}
+
+#[test]
+fn only_owner_can_update_owned_asset() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		para::Balances::make_free_balance_be(&ALICE, 1_000);
+		assert_ok!(AssetRegistry::register_owned_asset(
+			RuntimeOrigin::signed(ALICE),
+			owned_metadata()
+		));
+		register_asset_at(dummy_metadata().location);
+
+		assert_noop!(
+			AssetRegistry::update_owned_asset(RuntimeOrigin::signed(BOB), 1, None, None, None),
+			Error::<para::Runtime>::NotAssetOwner
+		);
+		// assets registered by governance have no owner
+		assert_noop!(
+			AssetRegistry::update_owned_asset(RuntimeOrigin::signed(ALICE), 2, None, None, None),
+			Error::<para::Runtime>::NotAssetOwner
+		);
+		assert_noop!(
+			AssetRegistry::update_owned_asset(RuntimeOrigin::root(), 1, None, None, None),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn deposit_is_returned_when_governance_deregisters_owned_asset() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		para::Balances::make_free_balance_be(&ALICE, 1_000);
+		assert_ok!(AssetRegistry::register_owned_asset(
+			RuntimeOrigin::signed(ALICE),
+			owned_metadata()
+		));
+
+		// governance still controls the location
+		assert_ok!(AssetRegistry::relocate_asset(
+			RuntimeOrigin::root(),
+			1,
+			Box::new(alias_location(1))
+		));
+		assert_eq!(AssetRegistry::metadata(1).unwrap().location, Some(alias_location(1)));
+
+		assert_ok!(AssetRegistry::deregister_asset(RuntimeOrigin::root(), 1));
+		assert_eq!(AssetRegistry::asset_owner(1), None);
+		assert_eq!(para::Balances::reserved_balance(&ALICE), 0);
+		assert_eq!(para::Balances::free_balance(&ALICE), 1_000);
+	});
+}
+
+#[test]
+fn owner_can_deregister_owned_asset_and_get_deposit_back() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = AssetMetadata {
+			location: None,
+			..dummy_metadata()
+		};
+		para::Balances::make_free_balance_be(&ALICE, 1_000);
+		assert_ok!(AssetRegistry::register_owned_asset(
+			RuntimeOrigin::signed(ALICE),
+			metadata
+		));
+		assert_ok!(AssetRegistry::register_asset(
+			RuntimeOrigin::root(),
+			dummy_metadata(),
+			None
+		));
+
+		// only the owner, and not governance through this call
+		assert_noop!(
+			AssetRegistry::deregister_owned_asset(RuntimeOrigin::signed(BOB), 1),
+			Error::<para::Runtime>::NotAssetOwner
+		);
+		assert_noop!(
+			AssetRegistry::deregister_owned_asset(RuntimeOrigin::signed(ALICE), 2),
+			Error::<para::Runtime>::NotAssetOwner
+		);
+		assert_noop!(
+			AssetRegistry::deregister_owned_asset(RuntimeOrigin::root(), 1),
+			BadOrigin
+		);
+
+		// not while the asset has holders
+		assert_ok!(ParaTokens::deposit(CurrencyId::RegisteredAsset(1), &BOB, 100));
+		assert_noop!(
+			AssetRegistry::deregister_owned_asset(RuntimeOrigin::signed(ALICE), 1),
+			Error::<para::Runtime>::NonZeroIssuance
+		);
+		assert_ok!(ParaTokens::withdraw(
+			CurrencyId::RegisteredAsset(1),
+			&BOB,
+			100,
+			ExistenceRequirement::AllowDeath
+		));
+
+		assert_ok!(AssetRegistry::deregister_owned_asset(RuntimeOrigin::signed(ALICE), 1));
+		assert_eq!(AssetRegistry::metadata(1), None);
+		assert_eq!(AssetRegistry::asset_owner(1), None);
+		assert_eq!(para::Balances::reserved_balance(&ALICE), 0);
+		assert_eq!(para::Balances::free_balance(&ALICE), 1_000);
+	});
+}
+
+#[test]
+fn deregistered_asset_frees_its_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		let location: v3::Location = metadata.location.clone().unwrap().try_into().unwrap();
+		register_asset_at(metadata.location.clone());
+
+		assert_ok!(AssetRegistry::deregister_asset(RuntimeOrigin::root(), 1));
+		para::System::assert_last_event(para::RuntimeEvent::AssetRegistry(
+			orml_asset_registry::Event::DeregisteredAsset {
+				asset_id: 1,
+				metadata: metadata.clone(),
+			},
+		));
+
+		assert_eq!(AssetRegistry::metadata(1), None);
+		assert_eq!(AssetRegistry::location_to_asset_id(location), None);
+
+		// the location can be registered again
+		assert_ok!(AssetRegistry::register_asset(RuntimeOrigin::root(), metadata, None));
+		assert_eq!(AssetRegistry::location_to_asset_id(location), Some(2));
+	});
+}
+
+#[test]
+fn deregister_asset_requires_authority_and_registered_asset() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		register_asset_at(dummy_metadata().location);
+
+		assert_noop!(
+			AssetRegistry::deregister_asset(RuntimeOrigin::signed(ALICE), 1),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetRegistry::deregister_asset(RuntimeOrigin::root(), 4),
+			Error::<para::Runtime>::AssetNotFound
+		);
+	});
+}
+
+#[test]
+fn asset_with_issuance_cannot_be_deregistered() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		register_asset_at(dummy_metadata().location);
+
+		assert_ok!(Tokens::deposit(CurrencyId::RegisteredAsset(1), &ALICE, 1_000));
+		assert_noop!(
+			AssetRegistry::deregister_asset(RuntimeOrigin::root(), 1),
+			Error::<para::Runtime>::NonZeroIssuance
+		);
+
+		assert_ok!(Tokens::withdraw(
+			CurrencyId::RegisteredAsset(1),
+			&ALICE,
+			1_000,
+			ExistenceRequirement::AllowDeath
+		));
+		assert_ok!(AssetRegistry::deregister_asset(RuntimeOrigin::root(), 1));
+	});
+}
+
+#[test]
+fn relocated_asset_is_only_found_at_new_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		register_asset_at(metadata.location.clone());
+
+		let new_location: VersionedLocation = Location::new(
+			1,
+			[Parachain(2), Junction::from(BoundedVec::try_from(vec![0]).unwrap())],
+		)
+		.into();
+		assert_ok!(AssetRegistry::relocate_asset(
+			RuntimeOrigin::root(),
+			1,
+			Box::new(new_location.clone())
+		));
+		para::System::assert_last_event(para::RuntimeEvent::AssetRegistry(
+			orml_asset_registry::Event::RelocatedAsset {
+				asset_id: 1,
+				old_location: metadata.location.clone(),
+				new_location: new_location.clone(),
+			},
+		));
+
+		let old_location: v3::Location = metadata.location.unwrap().try_into().unwrap();
+		assert_eq!(AssetRegistry::location_to_asset_id(old_location), None);
+		assert_eq!(
+			AssetRegistry::location_to_asset_id(v3::Location::try_from(new_location.clone()).unwrap()),
+			Some(1)
+		);
+		assert_eq!(AssetRegistry::metadata(1).unwrap().location, Some(new_location));
+	});
+}
+
+#[test]
+fn asset_cannot_be_relocated_to_taken_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let location = dummy_metadata().location.unwrap();
+		register_asset_at(Some(location.clone()));
+		register_asset_at(None);
+		assert_ok!(AssetRegistry::add_location_alias(
+			RuntimeOrigin::root(),
+			1,
+			Box::new(alias_location(1))
+		));
+
+		// the location of another asset
+		assert_noop!(
+			AssetRegistry::relocate_asset(RuntimeOrigin::signed(AdminAssetTwo::get()), 2, Box::new(location)),
+			Error::<para::Runtime>::ConflictingLocation
+		);
+		// an alias of another asset
+		assert_noop!(
+			AssetRegistry::relocate_asset(
+				RuntimeOrigin::signed(AdminAssetTwo::get()),
+				2,
+				Box::new(alias_location(1))
+			),
+			Error::<para::Runtime>::ConflictingLocation
+		);
+		// an alias of the same asset, which should be made canonical instead
+		assert_noop!(
+			AssetRegistry::relocate_asset(RuntimeOrigin::root(), 1, Box::new(alias_location(1))),
+			Error::<para::Runtime>::ConflictingLocation
+		);
+	});
+}
+
+#[test]
+fn relocate_asset_requires_authority_and_registered_asset() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		register_asset_at(dummy_metadata().location);
+
+		assert_noop!(
+			AssetRegistry::relocate_asset(RuntimeOrigin::signed(ALICE), 1, Box::new(alias_location(1))),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetRegistry::relocate_asset(RuntimeOrigin::root(), 4, Box::new(alias_location(1))),
+			Error::<para::Runtime>::AssetNotFound
+		);
+	});
+}
+
+fn alias_location(key: u8) -> VersionedLocation {
+	Location::new(
+		1,
+		[
+			Parachain(1000),
+			Junction::from(BoundedVec::try_from(vec![key]).unwrap()),
+		],
+	)
+	.into()
+}
+
+#[test]
+fn alias_resolves_to_asset_while_canonical_location_is_used_outbound() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		let canonical: Location = metadata.location.clone().unwrap().try_into().unwrap();
+		register_asset_at(metadata.location.clone());
+
+		assert_ok!(AssetRegistry::add_location_alias(
+			RuntimeOrigin::root(),
+			1,
+			Box::new(alias_location(1))
+		));
+		para::System::assert_last_event(para::RuntimeEvent::AssetRegistry(
+			orml_asset_registry::Event::LocationAliasAdded {
+				asset_id: 1,
+				alias: alias_location(1),
+			},
+		));
+
+		let alias: Location = alias_location(1).try_into().unwrap();
+		assert_eq!(
+			AssetRegistry::location_to_asset_id(v3::Location::try_from(alias_location(1)).unwrap()),
+			Some(1)
+		);
+		assert_eq!(<AssetRegistry as Inspect>::asset_id(&alias), Some(1));
+		assert_eq!(<AssetRegistry as Inspect>::asset_id(&canonical), Some(1));
+		assert_eq!(<AssetRegistry as Inspect>::metadata_by_location(&alias), Some(metadata));
+		assert_eq!(<AssetRegistry as Inspect>::location(&1), Ok(Some(canonical)));
+		assert_eq!(<AssetRegistry as Inspect>::location_aliases(&1), vec![alias]);
+	});
+}
+
+#[test]
+fn canonical_location_can_be_swapped_with_alias() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let canonical: Location = dummy_metadata().location.unwrap().try_into().unwrap();
+		let alias: Location = alias_location(1).try_into().unwrap();
+		register_asset_at(dummy_metadata().location);
+		assert_ok!(AssetRegistry::add_location_alias(
+			RuntimeOrigin::root(),
+			1,
+			Box::new(alias_location(1))
+		));
+
+		assert_ok!(AssetRegistry::set_canonical_location(
+			RuntimeOrigin::root(),
+			1,
+			Box::new(alias_location(1))
+		));
+		para::System::assert_last_event(para::RuntimeEvent::AssetRegistry(
+			orml_asset_registry::Event::CanonicalLocationChanged {
+				asset_id: 1,
+				old_location: dummy_metadata().location,
+				new_location: alias_location(1),
+			},
+		));
+		assert_eq!(<AssetRegistry as Inspect>::location(&1), Ok(Some(alias.clone())));
+		assert_eq!(
+			<AssetRegistry as Inspect>::location_aliases(&1),
+			vec![canonical.clone()]
+		);
+		// both locations still resolve to the asset
+		assert_eq!(<AssetRegistry as Inspect>::asset_id(&alias), Some(1));
+		assert_eq!(<AssetRegistry as Inspect>::asset_id(&canonical), Some(1));
+	});
+}
+
+#[test]
+fn removed_alias_no_longer_resolves() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let canonical: Location = dummy_metadata().location.unwrap().try_into().unwrap();
+		let alias: Location = alias_location(1).try_into().unwrap();
+		register_asset_at(dummy_metadata().location);
+		assert_ok!(AssetRegistry::add_location_alias(
+			RuntimeOrigin::root(),
+			1,
+			Box::new(alias_location(1))
+		));
+
+		assert_ok!(AssetRegistry::remove_location_alias(
+			RuntimeOrigin::root(),
+			1,
+			Box::new(alias_location(1))
+		));
+		para::System::assert_last_event(para::RuntimeEvent::AssetRegistry(
+			orml_asset_registry::Event::LocationAliasRemoved {
+				asset_id: 1,
+				alias: alias_location(1),
+			},
+		));
+		assert_eq!(<AssetRegistry as Inspect>::asset_id(&alias), None);
+		assert_eq!(<AssetRegistry as Inspect>::asset_id(&canonical), Some(1));
+		assert_eq!(<AssetRegistry as Inspect>::location_aliases(&1), vec![]);
+		assert!(!orml_asset_registry::LocationAliases::<para::Runtime>::contains_key(1));
+	});
+}
+
+#[test]
+fn add_location_alias_requires_authority_and_registered_asset() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		register_asset_at(dummy_metadata().location);
+
+		assert_noop!(
+			AssetRegistry::add_location_alias(RuntimeOrigin::signed(ALICE), 1, Box::new(alias_location(1))),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetRegistry::add_location_alias(RuntimeOrigin::root(), 4, Box::new(alias_location(1))),
+			Error::<para::Runtime>::AssetNotFound
+		);
+	});
+}
+
+#[test]
+fn alias_cannot_take_registered_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		register_asset_at(dummy_metadata().location);
+		register_asset_at(Some(alias_location(2)));
+
+		// its own canonical location
+		assert_noop!(
+			AssetRegistry::add_location_alias(RuntimeOrigin::root(), 1, Box::new(dummy_metadata().location.unwrap())),
+			Error::<para::Runtime>::ConflictingLocation
+		);
+		// the location of another asset
+		assert_noop!(
+			AssetRegistry::add_location_alias(RuntimeOrigin::root(), 1, Box::new(alias_location(2))),
+			Error::<para::Runtime>::ConflictingLocation
+		);
+	});
+}
+
+#[test]
+fn aliases_per_asset_are_bounded() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		register_asset_at(dummy_metadata().location);
+
+		for key in [1, 2] {
+			assert_ok!(AssetRegistry::add_location_alias(
+				RuntimeOrigin::root(),
+				1,
+				Box::new(alias_location(key))
+			));
+		}
+		assert_noop!(
+			AssetRegistry::add_location_alias(RuntimeOrigin::root(), 1, Box::new(alias_location(3))),
+			Error::<para::Runtime>::TooManyAliases
+		);
+	});
+}
+
+#[test]
+fn unknown_alias_cannot_be_removed_or_made_canonical() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		register_asset_at(dummy_metadata().location);
+		assert_ok!(AssetRegistry::add_location_alias(
+			RuntimeOrigin::root(),
+			1,
+			Box::new(alias_location(1))
+		));
+
+		assert_noop!(
+			AssetRegistry::remove_location_alias(RuntimeOrigin::root(), 1, Box::new(alias_location(3))),
+			Error::<para::Runtime>::AliasNotFound
+		);
+		assert_noop!(
+			AssetRegistry::set_canonical_location(RuntimeOrigin::root(), 1, Box::new(alias_location(3))),
+			Error::<para::Runtime>::AliasNotFound
+		);
+	});
+}
+
+#[test]
+fn deregistering_asset_removes_its_aliases() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		register_asset_at(dummy_metadata().location);
+		for key in [1, 2] {
+			assert_ok!(AssetRegistry::add_location_alias(
+				RuntimeOrigin::root(),
+				1,
+				Box::new(alias_location(key))
+			));
+		}
+
+		assert_ok!(AssetRegistry::deregister_asset(RuntimeOrigin::root(), 1));
+		for key in [1, 2] {
+			assert_eq!(
+				AssetRegistry::location_to_asset_id(v3::Location::try_from(alias_location(key)).unwrap()),
+				None
+			);
+		}
+		assert!(!orml_asset_registry::LocationAliases::<para::Runtime>::contains_key(1));
+
+		// and they can be registered again
+		register_asset_at(Some(alias_location(1)));
+	});
+}
+
+type Block = frame_system::mocking::MockBlock<para::Runtime>;
+
+struct MockApi;
+
+sp_api::mock_impl_runtime_apis! {
+	impl AssetRegistryApi<Block, u32, ParaAssetMetadata> for MockApi {
+		fn metadata(asset_id: u32) -> Option<ParaAssetMetadata> {
+			AssetRegistry::metadata(asset_id)
+		}
+
+		fn metadata_by_location(location: VersionedLocation) -> Option<(u32, ParaAssetMetadata)> {
+			AssetRegistry::metadata_by_versioned_location(location)
+		}
+
+		fn assets(start_after: Option<u32>, limit: u32) -> Vec<(u32, ParaAssetMetadata)> {
+			AssetRegistry::assets(start_after, limit)
+		}
+
+		fn location(asset_id: u32, version: u32) -> Option<VersionedLocation> {
+			AssetRegistry::versioned_location(&asset_id, version)
+		}
+	}
+}
+
+#[test]
+fn runtime_api_looks_up_metadata_by_location_in_any_version() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		register_asset_at(metadata.location.clone());
+
+		let api = MockApi;
+		let at = Default::default();
+		assert_eq!(api.metadata(at, 1).unwrap(), Some(metadata.clone()));
+		assert_eq!(api.metadata(at, 4).unwrap(), None);
+
+		let location: Location = metadata.location.clone().unwrap().try_into().unwrap();
+		let v3_location: v3::Location = metadata.location.clone().unwrap().try_into().unwrap();
+		assert_eq!(
+			api.metadata_by_location(at, VersionedLocation::from(location)).unwrap(),
+			Some((1, metadata.clone()))
+		);
+		assert_eq!(
+			api.metadata_by_location(at, VersionedLocation::from(v3_location))
+				.unwrap(),
+			Some((1, metadata))
+		);
+		assert_eq!(api.metadata_by_location(at, Location::parent().into()).unwrap(), None);
+	});
+}
+
+#[test]
+fn runtime_api_returns_location_in_requested_version() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		register_asset_at(metadata.location.clone());
+
+		let api = MockApi;
+		let at = Default::default();
+		let location: Location = metadata.location.clone().unwrap().try_into().unwrap();
+		let v3_location: v3::Location = metadata.location.unwrap().try_into().unwrap();
+		assert_eq!(
+			api.location(at, 1, 3).unwrap(),
+			Some(VersionedLocation::V3(v3_location))
+		);
+		assert_eq!(api.location(at, 1, 5).unwrap(), Some(VersionedLocation::V5(location)));
+		assert_eq!(api.location(at, 4, 5).unwrap(), None);
+	});
+}
+
+#[test]
+fn runtime_api_lists_each_asset_once_across_pages() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		register_asset_at(dummy_metadata().location);
+		for key in [1, 2] {
+			register_asset_at(Some(alias_location(key)));
+		}
+
+		let api = MockApi;
+		let at = Default::default();
+		let first_page = api.assets(at, None, 2).unwrap();
+		assert_eq!(first_page.len(), 2);
+		let second_page = api
+			.assets(at, first_page.last().map(|(asset_id, _)| *asset_id), 2)
+			.unwrap();
+		assert_eq!(second_page.len(), 1);
+		let mut asset_ids: Vec<_> = first_page
+			.into_iter()
+			.chain(second_page)
+			.map(|(asset_id, _)| asset_id)
+			.collect();
+		asset_ids.sort();
+		assert_eq!(asset_ids, vec![1, 2, 3]);
+	});
+}
+
+#[test]
+fn test_existential_deposits() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = AssetMetadata {
+			existential_deposit: 100,
+			..dummy_metadata()
+		};
+		assert_ok!(AssetRegistry::register_asset(RuntimeOrigin::root(), metadata, None));
+
+		assert_ok!(Tokens::set_balance(
+			RuntimeOrigin::root(),
+			ALICE,
+			CurrencyId::RegisteredAsset(1),
+			1_000,
+			0
+		));
+
+		// transferring at existential_deposit succeeds
+		assert_ok!(Tokens::transfer(
+			Some(ALICE).into(),
+			BOB,
+			CurrencyId::RegisteredAsset(1),
+			100
+		));
+		// transferring below existential_deposit fails
+		assert_noop!(
+			Tokens::transfer(Some(ALICE).into(), CHARLIE, CurrencyId::RegisteredAsset(1), 50),
+			orml_tokens::Error::<para::Runtime>::ExistentialDeposit
+		);
+	});
+}
+
+#[test]
+fn currency_id_to_location_uses_canonical_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let location: Location = dummy_metadata().location.unwrap().try_into().unwrap();
+		register_asset_at(dummy_metadata().location);
+		assert_ok!(AssetRegistry::add_location_alias(
+			RuntimeOrigin::root(),
+			1,
+			Box::new(alias_location(1))
+		));
+
+		assert_eq!(
+			CurrencyIdToLocation::convert(CurrencyId::RegisteredAsset(1)),
+			Some(location)
+		);
+		assert_eq!(CurrencyIdToLocation::convert(CurrencyId::RegisteredAsset(2)), None);
+		// other currencies are left to the fallback
+		assert_eq!(CurrencyIdToLocation::convert(CurrencyId::R), Some(Location::parent()));
+	});
+}
+
+#[test]
+fn currency_id_convert_resolves_aliases_of_fungible_assets() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let location: Location = dummy_metadata().location.unwrap().try_into().unwrap();
+		let alias: Location = alias_location(1).try_into().unwrap();
+		register_asset_at(dummy_metadata().location);
+		assert_ok!(AssetRegistry::add_location_alias(
+			RuntimeOrigin::root(),
+			1,
+			Box::new(alias_location(1))
+		));
+
+		assert_eq!(
+			<CurrencyIdConvert as Convert<Location, _>>::convert(alias),
+			Some(CurrencyId::RegisteredAsset(1))
+		);
+		assert_eq!(
+			<CurrencyIdConvert as Convert<Location, _>>::convert(Location::parent()),
+			Some(CurrencyId::R)
+		);
+		assert_eq!(
+			<CurrencyIdConvert as Convert<Asset, _>>::convert((location.clone(), 100u128).into()),
+			Some(CurrencyId::RegisteredAsset(1))
+		);
+		assert_eq!(
+			<CurrencyIdConvert as Convert<Asset, _>>::convert((location, [1u8; 32]).into()),
+			None
+		);
+	});
+}
+
+#[test]
+fn existential_deposits_of_unregistered_assets_are_max() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = AssetMetadata {
+			existential_deposit: 100,
+			..dummy_metadata()
+		};
+		assert_ok!(AssetRegistry::register_asset(RuntimeOrigin::root(), metadata, None));
+
+		assert_eq!(para::ExistentialDeposits::get(&CurrencyId::RegisteredAsset(1)), 100);
+		assert_eq!(
+			para::ExistentialDeposits::get(&CurrencyId::RegisteredAsset(2)),
+			Balance::MAX
+		);
+		// other currencies are left to the fallback
+		assert_eq!(para::ExistentialDeposits::get(&CurrencyId::R), 0);
+	});
+}
+
+#[test]
+/// test that xtokens enforces the min xcm fee of the destination from the
+/// metadata of the asset it charges fees in, not of its native asset
+fn send_self_parachain_asset_with_registered_min_xcm_fee() {
+	TestNet::reset();
+
+	let mut metadata = dummy_metadata();
+
+	ParaB::execute_with(|| {
+		AssetRegistry::register_asset(RuntimeOrigin::root(), metadata.clone(), None).unwrap();
+	});
+
+	ParaA::execute_with(|| {
+		// the asset ParaB charges fees in
+		let location = Location::new(0, [Junction::from(BoundedVec::try_from(vec![0]).unwrap())]);
+		metadata.location = Some(location.clone().into());
+		metadata.additional.min_xcm_fee = Some(1_000);
+		AssetRegistry::register_asset(RuntimeOrigin::root(), metadata, None).unwrap();
+		// the native asset of ParaB, whose min xcm fee is not in units of the fee asset
+		AssetRegistry::register_asset(
+			RuntimeOrigin::root(),
+			AssetMetadata {
+				location: Some(Location::new(1, [Parachain(2)]).into()),
+				additional: CustomMetadata {
+					fee_per_second: 1_000_000_000_000,
+					min_xcm_fee: Some(1),
+				},
+				..dummy_metadata()
+			},
+			None,
+		)
+		.unwrap();
+
+		assert_ok!(ParaTokens::deposit(CurrencyId::RegisteredAsset(1), &ALICE, 1_000));
+
+		let transfer = || {
+			ParaXTokens::transfer_assets_using_type_and_then(
+				Some(ALICE).into(),
+				Box::new(Assets::from((location.clone(), 500u128)).into()),
+				0,
+				Box::new(Location::new(1, [Parachain(2)]).into()),
+				Box::new(TransferType::LocalReserve),
+				None,
+				Box::new(VersionedXcm::from(Xcm(vec![DepositAsset {
+					assets: AllCounted(1).into(),
+					beneficiary: Junction::AccountId32 {
+						network: None,
+						id: BOB.into(),
+					}
+					.into(),
+				}]))),
+				WeightLimit::Unlimited,
+			)
+		};
+
+		assert_noop!(transfer(), orml_xtokens::Error::<para::Runtime>::FeeNotEnough);
+
+		assert_ok!(AssetRegistry::update_asset(
+			RuntimeOrigin::root(),
+			1,
+			None,
+			None,
+			None,
+			None,
+			None,
+			Some(CustomMetadata {
+				fee_per_second: 1_000_000_000_000,
+				min_xcm_fee: Some(100),
+			}),
+			None
+		));
+		assert_ok!(transfer());
+
+		assert_eq!(ParaTokens::free_balance(CurrencyId::RegisteredAsset(1), &ALICE), 500);
+		assert_eq!(
+			ParaTokens::free_balance(CurrencyId::RegisteredAsset(1), &sibling_b_account()),
+			500
+		);
+	});
+
+	ParaB::execute_with(|| {
+		let received = ParaTokens::free_balance(CurrencyId::RegisteredAsset(1), &BOB);
+		let fee = ParaTokens::free_balance(CurrencyId::RegisteredAsset(1), &treasury_account());
+		assert!(received > 0);
+		assert_eq!(received + fee, 500);
+	});
+}
+
 #[test]
 fn test_asset_authority() {
 	TestNet::reset();
@@ -621,6 +1969,7 @@ fn test_decode_bounded_vec() {
 				location: None,
 				additional: CustomMetadata {
 					fee_per_second: 1_000_000_000_000,
+					min_xcm_fee: None,
 				},
 			},
 		);
@@ -13,6 +13,16 @@ pub trait WeightInfo {
 	fn register_asset() -> Weight;
 	fn update_asset() -> Weight;
 	fn set_asset_location() -> Weight;
+	fn deregister_asset() -> Weight;
+	fn relocate_asset() -> Weight;
+	fn add_location_alias() -> Weight;
+	fn remove_location_alias() -> Weight;
+	fn set_canonical_location() -> Weight;
+	fn set_fee_per_second() -> Weight;
+	fn cancel_pending_update() -> Weight;
+	fn register_owned_asset() -> Weight;
+	fn update_owned_asset() -> Weight;
+	fn deregister_owned_asset() -> Weight;
 }
 
 /// Default weights.
@@ -26,4 +36,40 @@ impl WeightInfo for () {
 	fn set_asset_location() -> Weight {
 		Weight::zero()
 	}
+	fn deregister_asset() -> Weight {
+		Weight::zero()
+	}
+	fn relocate_asset() -> Weight {
+		Weight::zero()
+	}
+	fn add_location_alias() -> Weight {
+		Weight::zero()
+	}
+	fn remove_location_alias() -> Weight {
+		Weight::zero()
+	}
+	fn set_canonical_location() -> Weight {
+		Weight::zero()
+	}
+	fn set_fee_per_second() -> Weight {
+		Weight::zero()
+	}
+	fn cancel_pending_update() -> Weight {
+		Weight::zero()
+	}
+	fn register_owned_asset() -> Weight {
+		Weight::from_parts(27_773_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn update_owned_asset() -> Weight {
+		Weight::from_parts(11_979_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn deregister_owned_asset() -> Weight {
+		Weight::from_parts(39_333_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+	}
 }
@@ -0,0 +1,59 @@
+pub use crate::*;
+
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+
+/// Helper trait for benchmarking.
+pub trait BenchmarkHelper<CurrencyId, Balance> {
+	/// Returns a currency id, and an amount of it which can be deposited to
+	/// an empty account.
+	fn currency_id_and_amount() -> Option<(CurrencyId, Balance)>;
+}
+
+impl<CurrencyId, Balance> BenchmarkHelper<CurrencyId, Balance> for () {
+	fn currency_id_and_amount() -> Option<(CurrencyId, Balance)> {
+		None
+	}
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn claim() -> Result<(), BenchmarkError> {
+		let (currency_id, amount) = T::BenchmarkHelper::currency_id_and_amount().ok_or(BenchmarkError::Skip)?;
+		let who: T::AccountId = whitelisted_caller();
+		Pallet::<T>::quarantine(currency_id, &who, amount);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(who.clone()), currency_id);
+
+		assert!(!QuarantinedDeposits::<T>::contains_key(&who, currency_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn sweep() -> Result<(), BenchmarkError> {
+		let (currency_id, amount) = T::BenchmarkHelper::currency_id_and_amount().ok_or(BenchmarkError::Skip)?;
+		let origin = T::SweepOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let who: T::AccountId = account("who", 0, 0);
+		let to: T::AccountId = account("to", 0, 0);
+		Pallet::<T>::quarantine(currency_id, &who, amount);
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now.saturating_add(T::ClaimPeriod::get()));
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			T::Lookup::unlookup(who.clone()),
+			currency_id,
+			T::Lookup::unlookup(to),
+		);
+
+		assert!(!QuarantinedDeposits::<T>::contains_key(&who, currency_id));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder.build(), crate::mock::Runtime);
+}
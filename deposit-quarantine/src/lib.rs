@@ -0,0 +1,227 @@
+//! # Deposit Quarantine
+//!
+//! A storage backed `OnDepositFail` implementation. Instead of sending failed
+//! deposits to a single alternative account, the failed amount is recorded per
+//! beneficiary and currency. Beneficiaries could `claim` it later, for example
+//! after raising their balance above the existential deposit. Deposits which
+//! are not claimed within `ClaimPeriod` could be swept by governance.
+//!
+//! Set the module as the `OnDepositFail` of `MultiCurrencyAdapter` to use it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+use orml_xcm_support::OnDepositFail;
+use sp_runtime::traits::{Saturating, StaticLookup, Zero};
+use xcm::v5::Result as XcmResult;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod mock;
+mod tests;
+mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub use benchmarking::BenchmarkHelper;
+pub use module::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub(crate) type BalanceOf<T> =
+		<<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub(crate) type CurrencyIdOf<T> =
+		<<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+
+	/// Failed deposits of a currency to a beneficiary.
+	#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct QuarantinedDeposit<Balance, BlockNumber> {
+		/// The total amount of failed deposits.
+		pub amount: Balance,
+		/// The block number of the latest failed deposit.
+		pub last_deposit_at: BlockNumber,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The currency used to deposit claimed and swept amounts.
+		type MultiCurrency: MultiCurrency<Self::AccountId>;
+
+		/// The number of blocks after the latest failed deposit, within which
+		/// quarantined deposits can't be swept.
+		#[pallet::constant]
+		type ClaimPeriod: Get<BlockNumberFor<Self>>;
+
+		/// The origin which may sweep unclaimed deposits.
+		type SweepOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<CurrencyIdOf<Self>, BalanceOf<Self>>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A failed deposit was quarantined.
+		Quarantined {
+			currency_id: CurrencyIdOf<T>,
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// A deposit refused by the rate limiter was quarantined.
+		RateLimited {
+			currency_id: CurrencyIdOf<T>,
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Quarantined deposits were claimed by the beneficiary.
+		Claimed {
+			currency_id: CurrencyIdOf<T>,
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Unclaimed deposits were swept to `to`.
+		Swept {
+			currency_id: CurrencyIdOf<T>,
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+			to: T::AccountId,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// No quarantined deposits.
+		NoQuarantinedDeposit,
+		/// The claim period of quarantined deposits has not ended.
+		ClaimPeriodNotEnded,
+	}
+
+	/// Quarantined deposits of a beneficiary under a currency.
+	///
+	/// double_map: who, currency_id => QuarantinedDeposit
+	#[pallet::storage]
+	pub type QuarantinedDeposits<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		QuarantinedDeposit<BalanceOf<T>, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Claim quarantined deposits under `currency_id`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// beneficiary.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::claim())]
+		pub fn claim(origin: OriginFor<T>, currency_id: CurrencyIdOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let deposit = QuarantinedDeposits::<T>::take(&who, currency_id).ok_or(Error::<T>::NoQuarantinedDeposit)?;
+
+			T::MultiCurrency::deposit(currency_id, &who, deposit.amount)?;
+
+			Self::deposit_event(Event::Claimed {
+				currency_id,
+				who,
+				amount: deposit.amount,
+			});
+			Ok(())
+		}
+
+		/// Sweep unclaimed deposits of `who` under `currency_id` to `to`.
+		///
+		/// The dispatch origin for this call must be `SweepOrigin`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::sweep())]
+		pub fn sweep(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			to: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::SweepOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let to = T::Lookup::lookup(to)?;
+
+			let deposit = QuarantinedDeposits::<T>::take(&who, currency_id).ok_or(Error::<T>::NoQuarantinedDeposit)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				now >= deposit.last_deposit_at.saturating_add(T::ClaimPeriod::get()),
+				Error::<T>::ClaimPeriodNotEnded
+			);
+
+			T::MultiCurrency::deposit(currency_id, &to, deposit.amount)?;
+
+			Self::deposit_event(Event::Swept {
+				currency_id,
+				who,
+				amount: deposit.amount,
+				to,
+			});
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Record a failed deposit of `amount` to `who` under `currency_id`.
+	fn quarantine(currency_id: CurrencyIdOf<T>, who: &T::AccountId, amount: BalanceOf<T>) {
+		if amount.is_zero() {
+			return;
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		QuarantinedDeposits::<T>::mutate(who, currency_id, |maybe_deposit| {
+			let deposit = maybe_deposit.get_or_insert(QuarantinedDeposit {
+				amount: Zero::zero(),
+				last_deposit_at: now,
+			});
+			deposit.amount = deposit.amount.saturating_add(amount);
+			deposit.last_deposit_at = now;
+		});
+
+		Self::deposit_event(Event::Quarantined {
+			currency_id,
+			who: who.clone(),
+			amount,
+		});
+	}
+}
+
+impl<T: Config> OnDepositFail<CurrencyIdOf<T>, T::AccountId, BalanceOf<T>> for Pallet<T> {
+	fn on_deposit_currency_fail(
+		_err: DispatchError,
+		currency_id: CurrencyIdOf<T>,
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> XcmResult {
+		Self::quarantine(currency_id, who, amount);
+		Ok(())
+	}
+
+	fn on_deposit_rate_limited(currency_id: CurrencyIdOf<T>, who: &T::AccountId, amount: BalanceOf<T>) -> XcmResult {
+		Self::deposit_event(Event::RateLimited {
+			currency_id,
+			who: who.clone(),
+			amount,
+		});
+		Self::quarantine(currency_id, who, amount);
+		Ok(())
+	}
+}
@@ -0,0 +1,132 @@
+//! Mocks for the deposit quarantine module.
+
+#![cfg(test)]
+
+use super::*;
+use orml_xcm_support::{IsNativeConcrete, MultiCurrencyAdapter};
+
+use frame_support::{
+	construct_runtime, derive_impl,
+	traits::{ConstU32, ConstU64, Nothing},
+};
+use frame_system::EnsureRoot;
+use orml_traits::parameter_type_with_key;
+use sp_runtime::{
+	traits::{Convert, IdentityLookup},
+	AccountId32, BuildStorage,
+};
+use xcm::v5::prelude::*;
+use xcm_executor::traits::ConvertLocation;
+
+use crate as deposit_quarantine;
+
+pub type AccountId = AccountId32;
+pub type CurrencyId = u32;
+pub type Balance = u64;
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		10
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Balance = Balance;
+	type Amount = i64;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type CurrencyHooks = ();
+	type MaxLocks = ConstU32<100>;
+	type MaxReserves = ConstU32<100>;
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = Nothing;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+impl Config for Runtime {
+	type MultiCurrency = Tokens;
+	type ClaimPeriod = ConstU64<10>;
+	type SweepOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = MockBenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<CurrencyId, Balance> for MockBenchmarkHelper {
+	fn currency_id_and_amount() -> Option<(CurrencyId, Balance)> {
+		Some((RELAY_TOKEN, 100))
+	}
+}
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Tokens: orml_tokens,
+		DepositQuarantine: deposit_quarantine,
+	}
+);
+
+pub const ALICE: AccountId = AccountId32::new([1u8; 32]);
+pub const TREASURY: AccountId = AccountId32::new([2u8; 32]);
+pub const RELAY_TOKEN: CurrencyId = 0;
+
+pub struct CurrencyIdConvert;
+impl Convert<Location, Option<CurrencyId>> for CurrencyIdConvert {
+	fn convert(location: Location) -> Option<CurrencyId> {
+		(location == Location::parent()).then_some(RELAY_TOKEN)
+	}
+}
+impl Convert<Asset, Option<CurrencyId>> for CurrencyIdConvert {
+	fn convert(asset: Asset) -> Option<CurrencyId> {
+		Self::convert(asset.id.0)
+	}
+}
+
+pub struct AccountIdConvert;
+impl ConvertLocation<AccountId> for AccountIdConvert {
+	fn convert_location(location: &Location) -> Option<AccountId> {
+		match location.unpack() {
+			(0, [Junction::AccountId32 { id, .. }]) => Some((*id).into()),
+			_ => None,
+		}
+	}
+}
+
+pub type LocalAssetTransactor = MultiCurrencyAdapter<
+	Tokens,
+	(),
+	IsNativeConcrete<CurrencyId, CurrencyIdConvert>,
+	AccountId,
+	AccountIdConvert,
+	CurrencyId,
+	CurrencyIdConvert,
+	DepositQuarantine,
+>;
+
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::<Runtime>::default()
+			.build_storage()
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
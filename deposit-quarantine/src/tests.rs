@@ -0,0 +1,126 @@
+//! Unit tests for the deposit quarantine module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::*;
+use sp_runtime::traits::BadOrigin;
+use xcm::v5::prelude::*;
+use xcm_executor::traits::TransactAsset;
+
+fn deposit_relay_token(amount: u128, who: AccountId) -> XcmResult {
+	LocalAssetTransactor::deposit_asset(
+		&(Location::parent(), amount).into(),
+		&Junction::AccountId32 {
+			network: None,
+			id: who.into(),
+		}
+		.into(),
+		None,
+	)
+}
+
+#[test]
+fn failed_deposit_is_quarantined() {
+	ExtBuilder.build().execute_with(|| {
+		// below existential deposit
+		assert_ok!(deposit_relay_token(5, ALICE));
+		assert_eq!(Tokens::free_balance(RELAY_TOKEN, &ALICE), 0);
+		assert_eq!(
+			QuarantinedDeposits::<Runtime>::get(&ALICE, RELAY_TOKEN),
+			Some(QuarantinedDeposit {
+				amount: 5,
+				last_deposit_at: 1,
+			})
+		);
+		System::assert_last_event(RuntimeEvent::DepositQuarantine(crate::Event::Quarantined {
+			currency_id: RELAY_TOKEN,
+			who: ALICE,
+			amount: 5,
+		}));
+
+		System::set_block_number(2);
+		assert_ok!(deposit_relay_token(3, ALICE));
+		assert_eq!(
+			QuarantinedDeposits::<Runtime>::get(&ALICE, RELAY_TOKEN),
+			Some(QuarantinedDeposit {
+				amount: 8,
+				last_deposit_at: 2,
+			})
+		);
+
+		// succeeded deposit is not quarantined
+		assert_ok!(deposit_relay_token(20, ALICE));
+		assert_eq!(Tokens::free_balance(RELAY_TOKEN, &ALICE), 20);
+		assert_eq!(
+			QuarantinedDeposits::<Runtime>::get(&ALICE, RELAY_TOKEN).map(|d| d.amount),
+			Some(8)
+		);
+	});
+}
+
+#[test]
+fn claim_works() {
+	ExtBuilder.build().execute_with(|| {
+		assert_noop!(
+			DepositQuarantine::claim(RuntimeOrigin::signed(ALICE), RELAY_TOKEN),
+			Error::<Runtime>::NoQuarantinedDeposit
+		);
+
+		assert_ok!(deposit_relay_token(5, ALICE));
+
+		// still below existential deposit
+		assert_noop!(
+			DepositQuarantine::claim(RuntimeOrigin::signed(ALICE), RELAY_TOKEN),
+			orml_tokens::Error::<Runtime>::ExistentialDeposit
+		);
+
+		assert_ok!(Tokens::deposit(RELAY_TOKEN, &ALICE, 10));
+		assert_ok!(DepositQuarantine::claim(RuntimeOrigin::signed(ALICE), RELAY_TOKEN));
+		assert_eq!(Tokens::free_balance(RELAY_TOKEN, &ALICE), 15);
+		assert_eq!(QuarantinedDeposits::<Runtime>::get(&ALICE, RELAY_TOKEN), None);
+		System::assert_last_event(RuntimeEvent::DepositQuarantine(crate::Event::Claimed {
+			currency_id: RELAY_TOKEN,
+			who: ALICE,
+			amount: 5,
+		}));
+	});
+}
+
+#[test]
+fn sweep_works() {
+	ExtBuilder.build().execute_with(|| {
+		assert_ok!(deposit_relay_token(5, ALICE));
+		assert_ok!(Tokens::deposit(RELAY_TOKEN, &TREASURY, 10));
+
+		assert_noop!(
+			DepositQuarantine::sweep(RuntimeOrigin::signed(ALICE), ALICE, RELAY_TOKEN, TREASURY),
+			BadOrigin
+		);
+		assert_noop!(
+			DepositQuarantine::sweep(RuntimeOrigin::root(), ALICE, RELAY_TOKEN, TREASURY),
+			Error::<Runtime>::ClaimPeriodNotEnded
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			DepositQuarantine::sweep(RuntimeOrigin::root(), TREASURY, RELAY_TOKEN, TREASURY),
+			Error::<Runtime>::NoQuarantinedDeposit
+		);
+		assert_ok!(DepositQuarantine::sweep(
+			RuntimeOrigin::root(),
+			ALICE,
+			RELAY_TOKEN,
+			TREASURY
+		));
+		assert_eq!(Tokens::free_balance(RELAY_TOKEN, &TREASURY), 15);
+		assert_eq!(QuarantinedDeposits::<Runtime>::get(&ALICE, RELAY_TOKEN), None);
+		System::assert_last_event(RuntimeEvent::DepositQuarantine(crate::Event::Swept {
+			currency_id: RELAY_TOKEN,
+			who: ALICE,
+			amount: 5,
+			to: TREASURY,
+		}));
+	});
+}
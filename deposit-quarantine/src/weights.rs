@@ -0,0 +1,27 @@
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for orml_deposit_quarantine.
+pub trait WeightInfo {
+	fn claim() -> Weight;
+	fn sweep() -> Weight;
+}
+
+/// Default weights.
+impl WeightInfo for () {
+	fn claim() -> Weight {
+		Weight::from_parts(25_160_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn sweep() -> Weight {
+		Weight::from_parts(23_736_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+}
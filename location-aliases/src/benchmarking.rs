@@ -0,0 +1,51 @@
+pub use crate::*;
+
+use frame_benchmarking::v2::*;
+
+fn alias() -> Location {
+	Location::new(1, [Parachain(1000), PalletInstance(50), GeneralIndex(0)])
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	// worst case: replace the canonical location of an existing alias
+	#[benchmark]
+	fn set_location_alias() -> Result<(), BenchmarkError> {
+		let origin = T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		Pallet::<T>::set_location_alias(
+			origin.clone(),
+			Box::new(alias().into()),
+			Box::new(Location::parent().into()),
+		)?;
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			Box::new(alias().into()),
+			Box::new(Location::here().into()),
+		);
+
+		assert_eq!(LocationAliases::<T>::get(alias()), Some(Location::here()));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_location_alias() -> Result<(), BenchmarkError> {
+		let origin = T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		Pallet::<T>::set_location_alias(
+			origin.clone(),
+			Box::new(alias().into()),
+			Box::new(Location::parent().into()),
+		)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Box::new(alias().into()));
+
+		assert!(!LocationAliases::<T>::contains_key(alias()));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder.build(), crate::mock::Runtime);
+}
@@ -0,0 +1,150 @@
+//! # Location Aliases
+//!
+//! A runtime managed, many-to-one table from alias locations to a canonical
+//! location. Derivative or wrapped assets may arrive under several locations,
+//! all of which should map to the same currency. Implements `GetByKey` so it
+//! could be used as `Aliases` of `orml_xcm_support::AliasedCurrencyIdConvert`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use orml_traits::GetByKey;
+use sp_std::boxed::Box;
+use xcm::{v5::prelude::*, VersionedLocation};
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod mock;
+mod tests;
+mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The origin which may set and remove location aliases.
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// `alias` was set as an alias of `canonical`.
+		LocationAliasSet { alias: Location, canonical: Location },
+		/// `alias` was removed.
+		LocationAliasRemoved { alias: Location },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The version of the `VersionedLocation` value used is not able to be
+		/// interpreted.
+		BadVersion,
+		/// The alias is the canonical location of other aliases.
+		AliasIsCanonical,
+		/// The canonical location is an alias itself.
+		CanonicalIsAlias,
+		/// The alias doesn't exist.
+		AliasNotFound,
+	}
+
+	/// The canonical location of an alias location.
+	///
+	/// map: alias => canonical
+	#[pallet::storage]
+	pub type LocationAliases<T: Config> = StorageMap<_, Blake2_128Concat, Location, Location, OptionQuery>;
+
+	/// The number of aliases of a canonical location.
+	///
+	/// map: canonical => count
+	#[pallet::storage]
+	pub type AliasCount<T: Config> = StorageMap<_, Blake2_128Concat, Location, u32, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set `alias` as an alias of `canonical`, replacing its previous
+		/// canonical location if any.
+		///
+		/// The dispatch origin for this call must be `UpdateOrigin`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_location_alias())]
+		pub fn set_location_alias(
+			origin: OriginFor<T>,
+			alias: Box<VersionedLocation>,
+			canonical: Box<VersionedLocation>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let alias: Location = (*alias).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let canonical: Location = (*canonical).try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			// Aliases are resolved once, so chains are not allowed.
+			ensure!(
+				alias != canonical && AliasCount::<T>::get(&alias) == 0,
+				Error::<T>::AliasIsCanonical
+			);
+			ensure!(
+				!LocationAliases::<T>::contains_key(&canonical),
+				Error::<T>::CanonicalIsAlias
+			);
+
+			if let Some(old_canonical) = LocationAliases::<T>::get(&alias) {
+				Self::decrease_alias_count(&old_canonical);
+			}
+			LocationAliases::<T>::insert(&alias, &canonical);
+			AliasCount::<T>::mutate(&canonical, |count| *count = count.saturating_add(1));
+
+			Self::deposit_event(Event::LocationAliasSet { alias, canonical });
+			Ok(())
+		}
+
+		/// Remove `alias`.
+		///
+		/// The dispatch origin for this call must be `UpdateOrigin`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::remove_location_alias())]
+		pub fn remove_location_alias(origin: OriginFor<T>, alias: Box<VersionedLocation>) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let alias: Location = (*alias).try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			let canonical = LocationAliases::<T>::take(&alias).ok_or(Error::<T>::AliasNotFound)?;
+			Self::decrease_alias_count(&canonical);
+
+			Self::deposit_event(Event::LocationAliasRemoved { alias });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The canonical location of `location`, which is `location` itself if it
+	/// is not an alias.
+	pub fn canonical_location(location: Location) -> Location {
+		LocationAliases::<T>::get(&location).unwrap_or(location)
+	}
+
+	fn decrease_alias_count(canonical: &Location) {
+		AliasCount::<T>::mutate_exists(canonical, |maybe_count| {
+			let count = maybe_count.unwrap_or_default().saturating_sub(1);
+			*maybe_count = (count > 0).then_some(count);
+		});
+	}
+}
+
+impl<T: Config> GetByKey<Location, Option<Location>> for Pallet<T> {
+	fn get(alias: &Location) -> Option<Location> {
+		LocationAliases::<T>::get(alias)
+	}
+}
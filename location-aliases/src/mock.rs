@@ -0,0 +1,124 @@
+//! Mocks for the location aliases module.
+
+#![cfg(test)]
+
+use super::*;
+use orml_xcm_support::{AliasedCurrencyIdConvert, IsNativeConcrete, MultiCurrencyAdapter};
+
+use frame_support::{
+	construct_runtime, derive_impl,
+	traits::{ConstU32, Nothing},
+};
+use frame_system::EnsureRoot;
+use orml_traits::parameter_type_with_key;
+use sp_runtime::{
+	traits::{Convert, IdentityLookup},
+	AccountId32, BuildStorage,
+};
+use xcm_executor::traits::ConvertLocation;
+
+pub type AccountId = AccountId32;
+pub type CurrencyId = u32;
+pub type Balance = u64;
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Balance = Balance;
+	type Amount = i64;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type CurrencyHooks = ();
+	type MaxLocks = ConstU32<100>;
+	type MaxReserves = ConstU32<100>;
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = Nothing;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+impl Config for Runtime {
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = ();
+}
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+use crate as location_aliases;
+
+construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Tokens: orml_tokens,
+		LocationAliasesModule: location_aliases,
+	}
+);
+
+pub const ALICE: AccountId = AccountId32::new([1u8; 32]);
+pub const DOT: CurrencyId = 0;
+
+/// The canonical location of `DOT`.
+pub fn dot_location() -> Location {
+	Location::parent()
+}
+
+/// A wrapped `DOT` on a sibling chain.
+pub fn wrapped_dot_location() -> Location {
+	Location::new(1, [Parachain(1000), PalletInstance(50), GeneralIndex(0)])
+}
+
+pub struct CurrencyIdConvert;
+impl Convert<Location, Option<CurrencyId>> for CurrencyIdConvert {
+	fn convert(location: Location) -> Option<CurrencyId> {
+		(location == dot_location()).then_some(DOT)
+	}
+}
+
+pub type AliasedConvert = AliasedCurrencyIdConvert<LocationAliasesModule, CurrencyIdConvert>;
+
+pub struct AccountIdConvert;
+impl ConvertLocation<AccountId> for AccountIdConvert {
+	fn convert_location(location: &Location) -> Option<AccountId> {
+		match location.unpack() {
+			(0, [Junction::AccountId32 { id, .. }]) => Some((*id).into()),
+			_ => None,
+		}
+	}
+}
+
+pub type LocalAssetTransactor = MultiCurrencyAdapter<
+	Tokens,
+	(),
+	IsNativeConcrete<CurrencyId, AliasedConvert>,
+	AccountId,
+	AccountIdConvert,
+	CurrencyId,
+	AliasedConvert,
+	(),
+>;
+
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::<Runtime>::default()
+			.build_storage()
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,170 @@
+//! Unit tests for the location aliases module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::*;
+use orml_traits::MultiCurrency;
+use orml_xcm_support::IsNativeConcrete;
+use sp_runtime::traits::{BadOrigin, Convert};
+use xcm_executor::traits::{MatchesFungible, TransactAsset};
+
+fn alice_location() -> Location {
+	Junction::AccountId32 {
+		network: None,
+		id: ALICE.into(),
+	}
+	.into()
+}
+
+fn set_wrapped_dot_alias() -> DispatchResult {
+	LocationAliasesModule::set_location_alias(
+		RuntimeOrigin::root(),
+		Box::new(wrapped_dot_location().into()),
+		Box::new(dot_location().into()),
+	)
+}
+
+#[test]
+fn set_location_alias_works() {
+	ExtBuilder.build().execute_with(|| {
+		assert_noop!(
+			LocationAliasesModule::set_location_alias(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(wrapped_dot_location().into()),
+				Box::new(dot_location().into()),
+			),
+			BadOrigin
+		);
+
+		assert_ok!(set_wrapped_dot_alias());
+		assert_eq!(
+			LocationAliases::<Runtime>::get(wrapped_dot_location()),
+			Some(dot_location())
+		);
+		assert_eq!(AliasCount::<Runtime>::get(dot_location()), 1);
+		System::assert_last_event(RuntimeEvent::LocationAliasesModule(crate::Event::LocationAliasSet {
+			alias: wrapped_dot_location(),
+			canonical: dot_location(),
+		}));
+
+		// the canonical location can't be an alias
+		assert_noop!(
+			LocationAliasesModule::set_location_alias(
+				RuntimeOrigin::root(),
+				Box::new(dot_location().into()),
+				Box::new(Location::here().into()),
+			),
+			Error::<Runtime>::AliasIsCanonical
+		);
+		// an alias can't be the canonical location
+		assert_noop!(
+			LocationAliasesModule::set_location_alias(
+				RuntimeOrigin::root(),
+				Box::new(Location::here().into()),
+				Box::new(wrapped_dot_location().into()),
+			),
+			Error::<Runtime>::CanonicalIsAlias
+		);
+		assert_noop!(
+			LocationAliasesModule::set_location_alias(
+				RuntimeOrigin::root(),
+				Box::new(Location::here().into()),
+				Box::new(Location::here().into()),
+			),
+			Error::<Runtime>::AliasIsCanonical
+		);
+
+		// replace the canonical location
+		assert_ok!(LocationAliasesModule::set_location_alias(
+			RuntimeOrigin::root(),
+			Box::new(wrapped_dot_location().into()),
+			Box::new(Location::here().into()),
+		));
+		assert_eq!(AliasCount::<Runtime>::get(dot_location()), 0);
+		assert_eq!(AliasCount::<Runtime>::get(Location::here()), 1);
+	});
+}
+
+#[test]
+fn remove_location_alias_works() {
+	ExtBuilder.build().execute_with(|| {
+		assert_noop!(
+			LocationAliasesModule::remove_location_alias(
+				RuntimeOrigin::root(),
+				Box::new(wrapped_dot_location().into())
+			),
+			Error::<Runtime>::AliasNotFound
+		);
+
+		assert_ok!(set_wrapped_dot_alias());
+		assert_noop!(
+			LocationAliasesModule::remove_location_alias(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(wrapped_dot_location().into())
+			),
+			BadOrigin
+		);
+		assert_ok!(LocationAliasesModule::remove_location_alias(
+			RuntimeOrigin::root(),
+			Box::new(wrapped_dot_location().into())
+		));
+		assert_eq!(LocationAliases::<Runtime>::get(wrapped_dot_location()), None);
+		assert!(!AliasCount::<Runtime>::contains_key(dot_location()));
+		System::assert_last_event(RuntimeEvent::LocationAliasesModule(
+			crate::Event::LocationAliasRemoved {
+				alias: wrapped_dot_location(),
+			},
+		));
+	});
+}
+
+#[test]
+fn aliased_currency_id_convert_works() {
+	ExtBuilder.build().execute_with(|| {
+		let wrapped_dot: Asset = (wrapped_dot_location(), 100u128).into();
+		assert_eq!(AliasedConvert::convert(dot_location()), Some(DOT));
+		assert_eq!(AliasedConvert::convert(wrapped_dot_location()), None);
+		assert_eq!(
+			<IsNativeConcrete<CurrencyId, AliasedConvert> as MatchesFungible<u128>>::matches_fungible(&wrapped_dot),
+			None
+		);
+
+		assert_ok!(set_wrapped_dot_alias());
+		assert_eq!(AliasedConvert::convert(wrapped_dot_location()), Some(DOT));
+		assert_eq!(AliasedConvert::convert(wrapped_dot.clone()), Some(DOT));
+		assert_eq!(
+			<IsNativeConcrete<CurrencyId, AliasedConvert> as MatchesFungible<u128>>::matches_fungible(&wrapped_dot),
+			Some(100)
+		);
+	});
+}
+
+#[test]
+fn deposit_and_withdraw_through_alias_works() {
+	ExtBuilder.build().execute_with(|| {
+		let wrapped_dot: Asset = (wrapped_dot_location(), 100u128).into();
+		assert!(LocalAssetTransactor::deposit_asset(&wrapped_dot, &alice_location(), None).is_err());
+
+		assert_ok!(set_wrapped_dot_alias());
+		assert_ok!(LocalAssetTransactor::deposit_asset(
+			&wrapped_dot,
+			&alice_location(),
+			None
+		));
+		assert_ok!(LocalAssetTransactor::deposit_asset(
+			&(dot_location(), 50u128).into(),
+			&alice_location(),
+			None
+		));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 150);
+
+		assert_ok!(LocalAssetTransactor::withdraw_asset(
+			&(wrapped_dot_location(), 120u128).into(),
+			&alice_location(),
+			None
+		));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 30);
+	});
+}
@@ -0,0 +1,27 @@
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for orml_location_aliases.
+pub trait WeightInfo {
+	fn set_location_alias() -> Weight;
+	fn remove_location_alias() -> Weight;
+}
+
+/// Default weights.
+impl WeightInfo for () {
+	fn set_location_alias() -> Weight {
+		Weight::from_parts(17_611_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn remove_location_alias() -> Weight {
+		Weight::from_parts(9_319_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+}
@@ -0,0 +1,70 @@
+pub use crate::*;
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::Time;
+use frame_system::Pallet as System;
+use orml_oracle::BenchmarkHelper;
+
+#[instance_benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn on_initialize(
+		m: Linear<0, { <T as orml_oracle::Config<I>>::MaxHasDispatchedSize::get() }>,
+	) -> Result<(), BenchmarkError> {
+		// Every feeder of the round has `MaxPendingValues` pending values, and
+		// is punished, which costs more than rewarding it.
+		let pairs = <T as orml_oracle::Config<I>>::BenchmarkHelper::get_currency_id_value_pairs();
+		let now = <T as orml_oracle::Config<I>>::Time::now();
+		for (key, value) in pairs.iter() {
+			orml_oracle::Values::<T, I>::insert(
+				key,
+				orml_oracle::TimestampedValue {
+					value: value.clone(),
+					timestamp: now,
+				},
+			);
+		}
+		let pending: BoundedVec<_, T::MaxPendingValues> = pairs
+			.into_iter()
+			.cycle()
+			.take(T::MaxPendingValues::get() as usize)
+			.collect::<Vec<_>>()
+			.try_into()
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		let currency_id = T::RewardCurrencyId::get();
+		for i in 0..m {
+			let feeder: T::AccountId = account("feeder", i, 0);
+			T::Currency::deposit(currency_id, &feeder, T::SlashAmount::get())?;
+			PendingValues::<T, I>::insert(&feeder, pending.clone());
+			RoundRecords::<T, I>::insert(
+				&feeder,
+				RoundRecord {
+					valid: 0,
+					outliers: T::MaxPendingValues::get().saturating_add(1),
+				},
+			);
+			Strikes::<T, I>::insert(&feeder, T::MaxStrikes::get().saturating_sub(1));
+		}
+		let block = T::RoundLength::get();
+		// events are only deposited after genesis
+		System::<T>::set_block_number(block);
+
+		#[block]
+		{
+			Pallet::<T, I>::on_initialize(block);
+		}
+
+		assert_eq!(RoundRecords::<T, I>::iter().count(), 0);
+		assert_eq!(PendingValues::<T, I>::iter().count(), 0);
+		Ok(())
+	}
+
+	impl_benchmark_test_suite! {
+		Pallet,
+		crate::mock::new_test_ext(),
+		crate::mock::Test,
+	}
+}
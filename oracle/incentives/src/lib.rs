@@ -0,0 +1,321 @@
+//! # Oracle Incentives
+//! A companion module of `orml_oracle` to reward and punish oracle feeders.
+//!
+//! ## Overview
+//!
+//! Set as the `OnNewData` hook of an oracle instance, this module compares
+//! each fed value with the combined value of its key before the feed, which
+//! it keeps itself. Values within `MaxDeviation` of it are valid. The other
+//! values, and values fed before their key has a combined value, are pending
+//! until the end of the round, when they're compared with the combined value
+//! then. This way, feeders reporting a real move first aren't punished.
+//! Values still deviating by more than `MaxDeviation` are outliers.
+//!
+//! At the end of each round of `RoundLength` blocks, feeders which fed values
+//! without outliers share `RewardPerRound` from the pot. Feeders which posted
+//! more outliers than valid values get a strike, and a round without outliers
+//! clears them. Feeders reaching `MaxStrikes` strikes are slashed into the pot
+//! and reported to `OnMisbehavingFeeder`, so the runtime can remove them from
+//! the oracle members.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, traits::ExistenceRequirement, PalletId};
+use frame_system::pallet_prelude::*;
+use orml_oracle::TwapValue;
+use orml_traits::{MultiCurrency, OnNewData};
+use sp_runtime::{
+	traits::{AccountIdConversion, Saturating, Zero},
+	Permill,
+};
+use sp_std::prelude::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod mock;
+mod tests;
+mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// Handler of feeders reaching `MaxStrikes` strikes.
+pub trait OnMisbehavingFeeder<AccountId> {
+	/// `who` kept posting outliers.
+	fn on_misbehaving_feeder(who: &AccountId);
+}
+
+impl<AccountId> OnMisbehavingFeeder<AccountId> for () {
+	fn on_misbehaving_feeder(_who: &AccountId) {}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub(crate) type BalanceOf<T, I = ()> =
+		<<T as Config<I>>::Currency as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub(crate) type CurrencyIdOf<T, I = ()> =
+		<<T as Config<I>>::Currency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+
+	/// The values fed by a feeder in the current round.
+	#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq, Default, TypeInfo, MaxEncodedLen)]
+	pub struct RoundRecord {
+		/// The number of values close to the combined value.
+		pub valid: u32,
+		/// The number of values deviating by more than `MaxDeviation`.
+		pub outliers: u32,
+	}
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config + orml_oracle::Config<I, OracleValue: TwapValue> {
+		/// The currency to pay rewards and slash feeders
+		type Currency: MultiCurrency<Self::AccountId>;
+
+		/// The currency id of rewards and slashes
+		#[pallet::constant]
+		type RewardCurrencyId: Get<CurrencyIdOf<Self, I>>;
+
+		/// The rewards shared by the feeders of a round
+		#[pallet::constant]
+		type RewardPerRound: Get<BalanceOf<Self, I>>;
+
+		/// The number of blocks of a round
+		#[pallet::constant]
+		type RoundLength: Get<BlockNumberFor<Self>>;
+
+		/// The maximum deviation of a value from the combined value, above
+		/// which it's an outlier
+		#[pallet::constant]
+		type MaxDeviation: Get<Permill>;
+
+		/// The number of strikes after which a feeder is punished
+		#[pallet::constant]
+		type MaxStrikes: Get<u32>;
+
+		/// The amount slashed from a punished feeder into the pot
+		#[pallet::constant]
+		type SlashAmount: Get<BalanceOf<Self, I>>;
+
+		/// The id of the pot account
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The maximum number of pending values of a feeder in a round.
+		/// Further values of other keys aren't recorded.
+		#[pallet::constant]
+		type MaxPendingValues: Get<u32>;
+
+		/// Handler of punished feeders
+		type OnMisbehavingFeeder: OnMisbehavingFeeder<Self::AccountId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// A feeder fed an outlier.
+		OutlierFed {
+			who: T::AccountId,
+			key: T::OracleKey,
+			value: T::OracleValue,
+			deviation: Permill,
+		},
+		/// A feeder is rewarded for a round.
+		FeederRewarded { who: T::AccountId, amount: BalanceOf<T, I> },
+		/// A feeder got a strike for posting outliers in a round.
+		FeederStruck { who: T::AccountId, strikes: u32 },
+		/// A feeder reached `MaxStrikes` strikes and is punished.
+		FeederPunished {
+			who: T::AccountId,
+			slashed: BalanceOf<T, I>,
+		},
+	}
+
+	/// The values fed by each feeder in the current round
+	#[pallet::storage]
+	#[pallet::getter(fn round_records)]
+	pub type RoundRecords<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, RoundRecord, ValueQuery>;
+
+	/// The strikes of each feeder
+	#[pallet::storage]
+	#[pallet::getter(fn strikes)]
+	pub type Strikes<T: Config<I>, I: 'static = ()> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The combined value of each key after its last feed
+	#[pallet::storage]
+	#[pallet::getter(fn last_combined_values)]
+	pub type LastCombinedValues<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::OracleKey, T::OracleValue>;
+
+	/// The values of each feeder to compare with the combined value at the
+	/// end of the round
+	#[pallet::storage]
+	#[pallet::getter(fn pending_values)]
+	pub type PendingValues<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<(T::OracleKey, T::OracleValue), T::MaxPendingValues>,
+		ValueQuery,
+	>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		/// End the round every `RoundLength` blocks, weighing the number of
+		/// feeders of the round, at most the oracle members.
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let round_length = T::RoundLength::get();
+			if round_length.is_zero() || !(now % round_length).is_zero() {
+				return Weight::zero();
+			}
+			let feeders = Self::end_round();
+			<T as Config<I>>::WeightInfo::on_initialize(feeders)
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// The pot account paying rewards.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	/// The deviation of `value` from `combined`, saturating at 100%.
+	fn deviation(value: &T::OracleValue, combined: &T::OracleValue) -> Permill {
+		let combined = combined.clone().into_accumulator();
+		let diff = value.clone().into_accumulator().abs_diff(combined);
+		if diff.is_zero() {
+			Permill::zero()
+		} else {
+			Permill::from_rational(diff, combined)
+		}
+	}
+
+	/// Compare the pending values with the combined values, then reward the
+	/// feeders without outliers, and strike the ones which posted more
+	/// outliers than valid values. Returns the number of feeders of the round.
+	fn end_round() -> u32 {
+		let mut unrecorded: u32 = 0;
+		for (who, values) in PendingValues::<T, I>::drain() {
+			let mut recorded = false;
+			for (key, value) in values {
+				// values of keys which still have no combined value are dropped
+				if let Some(combined) = orml_oracle::Pallet::<T, I>::get(&key) {
+					Self::record_value(&who, &key, &value, &combined.value);
+					recorded = true;
+				}
+			}
+			if !recorded && !RoundRecords::<T, I>::contains_key(&who) {
+				unrecorded = unrecorded.saturating_add(1);
+			}
+		}
+
+		let records: Vec<(T::AccountId, RoundRecord)> = RoundRecords::<T, I>::drain().collect();
+		let currency_id = T::RewardCurrencyId::get();
+		let pot = Self::account_id();
+
+		let rewarded: Vec<&T::AccountId> = records
+			.iter()
+			.filter(|(_, record)| record.outliers.is_zero() && !record.valid.is_zero())
+			.map(|(who, _)| who)
+			.collect();
+		if !rewarded.is_empty() {
+			let amount = T::RewardPerRound::get() / BalanceOf::<T, I>::from(rewarded.len() as u32);
+			for who in rewarded {
+				// the pot may be exhausted
+				if T::Currency::transfer(currency_id, &pot, who, amount, ExistenceRequirement::KeepAlive).is_ok() {
+					Self::deposit_event(Event::FeederRewarded {
+						who: who.clone(),
+						amount,
+					});
+				}
+			}
+		}
+
+		for (who, record) in &records {
+			if record.outliers.is_zero() {
+				Strikes::<T, I>::remove(who);
+			} else if record.outliers > record.valid {
+				let strikes = Strikes::<T, I>::mutate(who, |strikes| {
+					*strikes = strikes.saturating_add(1);
+					*strikes
+				});
+				if strikes < T::MaxStrikes::get() {
+					Self::deposit_event(Event::FeederStruck {
+						who: who.clone(),
+						strikes,
+					});
+					continue;
+				}
+
+				Strikes::<T, I>::remove(who);
+				let slash_amount = T::SlashAmount::get();
+				let slashed = slash_amount.saturating_sub(T::Currency::slash(currency_id, who, slash_amount));
+				let _ = T::Currency::deposit(currency_id, &pot, slashed);
+				T::OnMisbehavingFeeder::on_misbehaving_feeder(who);
+				Self::deposit_event(Event::FeederPunished {
+					who: who.clone(),
+					slashed,
+				});
+			}
+		}
+
+		(records.len() as u32).saturating_add(unrecorded)
+	}
+
+	/// Record `value` fed by `who` for `key` as valid or an outlier, depending
+	/// on its deviation from `combined`.
+	fn record_value(who: &T::AccountId, key: &T::OracleKey, value: &T::OracleValue, combined: &T::OracleValue) {
+		let deviation = Self::deviation(value, combined);
+		RoundRecords::<T, I>::mutate(who, |record| {
+			if deviation > T::MaxDeviation::get() {
+				record.outliers = record.outliers.saturating_add(1);
+				Self::deposit_event(Event::OutlierFed {
+					who: who.clone(),
+					key: key.clone(),
+					value: value.clone(),
+					deviation,
+				});
+			} else {
+				record.valid = record.valid.saturating_add(1);
+			}
+		});
+	}
+}
+
+impl<T: Config<I>, I: 'static> OnNewData<T::AccountId, T::OracleKey, T::OracleValue> for Pallet<T, I> {
+	fn on_new_data(who: &T::AccountId, key: &T::OracleKey, value: &T::OracleValue) {
+		if *who == <T as orml_oracle::Config<I>>::RootOperatorAccountId::get() {
+			return;
+		}
+		let current = orml_oracle::Pallet::<T, I>::get(key).map(|x| x.value);
+		let previous = match current {
+			Some(current) => LastCombinedValues::<T, I>::mutate(key, |last| last.replace(current)),
+			None => LastCombinedValues::<T, I>::take(key),
+		};
+
+		if let Some(previous) = previous {
+			if Self::deviation(value, &previous) <= T::MaxDeviation::get() {
+				RoundRecords::<T, I>::mutate(who, |record| record.valid = record.valid.saturating_add(1));
+				return;
+			}
+		}
+
+		// compare with the combined value at the end of the round
+		PendingValues::<T, I>::mutate(who, |values| {
+			if let Some(pending) = values.iter_mut().find(|(pending_key, _)| pending_key == key) {
+				pending.1 = value.clone();
+			} else {
+				let _ = values.try_push((key.clone(), value.clone()));
+			}
+		});
+	}
+}
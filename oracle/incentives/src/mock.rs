@@ -0,0 +1,166 @@
+#![cfg(test)]
+
+use super::*;
+
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64, Nothing, SortedMembers, Time},
+};
+use frame_system::EnsureRoot;
+use orml_oracle::DefaultCombineData;
+use orml_traits::parameter_type_with_key;
+use sp_runtime::{traits::IdentityLookup, BuildStorage};
+
+use std::cell::RefCell;
+
+mod oracle_incentives {
+	pub use super::super::*;
+}
+
+pub type AccountId = u128;
+pub type Balance = u64;
+pub type CurrencyId = u32;
+type Key = u32;
+type Value = u32;
+
+pub const REWARD_CURRENCY: CurrencyId = 1;
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Test {
+	type Balance = Balance;
+	type Amount = i64;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type CurrencyHooks = ();
+	type MaxLocks = ConstU32<10>;
+	type MaxReserves = ConstU32<10>;
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = Nothing;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+thread_local! {
+	static MISBEHAVING_FEEDERS: RefCell<Vec<AccountId>> = const { RefCell::new(vec![]) };
+}
+
+pub struct Timestamp;
+impl Time for Timestamp {
+	type Moment = u32;
+
+	fn now() -> Self::Moment {
+		12345
+	}
+}
+
+parameter_types! {
+	pub const RootOperatorAccountId: AccountId = 5;
+	pub const MaxFeedValues: u32 = 5;
+}
+
+pub struct Members;
+impl SortedMembers<AccountId> for Members {
+	fn sorted_members() -> Vec<AccountId> {
+		vec![1, 2, 3, 4]
+	}
+}
+
+impl orml_oracle::Config for Test {
+	type OnNewData = OracleIncentives;
+	type CombineData = DefaultCombineData<Self, ConstU32<3>, ConstU32<600>>;
+	type Time = Timestamp;
+	type OracleKey = Key;
+	type OracleValue = Value;
+	type RootOperatorAccountId = RootOperatorAccountId;
+	type Members = Members;
+	type WeightInfo = ();
+	type MaxHasDispatchedSize = ConstU32<100>;
+	type MaxFeedValues = MaxFeedValues;
+	type GovernanceOrigin = EnsureRoot<AccountId>;
+	type MaxFeedersPerGroup = ConstU32<5>;
+	type MaxTwapHistory = ConstU32<10>;
+	type OffchainWorker = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = MockBenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl orml_oracle::BenchmarkHelper<Key, Value, MaxFeedValues> for MockBenchmarkHelper {
+	fn get_currency_id_value_pairs() -> BoundedVec<(Key, Value), MaxFeedValues> {
+		BoundedVec::truncate_from((0..MaxFeedValues::get()).map(|key| (key, 1_000)).collect())
+	}
+}
+
+pub struct RecordMisbehavingFeeders;
+impl OnMisbehavingFeeder<AccountId> for RecordMisbehavingFeeders {
+	fn on_misbehaving_feeder(who: &AccountId) {
+		MISBEHAVING_FEEDERS.with(|v| v.borrow_mut().push(*who));
+	}
+}
+
+pub fn misbehaving_feeders() -> Vec<AccountId> {
+	MISBEHAVING_FEEDERS.with(|v| v.borrow().clone())
+}
+
+parameter_types! {
+	pub const RewardCurrencyId: CurrencyId = REWARD_CURRENCY;
+	pub const MaxDeviation: Permill = Permill::from_percent(5);
+	pub const OracleIncentivesPalletId: PalletId = PalletId(*b"orml/ori");
+}
+
+impl Config for Test {
+	type Currency = Tokens;
+	type RewardCurrencyId = RewardCurrencyId;
+	type RewardPerRound = ConstU64<100>;
+	type RoundLength = ConstU64<5>;
+	type MaxDeviation = MaxDeviation;
+	type MaxStrikes = ConstU32<2>;
+	type SlashAmount = ConstU64<20>;
+	type PalletId = OracleIncentivesPalletId;
+	type MaxPendingValues = ConstU32<2>;
+	type OnMisbehavingFeeder = RecordMisbehavingFeeders;
+	type WeightInfo = ();
+}
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Tokens: orml_tokens,
+		ModuleOracle: orml_oracle,
+		OracleIncentives: oracle_incentives,
+	}
+);
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+
+	orml_tokens::GenesisConfig::<Test> {
+		balances: vec![
+			(OracleIncentives::account_id(), REWARD_CURRENCY, 1000),
+			(4, REWARD_CURRENCY, 50),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,170 @@
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_ok, traits::Hooks};
+use mock::*;
+use orml_traits::DataFeeder;
+
+fn feed(who: AccountId, value: u32) {
+	assert_ok!(ModuleOracle::feed_value(Some(who), 50, value));
+}
+
+fn balance(who: AccountId) -> Balance {
+	Tokens::free_balance(REWARD_CURRENCY, &who)
+}
+
+#[test]
+fn should_track_outliers() {
+	new_test_ext().execute_with(|| {
+		// pending until the key has a combined value
+		feed(1, 1000);
+		feed(2, 1000);
+		feed(3, 1010);
+		assert_eq!(OracleIncentives::round_records(1), Default::default());
+		assert_eq!(OracleIncentives::pending_values(3).into_inner(), vec![(50, 1010)]);
+		assert_eq!(OracleIncentives::last_combined_values(50), Some(1000));
+
+		// valid compared with the combined value before the feed
+		feed(1, 1005);
+		assert_eq!(
+			OracleIncentives::round_records(1),
+			RoundRecord { valid: 1, outliers: 0 }
+		);
+
+		// pending, as it deviates from the combined value before the feed
+		feed(4, 2000);
+		assert_eq!(OracleIncentives::pending_values(4).into_inner(), vec![(50, 2000)]);
+		assert_eq!(OracleIncentives::last_combined_values(50), Some(1010));
+
+		// the root operator is ignored
+		assert_ok!(ModuleOracle::feed_value(None, 50, 3000));
+		assert!(OracleIncentives::pending_values(5).is_empty());
+
+		OracleIncentives::on_initialize(5);
+		System::assert_has_event(RuntimeEvent::OracleIncentives(crate::Event::OutlierFed {
+			who: 4,
+			key: 50,
+			value: 2000,
+			deviation: Permill::from_rational(990u32, 1010u32),
+		}));
+		for who in [1, 2, 3] {
+			System::assert_has_event(RuntimeEvent::OracleIncentives(crate::Event::FeederRewarded {
+				who,
+				amount: 33,
+			}));
+		}
+		assert_eq!(OracleIncentives::strikes(4), 1);
+		assert!(OracleIncentives::pending_values(1).is_empty());
+	});
+}
+
+#[test]
+fn should_not_punish_first_feeders_of_a_move() {
+	new_test_ext().execute_with(|| {
+		for who in 1..=4 {
+			feed(who, 1000);
+		}
+		OracleIncentives::on_initialize(5);
+
+		// the combined value only moves with the second feeder
+		feed(1, 1500);
+		feed(2, 1500);
+		assert_eq!(OracleIncentives::pending_values(1).into_inner(), vec![(50, 1500)]);
+		assert_eq!(OracleIncentives::pending_values(2).into_inner(), vec![(50, 1500)]);
+		feed(3, 1500);
+		assert_eq!(
+			OracleIncentives::round_records(3),
+			RoundRecord { valid: 1, outliers: 0 }
+		);
+		feed(4, 1000);
+
+		OracleIncentives::on_initialize(10);
+		assert_eq!(balance(1), 25 + 33);
+		assert_eq!(balance(2), 25 + 33);
+		assert_eq!(balance(3), 25 + 33);
+		assert_eq!(balance(4), 75);
+		assert_eq!(OracleIncentives::strikes(4), 1);
+	});
+}
+
+#[test]
+fn pending_values_should_be_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ModuleOracle::feed_value(Some(1), 50, 1000));
+		assert_ok!(ModuleOracle::feed_value(Some(1), 51, 1000));
+		assert_ok!(ModuleOracle::feed_value(Some(1), 52, 1000));
+		assert_eq!(
+			OracleIncentives::pending_values(1).into_inner(),
+			vec![(50, 1000), (51, 1000)]
+		);
+
+		// replaces the pending value of the key
+		assert_ok!(ModuleOracle::feed_value(Some(1), 50, 1100));
+		assert_eq!(
+			OracleIncentives::pending_values(1).into_inner(),
+			vec![(50, 1100), (51, 1000)]
+		);
+
+		// dropped as the keys have no combined value
+		OracleIncentives::on_initialize(5);
+		assert!(OracleIncentives::pending_values(1).is_empty());
+		assert_eq!(OracleIncentives::round_records(1), Default::default());
+	});
+}
+
+#[test]
+fn should_reward_and_punish_feeders_per_round() {
+	new_test_ext().execute_with(|| {
+		feed(1, 1000);
+		feed(2, 1000);
+		feed(3, 1010);
+		feed(4, 2000);
+
+		OracleIncentives::on_initialize(4);
+		assert_eq!(OracleIncentives::pending_values(3).into_inner(), vec![(50, 1010)]);
+
+		OracleIncentives::on_initialize(5);
+		assert_eq!(OracleIncentives::round_records(3), Default::default());
+		assert_eq!(balance(1), 33);
+		assert_eq!(balance(3), 33);
+		assert_eq!(OracleIncentives::strikes(4), 1);
+		System::assert_last_event(RuntimeEvent::OracleIncentives(crate::Event::FeederStruck {
+			who: 4,
+			strikes: 1,
+		}));
+
+		feed(1, 1000);
+		feed(2, 1000);
+		feed(4, 2000);
+
+		OracleIncentives::on_initialize(10);
+		assert_eq!(balance(1), 83);
+		assert_eq!(balance(2), 83);
+		assert_eq!(balance(4), 30);
+		assert_eq!(balance(OracleIncentives::account_id()), 821);
+		assert_eq!(OracleIncentives::strikes(4), 0);
+		assert_eq!(misbehaving_feeders(), vec![4]);
+		System::assert_last_event(RuntimeEvent::OracleIncentives(crate::Event::FeederPunished {
+			who: 4,
+			slashed: 20,
+		}));
+	});
+}
+
+#[test]
+fn round_without_outliers_should_clear_strikes() {
+	new_test_ext().execute_with(|| {
+		feed(1, 1000);
+		feed(2, 1000);
+		feed(3, 1000);
+		feed(4, 2000);
+		OracleIncentives::on_initialize(5);
+		assert_eq!(OracleIncentives::strikes(4), 1);
+
+		feed(4, 1000);
+		OracleIncentives::on_initialize(10);
+		assert_eq!(OracleIncentives::strikes(4), 0);
+		assert_eq!(balance(4), 150);
+		assert!(misbehaving_feeders().is_empty());
+	});
+}
@@ -0,0 +1,25 @@
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for orml_oracle_incentives.
+pub trait WeightInfo {
+	fn on_initialize(m: u32, ) -> Weight;
+}
+
+/// Default weights.
+impl WeightInfo for () {
+	fn on_initialize(m: u32, ) -> Weight {
+		Weight::from_parts(62_363_721, 0)
+			// Standard Error: 428_201
+			.saturating_add(Weight::from_parts(39_496_412, 0).saturating_mul(m as u64))
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().reads((4 as u64).saturating_mul(m as u64)))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes((4 as u64).saturating_mul(m as u64)))
+	}
+}
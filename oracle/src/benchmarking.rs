@@ -25,20 +25,53 @@ mod benchmarks {
 	use super::*;
 
 	#[benchmark]
-	fn feed_values(x: Linear<0, { T::BenchmarkHelper::get_currency_id_value_pairs().len() as u32 }>) {
+	fn feed_values(
+		x: Linear<0, { T::BenchmarkHelper::get_currency_id_value_pairs().len() as u32 }>,
+	) -> Result<(), BenchmarkError> {
 		// Register the caller
 		let caller: T::AccountId = whitelisted_caller();
 		T::Members::add(&caller);
 
-		let values = T::BenchmarkHelper::get_currency_id_value_pairs()[..x as usize]
+		let values: BoundedVec<_, _> = T::BenchmarkHelper::get_currency_id_value_pairs()[..x as usize]
 			.to_vec()
 			.try_into()
 			.expect("Must succeed since at worst the length remained the same.");
 
+		// Other feeders have fed the keys, so they are combined, and the keys
+		// are restricted to their feeder group.
+		let feeders: Vec<T::AccountId> = (0..2).map(|i| account("feeder", i, 0)).collect();
+		let now = T::Time::now();
+		for feeder in &feeders {
+			T::Members::add(feeder);
+			for (key, value) in &values {
+				RawValues::<T, I>::insert(
+					feeder,
+					key,
+					TimestampedValue {
+						value: value.clone(),
+						timestamp: now,
+					},
+				);
+			}
+		}
+		let group: BoundedVec<_, T::MaxFeedersPerGroup> = feeders
+			.into_iter()
+			.chain([caller.clone()])
+			.collect::<Vec<_>>()
+			.try_into()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		FeederGroups::<T, I>::insert(0, OrderedSet::from(group));
+		for (key, _) in &values {
+			KeyFeederGroup::<T, I>::insert(key, 0);
+		}
+		// events are only deposited after genesis
+		System::<T>::set_block_number(1u32.into());
+
 		#[extrinsic_call]
 		_(RawOrigin::Signed(caller.clone()), values);
 
 		assert!(HasDispatched::<T, I>::get().contains(&caller));
+		Ok(())
 	}
 
 	#[benchmark]
@@ -60,6 +93,55 @@ mod benchmarks {
 		assert!(!HasDispatched::<T, I>::exists());
 	}
 
+	#[benchmark]
+	fn set_feeder_group() -> Result<(), BenchmarkError> {
+		let feeders: BoundedVec<T::AccountId, T::MaxFeedersPerGroup> = (0..T::MaxFeedersPerGroup::get())
+			.map(|i| account("feeder", i, 0))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("Must succeed since the length is MaxFeedersPerGroup.");
+		let origin = T::GovernanceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin, 0, feeders);
+
+		assert_eq!(
+			FeederGroups::<T, I>::get(0).0.len() as u32,
+			T::MaxFeedersPerGroup::get()
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_key_feeder_group() -> Result<(), BenchmarkError> {
+		let key = T::BenchmarkHelper::get_currency_id_value_pairs()
+			.first()
+			.map(|(key, _)| key.clone())
+			.ok_or(BenchmarkError::Weightless)?;
+		let origin = T::GovernanceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin, key.clone(), Some(0));
+
+		assert_eq!(KeyFeederGroup::<T, I>::get(key), Some(0));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn confirm_value() -> Result<(), BenchmarkError> {
+		let (key, value) = T::BenchmarkHelper::get_currency_id_value_pairs()
+			.first()
+			.cloned()
+			.ok_or(BenchmarkError::Weightless)?;
+		let origin = T::GovernanceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin, key.clone(), value.clone());
+
+		assert_eq!(Values::<T, I>::get(key).map(|x| x.value), Some(value));
+		Ok(())
+	}
+
 	impl_benchmark_test_suite! {
 		Pallet,
 		crate::mock::new_test_ext(),
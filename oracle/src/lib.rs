@@ -29,25 +29,33 @@ use frame_support::{
 	dispatch::Pays,
 	ensure,
 	pallet_prelude::*,
-	traits::{ChangeMembers, Get, SortedMembers, Time},
+	traits::{ChangeMembers, Get, OffchainWorker, SortedMembers, Time},
 	weights::Weight,
 	Parameter,
 };
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
-pub use orml_traits::{CombineData, DataFeeder, DataProvider, DataProviderExtended, OnNewData};
+pub use orml_traits::{
+	CombineData, DataFeeder, DataProvider, DataProviderExtended, OnNewData, OnRejectedData, RejectionReason,
+};
 use orml_utilities::OrderedSet;
 use scale_info::TypeInfo;
 use sp_runtime::{traits::Member, DispatchResult, RuntimeDebug};
 use sp_std::{prelude::*, vec};
 
 pub use crate::default_combine_data::DefaultCombineData;
+pub use crate::offchain::{HttpFeeder, HttpSource};
+pub use crate::outlier_combine_data::{Deviation, OutlierFilteredCombineData};
+pub use crate::twap::TwapValue;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
 mod default_combine_data;
 mod mock;
+mod offchain;
+mod outlier_combine_data;
 mod tests;
+mod twap;
 mod weights;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -62,6 +70,9 @@ pub mod module {
 	pub(crate) type MomentOf<T, I = ()> = <<T as Config<I>>::Time as Time>::Moment;
 	pub(crate) type TimestampedValueOf<T, I = ()> = TimestampedValue<<T as Config<I>>::OracleValue, MomentOf<T, I>>;
 
+	/// The id of a group of feeders.
+	pub type FeederGroupId = u32;
+
 	#[derive(
 		Encode,
 		Decode,
@@ -118,6 +129,21 @@ pub mod module {
 		#[pallet::constant]
 		type MaxFeedValues: Get<u32>;
 
+		/// Origin represented Governance, which manages feeder groups.
+		type GovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Maximum number of feeders in a feeder group
+		#[pallet::constant]
+		type MaxFeedersPerGroup: Get<u32>;
+
+		/// Maximum number of combined values kept per key to compute TWAP
+		#[pallet::constant]
+		type MaxTwapHistory: Get<u32>;
+
+		/// Offchain worker feeding values, e.g. `HttpFeeder`. Use `()` to feed
+		/// values with external bots only.
+		type OffchainWorker: OffchainWorker<BlockNumberFor<Self>>;
+
 		#[cfg(feature = "runtime-benchmarks")]
 		type BenchmarkHelper: BenchmarkHelper<Self::OracleKey, Self::OracleValue, Self::MaxFeedValues>;
 	}
@@ -128,6 +154,8 @@ pub mod module {
 		NoPermission,
 		/// Feeder has already fed at this block
 		AlreadyFeeded,
+		/// Feeder is not in the feeder group of a key
+		UnauthorizedKey,
 	}
 
 	#[pallet::event]
@@ -138,6 +166,24 @@ pub mod module {
 			sender: T::AccountId,
 			values: Vec<(T::OracleKey, T::OracleValue)>,
 		},
+		/// The feeders of a group are updated.
+		FeederGroupUpdated {
+			group: FeederGroupId,
+			feeders: Vec<T::AccountId>,
+		},
+		/// The feeder group of a key is updated.
+		KeyFeederGroupUpdated {
+			key: T::OracleKey,
+			group: Option<FeederGroupId>,
+		},
+		/// A fed value is rejected by the combiner.
+		DataRejected {
+			key: T::OracleKey,
+			value: T::OracleValue,
+			reason: RejectionReason,
+		},
+		/// A combined value is confirmed by governance.
+		ValueConfirmed { key: T::OracleKey, value: T::OracleValue },
 	}
 
 	/// Raw values for each oracle operators
@@ -157,6 +203,37 @@ pub mod module {
 	pub(crate) type HasDispatched<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, OrderedSet<T::AccountId, T::MaxHasDispatchedSize>, ValueQuery>;
 
+	/// The feeders of each feeder group
+	#[pallet::storage]
+	#[pallet::getter(fn feeder_groups)]
+	pub type FeederGroups<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, FeederGroupId, OrderedSet<T::AccountId, T::MaxFeedersPerGroup>, ValueQuery>;
+
+	/// The feeder group allowed to feed a key. Any member can feed keys
+	/// without a group.
+	#[pallet::storage]
+	#[pallet::getter(fn key_feeder_group)]
+	pub type KeyFeederGroup<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, <T as Config<I>>::OracleKey, FeederGroupId>;
+
+	/// History of combined values of each key, oldest first. Used to compute
+	/// TWAP.
+	#[pallet::storage]
+	#[pallet::getter(fn twap_history)]
+	pub type TwapHistory<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		<T as Config<I>>::OracleKey,
+		BoundedVec<TimestampedValueOf<T, I>, T::MaxTwapHistory>,
+		ValueQuery,
+	>;
+
+	/// The combined value of each key, if any, at the start of the block it
+	/// last changed in. Used to limit moves per block.
+	#[pallet::storage]
+	pub(crate) type BlockStartValues<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, <T as Config<I>>::OracleKey, (BlockNumberFor<T>, Option<T::OracleValue>)>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
@@ -171,6 +248,10 @@ pub mod module {
 			// cleanup for next block
 			<HasDispatched<T, I>>::kill();
 		}
+
+		fn offchain_worker(now: BlockNumberFor<T>) {
+			T::OffchainWorker::offchain_worker(now);
+		}
 	}
 
 	#[pallet::call]
@@ -178,6 +259,10 @@ pub mod module {
 		/// Feed the external value.
 		///
 		/// Require authorized operator.
+		///
+		/// The weight covers combining each key, recording its combined
+		/// value at the start of the block when it first changes in the
+		/// block, the `OnNewData` hook and depositing the `NewFeedData` event.
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::feed_values(values.len() as u32))]
 		pub fn feed_values(
@@ -189,6 +274,7 @@ pub mod module {
 				.or_else(|_| ensure_root(origin).map(|_| None))?;
 
 			let who = Self::ensure_account(feeder)?;
+			Self::ensure_key_permissions(&who, &values)?;
 
 			// ensure account hasn't dispatched an updated yet
 			ensure!(
@@ -199,13 +285,86 @@ pub mod module {
 			Self::do_feed_values(who, values.into())?;
 			Ok(Pays::No.into())
 		}
+
+		/// Set the feeders of a feeder group.
+		///
+		/// Requires `GovernanceOrigin`
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::set_feeder_group())]
+		pub fn set_feeder_group(
+			origin: OriginFor<T>,
+			group: FeederGroupId,
+			feeders: BoundedVec<T::AccountId, T::MaxFeedersPerGroup>,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			let feeders = OrderedSet::from(feeders);
+			let updated = feeders.0.to_vec();
+			if feeders.0.is_empty() {
+				FeederGroups::<T, I>::remove(group);
+			} else {
+				FeederGroups::<T, I>::insert(group, feeders);
+			}
+
+			Self::deposit_event(Event::FeederGroupUpdated {
+				group,
+				feeders: updated,
+			});
+			Ok(())
+		}
+
+		/// Set the feeder group allowed to feed `key`, or allow any member
+		/// with `None`.
+		///
+		/// Requires `GovernanceOrigin`
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::set_key_feeder_group())]
+		pub fn set_key_feeder_group(
+			origin: OriginFor<T>,
+			key: T::OracleKey,
+			group: Option<FeederGroupId>,
+		) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			KeyFeederGroup::<T, I>::set(&key, group);
+
+			Self::deposit_event(Event::KeyFeederGroupUpdated { key, group });
+			Ok(())
+		}
+
+		/// Set the combined value of `key`, confirming a move rejected by
+		/// the combiner.
+		///
+		/// Requires `GovernanceOrigin`
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::confirm_value())]
+		pub fn confirm_value(origin: OriginFor<T>, key: T::OracleKey, value: T::OracleValue) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			let now = T::Time::now();
+			Self::record_twap_history(&key, value.clone(), now);
+			Values::<T, I>::insert(
+				&key,
+				TimestampedValue {
+					value: value.clone(),
+					timestamp: now,
+				},
+			);
+			BlockStartValues::<T, I>::insert(&key, (frame_system::Pallet::<T>::block_number(), Some(value.clone())));
+
+			Self::deposit_event(Event::ValueConfirmed { key, value });
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	pub fn read_raw_values(key: &T::OracleKey) -> Vec<TimestampedValueOf<T, I>> {
+		let group = Self::key_feeder_group(key).map(Self::feeder_groups);
 		T::Members::sorted_members()
 			.iter()
+			// values fed before the key was restricted are ignored
+			.filter(|x| group.as_ref().is_none_or(|group| group.contains(*x)))
 			.chain([T::RootOperatorAccountId::get()].iter())
 			.filter_map(|x| Self::raw_values(x, key))
 			.collect()
@@ -236,6 +395,59 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 	}
 
+	/// Ensure `who` is in the feeder group of each key it feeds. The root
+	/// operator can feed any key.
+	fn ensure_key_permissions(who: &T::AccountId, values: &[(T::OracleKey, T::OracleValue)]) -> DispatchResult {
+		if *who == T::RootOperatorAccountId::get() {
+			return Ok(());
+		}
+		for (key, _) in values {
+			if let Some(group) = Self::key_feeder_group(key) {
+				ensure!(Self::feeder_groups(group).contains(who), Error::<T, I>::UnauthorizedKey);
+			}
+		}
+		Ok(())
+	}
+
+	/// Record the combined value of `key` at the start of the block, before
+	/// it first changes to `value` in the block.
+	fn record_block_start_value(key: &T::OracleKey, value: &T::OracleValue) {
+		let current = Self::values(key).map(|x| x.value);
+		if current.as_ref() == Some(value) {
+			return;
+		}
+		let current_block = frame_system::Pallet::<T>::block_number();
+		BlockStartValues::<T, I>::mutate(key, |start| {
+			if !matches!(start, Some((block, _)) if *block == current_block) {
+				*start = Some((current_block, current));
+			}
+		});
+	}
+
+	/// Record a combined value in the TWAP history of `key`. The oldest value
+	/// is dropped once the history is full.
+	fn record_twap_history(key: &T::OracleKey, value: T::OracleValue, now: MomentOf<T, I>) {
+		if T::MaxTwapHistory::get() == 0 {
+			return;
+		}
+		TwapHistory::<T, I>::mutate(key, |history| {
+			match history.last_mut() {
+				// the value still holds, nothing to record
+				Some(last) if last.value == value => return,
+				// combined again at the same time, only the last value counts
+				Some(last) if last.timestamp == now => {
+					last.value = value;
+					return;
+				}
+				_ => {}
+			}
+			if history.is_full() {
+				history.remove(0);
+			}
+			let _ = history.try_push(TimestampedValue { value, timestamp: now });
+		});
+	}
+
 	fn do_feed_values(who: T::AccountId, values: Vec<(T::OracleKey, T::OracleValue)>) -> DispatchResult {
 		let now = T::Time::now();
 		for (key, value) in &values {
@@ -247,6 +459,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 			// Update `Values` storage if `combined` yielded result.
 			if let Some(combined) = Self::combined(key) {
+				Self::record_block_start_value(key, &combined.value);
+				Self::record_twap_history(key, combined.value.clone(), now);
 				<Values<T, I>>::insert(key, combined);
 			}
 
@@ -288,6 +502,9 @@ impl<T: Config<I>, I: 'static> DataProviderExtended<T::OracleKey, TimestampedVal
 
 impl<T: Config<I>, I: 'static> DataFeeder<T::OracleKey, T::OracleValue, T::AccountId> for Pallet<T, I> {
 	fn feed_value(who: Option<T::AccountId>, key: T::OracleKey, value: T::OracleValue) -> DispatchResult {
-		Self::do_feed_values(Self::ensure_account(who)?, vec![(key, value)])
+		let who = Self::ensure_account(who)?;
+		let values = vec![(key, value)];
+		Self::ensure_key_permissions(&who, &values)?;
+		Self::do_feed_values(who, values)
 	}
 }
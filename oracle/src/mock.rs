@@ -3,10 +3,24 @@
 use super::*;
 
 use frame_support::{
-	construct_runtime, derive_impl, parameter_types,
+	construct_runtime, derive_impl,
+	instances::Instance1,
+	parameter_types,
 	traits::{ConstU32, SortedMembers},
 };
-use sp_runtime::{traits::IdentityLookup, BuildStorage};
+use frame_system::{offchain::AppCrypto, EnsureRoot};
+use sp_core::{
+	offchain::{
+		testing::TestOffchainExt, HttpError, HttpRequestId, HttpRequestStatus, OpaqueNetworkState,
+		Timestamp as OffchainTimestamp,
+	},
+	OpaquePeerId,
+};
+use sp_runtime::{
+	testing::{TestSignature, TestXt, UintAuthorityId},
+	traits::IdentityLookup,
+	BuildStorage, FixedU128, Permill,
+};
 
 use std::cell::RefCell;
 
@@ -14,7 +28,7 @@ mod oracle {
 	pub use super::super::*;
 }
 
-pub type AccountId = u128;
+pub type AccountId = u64;
 type Key = u32;
 type Value = u32;
 
@@ -28,6 +42,7 @@ impl frame_system::Config for Test {
 thread_local! {
 	static TIME: RefCell<u32> = RefCell::new(0);
 	static MEMBERS: RefCell<Vec<AccountId>> = RefCell::new(vec![1, 2, 3]);
+	static REJECTED_DATA: RefCell<Vec<(Key, Value, RejectionReason)>> = const { RefCell::new(vec![]) };
 }
 
 pub struct Timestamp;
@@ -59,7 +74,12 @@ impl SortedMembers<AccountId> for Members {
 
 	#[cfg(feature = "runtime-benchmarks")]
 	fn add(who: &AccountId) {
-		MEMBERS.with(|v| v.borrow_mut().push(*who));
+		MEMBERS.with(|v| {
+			let mut members = v.borrow_mut();
+			if let Err(index) = members.binary_search(who) {
+				members.insert(index, *who);
+			}
+		});
 	}
 }
 
@@ -74,16 +94,205 @@ impl Config for Test {
 	type WeightInfo = ();
 	type MaxHasDispatchedSize = ConstU32<100>;
 	type MaxFeedValues = MaxFeedValues;
+	type GovernanceOrigin = EnsureRoot<AccountId>;
+	type MaxFeedersPerGroup = ConstU32<5>;
+	type MaxTwapHistory = ConstU32<10>;
+	type OffchainWorker = OffchainFeeder;
 	#[cfg(feature = "runtime-benchmarks")]
-	type BenchmarkHelper = ();
+	type BenchmarkHelper = MockBenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<Key, Value, MaxFeedValues> for MockBenchmarkHelper {
+	fn get_currency_id_value_pairs() -> BoundedVec<(Key, Value), MaxFeedValues> {
+		BoundedVec::truncate_from((0..MaxFeedValues::get()).map(|key| (key, 1_000)).collect())
+	}
+}
+
+parameter_types! {
+	pub MadDeviation: Deviation = Deviation::Mad {
+		multiplier: FixedU128::from_u32(3),
+		min_tolerance: Permill::from_percent(1),
+	};
+	pub PercentDeviation: Deviation = Deviation::Percent(Permill::from_percent(10));
+	pub const MaxMovePerBlock: Permill = Permill::from_percent(20);
+}
+
+pub type OffchainFeeder = HttpFeeder<Test, TestAuthId, ConstU32<2>>;
+
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
+pub struct TestAuthId;
+impl AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> frame_system::offchain::CreateTransactionBase<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type RuntimeCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
 }
 
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_signed_transaction<C: AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		_public: Self::Public,
+		account: Self::AccountId,
+		_nonce: Self::Nonce,
+	) -> Option<Extrinsic> {
+		Some(Extrinsic::new_signed(call, account, (), ()))
+	}
+}
+
+/// Offchain externalities answering requests to some URIs with another
+/// status than the `200` of `TestOffchainExt`, e.g. a timeout.
+pub struct HttpStatusOffchainExt {
+	pub inner: TestOffchainExt,
+	pub statuses: Vec<(&'static str, HttpRequestStatus)>,
+}
+
+impl sp_core::offchain::Externalities for HttpStatusOffchainExt {
+	fn is_validator(&self) -> bool {
+		self.inner.is_validator()
+	}
+
+	fn network_state(&self) -> Result<OpaqueNetworkState, ()> {
+		self.inner.network_state()
+	}
+
+	fn timestamp(&mut self) -> OffchainTimestamp {
+		self.inner.timestamp()
+	}
+
+	fn sleep_until(&mut self, deadline: OffchainTimestamp) {
+		self.inner.sleep_until(deadline)
+	}
+
+	fn random_seed(&mut self) -> [u8; 32] {
+		self.inner.random_seed()
+	}
+
+	fn http_request_start(&mut self, method: &str, uri: &str, meta: &[u8]) -> Result<HttpRequestId, ()> {
+		self.inner.http_request_start(method, uri, meta)
+	}
+
+	fn http_request_add_header(&mut self, request_id: HttpRequestId, name: &str, value: &str) -> Result<(), ()> {
+		self.inner.http_request_add_header(request_id, name, value)
+	}
+
+	fn http_request_write_body(
+		&mut self,
+		request_id: HttpRequestId,
+		chunk: &[u8],
+		deadline: Option<OffchainTimestamp>,
+	) -> Result<(), HttpError> {
+		self.inner.http_request_write_body(request_id, chunk, deadline)
+	}
+
+	fn http_response_wait(
+		&mut self,
+		ids: &[HttpRequestId],
+		deadline: Option<OffchainTimestamp>,
+	) -> Vec<HttpRequestStatus> {
+		let statuses = self.inner.http_response_wait(ids, deadline);
+		let state = self.inner.0.read();
+		ids.iter()
+			.zip(statuses)
+			.map(|(id, status)| {
+				let uri = state.requests.get(id).map(|request| request.uri.as_str());
+				self.statuses
+					.iter()
+					.find(|(status_uri, _)| Some(*status_uri) == uri)
+					.map_or(status, |(_, status)| *status)
+			})
+			.collect()
+	}
+
+	fn http_response_headers(&mut self, request_id: HttpRequestId) -> Vec<(Vec<u8>, Vec<u8>)> {
+		self.inner.http_response_headers(request_id)
+	}
+
+	fn http_response_read_body(
+		&mut self,
+		request_id: HttpRequestId,
+		buffer: &mut [u8],
+		deadline: Option<OffchainTimestamp>,
+	) -> Result<usize, HttpError> {
+		self.inner.http_response_read_body(request_id, buffer, deadline)
+	}
+
+	fn set_authorized_nodes(&mut self, nodes: Vec<OpaquePeerId>, authorized_only: bool) {
+		self.inner.set_authorized_nodes(nodes, authorized_only)
+	}
+}
+
+pub struct RecordRejectedData;
+impl OnRejectedData<Key, Value> for RecordRejectedData {
+	fn on_rejected_data(key: &Key, value: &Value, reason: RejectionReason) {
+		REJECTED_DATA.with(|v| v.borrow_mut().push((*key, *value, reason)));
+	}
+}
+
+pub fn rejected_data() -> Vec<(Key, Value, RejectionReason)> {
+	REJECTED_DATA.with(|v| v.borrow().clone())
+}
+
+pub type MadCombineData =
+	OutlierFilteredCombineData<Test, ConstU32<3>, ConstU32<600>, MadDeviation, MaxMovePerBlock, RecordRejectedData>;
+pub type PercentCombineData =
+	OutlierFilteredCombineData<Test, ConstU32<3>, ConstU32<600>, PercentDeviation, MaxMovePerBlock, RecordRejectedData>;
+pub type OutlierOracleCombineData = OutlierFilteredCombineData<
+	Test,
+	ConstU32<3>,
+	ConstU32<600>,
+	PercentDeviation,
+	MaxMovePerBlock,
+	RecordRejectedData,
+	Instance1,
+>;
+
 type Block = frame_system::mocking::MockBlock<Test>;
 
+/// An oracle filtering outliers and limiting moves per block, benchmarked for
+/// the default weights.
+impl Config<Instance1> for Test {
+	type OnNewData = OutlierOracleCombineData;
+	type CombineData = OutlierOracleCombineData;
+	type Time = Timestamp;
+	type OracleKey = Key;
+	type OracleValue = Value;
+	type RootOperatorAccountId = RootOperatorAccountId;
+	type Members = Members;
+	type WeightInfo = ();
+	type MaxHasDispatchedSize = ConstU32<100>;
+	type MaxFeedValues = MaxFeedValues;
+	type GovernanceOrigin = EnsureRoot<AccountId>;
+	type MaxFeedersPerGroup = ConstU32<5>;
+	type MaxTwapHistory = ConstU32<10>;
+	type OffchainWorker = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = MockBenchmarkHelper;
+}
+
 construct_runtime!(
 	pub enum Test {
 		System: frame_system,
 		ModuleOracle: oracle,
+		OutlierOracle: oracle::<Instance1>,
 	}
 );
 
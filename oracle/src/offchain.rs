@@ -0,0 +1,249 @@
+use crate::{Call, Config, Pallet, TwapValue};
+use frame_support::traits::{Get, OffchainWorker, PalletInfoAccess};
+use frame_system::{
+	offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer},
+	pallet_prelude::BlockNumberFor,
+};
+use orml_traits::data_provider::median;
+use orml_utilities::{
+	offchain_worker::{once_per_block, with_offchain_lock},
+	OffchainErr,
+};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	offchain::{http, storage::StorageValueRef, Duration},
+	BoundedVec, RuntimeDebug,
+};
+use sp_std::{marker, prelude::*};
+
+/// How long to wait for the responses of the HTTP sources, in milliseconds.
+const HTTP_TIMEOUT_MS: u64 = 3_000;
+/// How long the offchain lock is held at most, in milliseconds.
+const LOCK_TIMEOUT_EXPIRATION_MS: u64 = 20_000;
+
+/// An HTTP source of a value.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct HttpSource {
+	/// The URL to fetch the value from.
+	pub url: Vec<u8>,
+	/// The JSON field holding the value, e.g. `price`. The first occurrence of
+	/// the field in the response is used, whatever the object it's in. If
+	/// `None`, the whole response is the value.
+	pub field: Option<Vec<u8>>,
+}
+
+/// An offchain worker fetching values from HTTP sources and feeding their
+/// median with a signed `feed_values` transaction.
+///
+/// The sources of each key are read from the persistent offchain storage of
+/// the node, under `sources_key`, as a SCALE encoded `Vec<(OracleKey,
+/// Vec<HttpSource>)>`. They can be set with `set_sources`, or the
+/// `offchain_localStorageSet` RPC. Nothing is fed until they are set.
+///
+/// Each source responds with the value as a decimal number, e.g. `1.2345`,
+/// either alone or in a JSON field. The number is converted to the inner
+/// representation of the `TwapValue` with `Decimals` decimals, rounding down,
+/// e.g. 18 for `FixedU128`. Only the first `MaxFeedValues` keys are fetched.
+/// All requests are sent at once and share a timeout, well within the
+/// offchain lock expiration. Failing sources are skipped, and so are keys
+/// without any value.
+///
+/// Feeds are signed by any local `AuthorityId` key, which must be of an
+/// oracle member. An offchain lock makes sure values are fed at most once per
+/// block.
+pub struct HttpFeeder<T, AuthorityId, Decimals, I = ()>(marker::PhantomData<(T, AuthorityId, Decimals, I)>);
+
+impl<T, AuthorityId, Decimals, I> OffchainWorker<BlockNumberFor<T>> for HttpFeeder<T, AuthorityId, Decimals, I>
+where
+	T: Config<I> + CreateSignedTransaction<Call<T, I>>,
+	I: 'static,
+	T::OracleValue: TwapValue,
+	AuthorityId: AppCrypto<T::Public, T::Signature>,
+	Decimals: Get<u32>,
+{
+	fn offchain_worker(now: BlockNumberFor<T>) {
+		if let Err(e) = Self::fetch_and_feed(now) {
+			log::info!(
+				target: "oracle offchain worker",
+				"fetch and feed values at block {now:?}: error: {e:?}",
+			);
+		}
+	}
+}
+
+impl<T, AuthorityId, Decimals, I> HttpFeeder<T, AuthorityId, Decimals, I>
+where
+	T: Config<I> + CreateSignedTransaction<Call<T, I>>,
+	I: 'static,
+	T::OracleValue: TwapValue,
+	AuthorityId: AppCrypto<T::Public, T::Signature>,
+	Decimals: Get<u32>,
+{
+	fn storage_key(suffix: &[u8]) -> Vec<u8> {
+		[Pallet::<T, I>::name().as_bytes(), suffix].concat()
+	}
+
+	/// The persistent offchain storage key of the sources.
+	pub fn sources_key() -> Vec<u8> {
+		Self::storage_key(b"/offchain-worker-sources")
+	}
+
+	/// Sets the sources of each key in the persistent offchain storage.
+	pub fn set_sources(sources: &[(T::OracleKey, Vec<HttpSource>)]) {
+		StorageValueRef::persistent(&Self::sources_key()).set(&sources);
+	}
+
+	fn sources() -> Vec<(T::OracleKey, Vec<HttpSource>)> {
+		StorageValueRef::persistent(&Self::sources_key())
+			.get()
+			.ok()
+			.flatten()
+			.unwrap_or_default()
+	}
+
+	fn fetch_and_feed(now: BlockNumberFor<T>) -> Result<(), OffchainErr> {
+		let lock_key = Self::storage_key(b"/offchain-worker-lock");
+		let last_fed_key = Self::storage_key(b"/offchain-worker-last-fed");
+		with_offchain_lock(&lock_key, Duration::from_millis(LOCK_TIMEOUT_EXPIRATION_MS), || {
+			once_per_block(&last_fed_key, now, Self::feed_fetched_values).map(|_| ())
+		})
+	}
+
+	fn feed_fetched_values() -> Result<(), OffchainErr> {
+		let values = Self::fetch_values();
+		if values.is_empty() {
+			return Ok(());
+		}
+
+		let values: BoundedVec<_, T::MaxFeedValues> = BoundedVec::truncate_from(values);
+		let (_, result) = Signer::<T, AuthorityId>::any_account()
+			.send_signed_transaction(|_| Call::feed_values { values: values.clone() })
+			.ok_or(OffchainErr::SubmitTransaction)?;
+		result.map_err(|_| OffchainErr::SubmitTransaction)
+	}
+
+	/// The median of the values fetched for each key.
+	fn fetch_values() -> Vec<(T::OracleKey, T::OracleValue)> {
+		let sources: Vec<_> = Self::sources()
+			.into_iter()
+			.take(T::MaxFeedValues::get() as usize)
+			.collect();
+		let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(HTTP_TIMEOUT_MS));
+
+		let mut requests = vec![];
+		let mut pending = vec![];
+		for (index, (_, key_sources)) in sources.iter().enumerate() {
+			for source in key_sources {
+				let Ok(url) = sp_std::str::from_utf8(&source.url) else {
+					Self::log_fetch_error(&source.url, OffchainErr::Http);
+					continue;
+				};
+				match http::Request::get(url).deadline(deadline).send() {
+					Ok(request) => {
+						requests.push((index, source));
+						pending.push(request);
+					}
+					Err(e) => Self::log_fetch_error(&source.url, e),
+				}
+			}
+		}
+
+		let mut fetched: Vec<Vec<T::OracleValue>> = sources.iter().map(|_| vec![]).collect();
+		for ((index, source), response) in requests
+			.into_iter()
+			.zip(http::PendingRequest::try_wait_all(pending, deadline))
+		{
+			match Self::parse_response(source, response) {
+				Ok(value) => fetched[index].push(value),
+				Err(e) => Self::log_fetch_error(&source.url, e),
+			}
+		}
+
+		sources
+			.into_iter()
+			.zip(fetched)
+			.filter_map(|((key, _), values)| median(values).map(|value| (key, value)))
+			.collect()
+	}
+
+	fn parse_response(
+		source: &HttpSource,
+		response: Result<Result<http::Response, http::Error>, http::PendingRequest>,
+	) -> Result<T::OracleValue, OffchainErr> {
+		let response = response
+			.map_err(|_| OffchainErr::Http)?
+			.map_err(|_| OffchainErr::Http)?;
+		if response.code != 200 {
+			return Err(OffchainErr::Http);
+		}
+
+		let body = response.body().collect::<Vec<u8>>();
+		let body = sp_std::str::from_utf8(&body).map_err(|_| OffchainErr::Http)?;
+		let number = match &source.field {
+			Some(field) => {
+				let field = sp_std::str::from_utf8(field).map_err(|_| OffchainErr::Http)?;
+				json_field(body, field).ok_or(OffchainErr::Http)?
+			}
+			None => body.trim(),
+		};
+		parse_decimal(number, Decimals::get())
+			.and_then(T::OracleValue::from_accumulator)
+			.ok_or(OffchainErr::Http)
+	}
+
+	fn log_fetch_error(url: &[u8], e: impl sp_std::fmt::Debug) {
+		log::info!(
+			target: "oracle offchain worker",
+			"fetch value from {}: error: {e:?}",
+			sp_std::str::from_utf8(url).unwrap_or("<invalid url>"),
+		);
+	}
+}
+
+/// The number in the first `field` of a JSON object, either as a number or a
+/// string.
+pub(crate) fn json_field<'a>(json: &'a str, field: &str) -> Option<&'a str> {
+	json.match_indices(field).find_map(|(index, _)| {
+		// the field name must be quoted, and followed by a colon
+		json[..index].strip_suffix('"')?;
+		let value = json[index + field.len()..]
+			.strip_prefix('"')?
+			.trim_start()
+			.strip_prefix(':')?
+			.trim_start();
+		let (value, quoted) = match value.strip_prefix('"') {
+			Some(value) => (value, true),
+			None => (value, false),
+		};
+		let len = value
+			.find(|c: char| !c.is_ascii_digit() && c != '.')
+			.unwrap_or(value.len());
+		if len == 0 || (quoted && !value[len..].starts_with('"')) {
+			return None;
+		}
+		Some(&value[..len])
+	})
+}
+
+/// Converts a decimal number, e.g. `1.2345`, to an integer with `decimals`
+/// decimals, rounding down.
+pub(crate) fn parse_decimal(number: &str, decimals: u32) -> Option<u128> {
+	let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+	let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+	if integer.is_empty() || !is_digits(integer) || !is_digits(fraction) {
+		return None;
+	}
+
+	let fraction = &fraction[..fraction.len().min(decimals as usize)];
+	let fraction_unit = 10u128.checked_pow(decimals - fraction.len() as u32)?;
+	let fraction = match fraction {
+		"" => 0,
+		fraction => fraction.parse::<u128>().ok()?,
+	};
+	integer
+		.parse::<u128>()
+		.ok()?
+		.checked_mul(10u128.checked_pow(decimals)?)?
+		.checked_add(fraction.checked_mul(fraction_unit)?)
+}
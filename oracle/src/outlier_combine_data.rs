@@ -0,0 +1,191 @@
+use crate::{BlockStartValues, Config, Event, MomentOf, Pallet, TimestampedValueOf, TwapValue};
+use frame_support::traits::{Get, Time};
+use orml_traits::{data_provider::median, CombineData, OnNewData, OnRejectedData, RejectionReason};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	FixedPointNumber, FixedU128, Permill, RuntimeDebug,
+};
+use sp_std::{marker, prelude::*};
+
+/// The maximum deviation of a value from the median.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum Deviation {
+	/// `multiplier` times the median absolute deviation of the values, and at
+	/// least `min_tolerance` of the median. The minimum keeps values close to
+	/// the median when most values are equal, and their MAD is zero.
+	Mad {
+		multiplier: FixedU128,
+		min_tolerance: Permill,
+	},
+	/// A percentage of the median.
+	Percent(Permill),
+}
+
+/// Like `DefaultCombineData`, but drops values deviating from the median by
+/// more than `MaxDeviation` before taking the median.
+///
+/// Returns prev_value if not enough valid values, or if the combined value
+/// would move by more than `MaxMovePerBlock` from the combined value at the
+/// start of the block. Such moves can be confirmed by governance with
+/// `confirm_value`. Moves aren't limited if there is no combined value at the
+/// start of the block, or it's zero.
+///
+/// Combining has no side effects. To report rejected values to `OnRejected`
+/// and in `DataRejected` events, also set it as (part of) the `OnNewData` hook
+/// of the oracle, which checks each fed value.
+pub struct OutlierFilteredCombineData<T, MinimumCount, ExpiresIn, MaxDeviation, MaxMovePerBlock, OnRejected, I = ()>(
+	marker::PhantomData<(T, I, MinimumCount, ExpiresIn, MaxDeviation, MaxMovePerBlock, OnRejected)>,
+);
+
+impl<T, I, MinimumCount, ExpiresIn, MaxDeviation, MaxMovePerBlock, OnRejected>
+	OutlierFilteredCombineData<T, MinimumCount, ExpiresIn, MaxDeviation, MaxMovePerBlock, OnRejected, I>
+where
+	T: Config<I>,
+	I: 'static,
+	T::OracleValue: TwapValue,
+	MinimumCount: Get<u32>,
+	ExpiresIn: Get<MomentOf<T, I>>,
+	MaxDeviation: Get<Deviation>,
+	MaxMovePerBlock: Get<Permill>,
+	OnRejected: OnRejectedData<T::OracleKey, T::OracleValue>,
+{
+	fn reject(key: &T::OracleKey, value: &T::OracleValue, reason: RejectionReason) {
+		OnRejected::on_rejected_data(key, value, reason);
+		Pallet::<T, I>::deposit_event(Event::DataRejected {
+			key: key.clone(),
+			value: value.clone(),
+			reason,
+		});
+	}
+
+	/// Drops expired values, and returns a filter telling whether a value
+	/// deviates from the median of the others by no more than
+	/// `MaxDeviation`. Returns `None` if there are no values left.
+	fn deviation_filter(values: &mut Vec<TimestampedValueOf<T, I>>) -> Option<impl Fn(&T::OracleValue) -> bool> {
+		let expires_in = ExpiresIn::get();
+		let now = T::Time::now();
+		values.retain(|x| x.timestamp.saturating_add(expires_in) > now);
+
+		let median_value = median(values.iter().map(|x| x.value.clone().into_accumulator()).collect())?;
+		let max_deviation = match MaxDeviation::get() {
+			Deviation::Mad {
+				multiplier,
+				min_tolerance,
+			} => {
+				let deviations = values
+					.iter()
+					.map(|x| x.value.clone().into_accumulator().abs_diff(median_value))
+					.collect();
+				// Won't be `None` as `values` ensured not empty.
+				let mad = median(deviations).unwrap_or_default();
+				multiplier
+					.saturating_mul_int(mad)
+					.max(min_tolerance.mul_floor(median_value))
+			}
+			Deviation::Percent(percent) => percent.mul_floor(median_value),
+		};
+
+		Some(move |value: &T::OracleValue| value.clone().into_accumulator().abs_diff(median_value) <= max_deviation)
+	}
+
+	/// The median of the values left after dropping expired values and
+	/// outliers, if there are enough of them.
+	fn filtered_median(mut values: Vec<TimestampedValueOf<T, I>>) -> Option<TimestampedValueOf<T, I>> {
+		let is_valid = Self::deviation_filter(&mut values)?;
+		values.retain(|x| is_valid(&x.value));
+
+		let count = values.len() as u32;
+		if count < MinimumCount::get() || count == 0 {
+			return None;
+		}
+
+		let mid_index = count / 2;
+		// Won't panic as `values` ensured not empty.
+		let (_, value, _) = values.select_nth_unstable_by(mid_index as usize, |a, b| a.value.cmp(&b.value));
+		Some(value.clone())
+	}
+
+	/// Whether `value` moves by more than `MaxMovePerBlock` from the combined
+	/// value of `key` at the start of the block. That's the value recorded by
+	/// the oracle when the combined value first changed in this block, or
+	/// else the current combined value.
+	fn is_excessive_move(key: &T::OracleKey, value: &T::OracleValue, current: Option<&T::OracleValue>) -> bool {
+		let current_block = frame_system::Pallet::<T>::block_number();
+		let start_value = match BlockStartValues::<T, I>::get(key) {
+			Some((block, start_value)) if block == current_block => start_value,
+			_ => current.cloned(),
+		};
+
+		// moves are only limited from a non-zero value at the start of the block
+		match start_value.map(TwapValue::into_accumulator) {
+			Some(start_value) if !start_value.is_zero() => {
+				let moved = value.clone().into_accumulator().abs_diff(start_value);
+				moved > MaxMovePerBlock::get().mul_floor(start_value)
+			}
+			_ => false,
+		}
+	}
+}
+
+impl<T, I, MinimumCount, ExpiresIn, MaxDeviation, MaxMovePerBlock, OnRejected>
+	CombineData<<T as Config<I>>::OracleKey, TimestampedValueOf<T, I>>
+	for OutlierFilteredCombineData<T, MinimumCount, ExpiresIn, MaxDeviation, MaxMovePerBlock, OnRejected, I>
+where
+	T: Config<I>,
+	I: 'static,
+	T::OracleValue: TwapValue,
+	MinimumCount: Get<u32>,
+	ExpiresIn: Get<MomentOf<T, I>>,
+	MaxDeviation: Get<Deviation>,
+	MaxMovePerBlock: Get<Permill>,
+	OnRejected: OnRejectedData<T::OracleKey, T::OracleValue>,
+{
+	fn combine_data(
+		key: &<T as Config<I>>::OracleKey,
+		values: Vec<TimestampedValueOf<T, I>>,
+		prev_value: Option<TimestampedValueOf<T, I>>,
+	) -> Option<TimestampedValueOf<T, I>> {
+		let Some(value) = Self::filtered_median(values) else {
+			return prev_value;
+		};
+		if Self::is_excessive_move(key, &value.value, prev_value.as_ref().map(|x| &x.value)) {
+			return prev_value;
+		}
+
+		Some(value)
+	}
+}
+
+impl<T, I, MinimumCount, ExpiresIn, MaxDeviation, MaxMovePerBlock, OnRejected>
+	OnNewData<T::AccountId, T::OracleKey, T::OracleValue>
+	for OutlierFilteredCombineData<T, MinimumCount, ExpiresIn, MaxDeviation, MaxMovePerBlock, OnRejected, I>
+where
+	T: Config<I>,
+	I: 'static,
+	T::OracleValue: TwapValue,
+	MinimumCount: Get<u32>,
+	ExpiresIn: Get<MomentOf<T, I>>,
+	MaxDeviation: Get<Deviation>,
+	MaxMovePerBlock: Get<Permill>,
+	OnRejected: OnRejectedData<T::OracleKey, T::OracleValue>,
+{
+	/// Reports `value` if it was dropped as an outlier, or if the combined
+	/// value was held back by the move limit.
+	fn on_new_data(_who: &T::AccountId, key: &T::OracleKey, value: &T::OracleValue) {
+		let mut values = Pallet::<T, I>::read_raw_values(key);
+		let Some(is_valid) = Self::deviation_filter(&mut values) else {
+			return;
+		};
+		if !is_valid(value) {
+			Self::reject(key, value, RejectionReason::Outlier);
+			return;
+		}
+
+		let current = Pallet::<T, I>::values(key).map(|x| x.value);
+		if let Some(combined) = Self::filtered_median(values) {
+			if Self::is_excessive_move(key, &combined.value, current.as_ref()) {
+				Self::reject(key, &combined.value, RejectionReason::ExcessiveMove);
+			}
+		}
+	}
+}
@@ -1,8 +1,14 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, instances::Instance1};
 use mock::*;
+use orml_traits::TwapProvider;
+use sp_core::offchain::{
+	testing::{OffchainState, PendingRequest, TestOffchainExt, TestTransactionPoolExt},
+	HttpRequestStatus, OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+};
+use sp_runtime::{testing::UintAuthorityId, traits::BadOrigin};
 
 #[test]
 fn should_feed_values_from_member() {
@@ -15,7 +21,7 @@ fn should_feed_values_from_member() {
 				RuntimeOrigin::signed(5),
 				vec![(50, 1000), (51, 900), (52, 800)].try_into().unwrap()
 			),
-			Error::<Test, _>::NoPermission,
+			Error::<Test>::NoPermission,
 		);
 
 		assert_eq!(
@@ -115,7 +121,7 @@ fn should_not_feed_values_from_root_directly() {
 				RuntimeOrigin::signed(root_feeder),
 				vec![(50, 1000), (51, 900), (52, 800)].try_into().unwrap()
 			),
-			Error::<Test, _>::NoPermission,
+			Error::<Test>::NoPermission,
 		);
 	});
 }
@@ -203,7 +209,7 @@ fn multiple_calls_should_fail() {
 		// Fails feeding by the extrinsic
 		assert_noop!(
 			ModuleOracle::feed_values(RuntimeOrigin::signed(1), vec![(50, 1300)].try_into().unwrap()),
-			Error::<Test, _>::AlreadyFeeded,
+			Error::<Test>::AlreadyFeeded,
 		);
 
 		// But not if fed thought the trait internally
@@ -294,7 +300,7 @@ fn change_member_should_work() {
 		<ModuleOracle as ChangeMembers<AccountId>>::change_members_sorted(&[4], &[1], &[2, 3, 4]);
 		assert_noop!(
 			ModuleOracle::feed_values(RuntimeOrigin::signed(1), vec![(50, 1000)].try_into().unwrap()),
-			Error::<Test, _>::NoPermission,
+			Error::<Test>::NoPermission,
 		);
 		assert_ok!(ModuleOracle::feed_values(
 			RuntimeOrigin::signed(2),
@@ -354,3 +360,604 @@ fn values_are_updated_on_feed() {
 		);
 	});
 }
+
+#[test]
+fn feeder_groups_should_restrict_keys() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			ModuleOracle::set_feeder_group(RuntimeOrigin::signed(1), 1, vec![1, 2].try_into().unwrap()),
+			BadOrigin,
+		);
+		assert_noop!(
+			ModuleOracle::set_key_feeder_group(RuntimeOrigin::signed(1), 50, Some(1)),
+			BadOrigin,
+		);
+
+		assert_ok!(ModuleOracle::set_feeder_group(
+			RuntimeOrigin::root(),
+			1,
+			vec![2, 1].try_into().unwrap()
+		));
+		System::assert_last_event(RuntimeEvent::ModuleOracle(crate::Event::FeederGroupUpdated {
+			group: 1,
+			feeders: vec![1, 2],
+		}));
+		assert_ok!(ModuleOracle::set_key_feeder_group(RuntimeOrigin::root(), 50, Some(1)));
+		System::assert_last_event(RuntimeEvent::ModuleOracle(crate::Event::KeyFeederGroupUpdated {
+			key: 50,
+			group: Some(1),
+		}));
+
+		// 3 is a member, but not in the group of 50
+		assert_noop!(
+			ModuleOracle::feed_values(
+				RuntimeOrigin::signed(3),
+				vec![(51, 900), (50, 1000)].try_into().unwrap()
+			),
+			Error::<Test>::UnauthorizedKey,
+		);
+		assert_noop!(
+			ModuleOracle::feed_value(Some(3), 50, 1000),
+			Error::<Test>::UnauthorizedKey
+		);
+		assert_ok!(ModuleOracle::feed_values(
+			RuntimeOrigin::signed(3),
+			vec![(51, 900)].try_into().unwrap()
+		));
+
+		// the group still needs to be members
+		set_members(vec![2, 3]);
+		assert_noop!(
+			ModuleOracle::feed_values(RuntimeOrigin::signed(1), vec![(50, 1000)].try_into().unwrap()),
+			Error::<Test>::NoPermission,
+		);
+		set_members(vec![1, 2, 3]);
+
+		assert_ok!(ModuleOracle::feed_values(
+			RuntimeOrigin::signed(1),
+			vec![(50, 1000)].try_into().unwrap()
+		));
+		// root can feed any key
+		assert_ok!(ModuleOracle::feed_values(
+			RuntimeOrigin::root(),
+			vec![(50, 1100)].try_into().unwrap()
+		));
+
+		// any member can feed again once the key is unrestricted
+		assert_ok!(ModuleOracle::set_key_feeder_group(RuntimeOrigin::root(), 50, None));
+		assert_eq!(ModuleOracle::key_feeder_group(50), None);
+		assert_ok!(ModuleOracle::feed_value(Some(3), 50, 1000));
+
+		assert_ok!(ModuleOracle::set_feeder_group(
+			RuntimeOrigin::root(),
+			1,
+			Default::default()
+		));
+		assert!(!FeederGroups::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn should_read_raw_values_from_feeder_group() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ModuleOracle::feed_value(Some(1), 50, 1000));
+		assert_ok!(ModuleOracle::feed_value(Some(3), 50, 1200));
+		assert_eq!(ModuleOracle::read_raw_values(&50).len(), 2);
+
+		// values of feeders outside of the group are ignored
+		assert_ok!(ModuleOracle::set_feeder_group(
+			RuntimeOrigin::root(),
+			1,
+			vec![1, 2].try_into().unwrap()
+		));
+		assert_ok!(ModuleOracle::set_key_feeder_group(RuntimeOrigin::root(), 50, Some(1)));
+		assert_eq!(
+			ModuleOracle::read_raw_values(&50),
+			vec![TimestampedValue {
+				value: 1000,
+				timestamp: 12345,
+			}]
+		);
+	});
+}
+
+#[test]
+fn should_record_twap_history() {
+	new_test_ext().execute_with(|| {
+		let feed_all = |value: u32| {
+			for who in 1..=3 {
+				assert_ok!(ModuleOracle::feed_value(Some(who), 50, value));
+			}
+		};
+
+		feed_all(1000);
+		// the combined value is only recorded once it changes
+		feed_all(1000);
+		assert_eq!(
+			ModuleOracle::twap_history(50).into_inner(),
+			vec![TimestampedValue {
+				value: 1000,
+				timestamp: 12345,
+			}]
+		);
+
+		for i in 1..=12 {
+			Timestamp::set_timestamp(12345 + i * 10);
+			feed_all(1000 + i);
+		}
+
+		// the oldest values are dropped
+		let history = ModuleOracle::twap_history(50);
+		assert_eq!(history.len(), 10);
+		assert_eq!(
+			history.first(),
+			Some(&TimestampedValue {
+				value: 1003,
+				timestamp: 12375,
+			})
+		);
+		assert_eq!(
+			history.last(),
+			Some(&TimestampedValue {
+				value: 1012,
+				timestamp: 12465,
+			})
+		);
+	});
+}
+
+#[test]
+fn twap_should_work() {
+	new_test_ext().execute_with(|| {
+		let feed_all = |value: u32| {
+			for who in 1..=3 {
+				assert_ok!(ModuleOracle::feed_value(Some(who), 50, value));
+			}
+		};
+
+		assert_eq!(ModuleOracle::twap(&50, 100), None);
+
+		feed_all(1000);
+		assert_eq!(ModuleOracle::twap(&50, 100), Some(1000));
+
+		Timestamp::set_timestamp(12445);
+		feed_all(2000);
+		// the new value was only just fed
+		assert_eq!(ModuleOracle::twap(&50, 100), Some(1000));
+		assert_eq!(ModuleOracle::twap(&50, 0), Some(2000));
+
+		Timestamp::set_timestamp(12545);
+		assert_eq!(ModuleOracle::twap(&50, 100), Some(2000));
+		assert_eq!(ModuleOracle::twap(&50, 200), Some(1500));
+		assert_eq!(ModuleOracle::twap(&50, 150), Some(1666));
+		// the window is capped to the recorded history
+		assert_eq!(ModuleOracle::twap(&50, 1000), Some(1500));
+
+		assert_eq!(ModuleOracle::twap(&51, 100), None);
+	});
+}
+
+fn timestamped(values: Vec<(u32, u32)>) -> Vec<TimestampedValue<u32, u32>> {
+	values
+		.into_iter()
+		.map(|(value, timestamp)| TimestampedValue { value, timestamp })
+		.collect()
+}
+
+#[test]
+fn should_reject_values_out_of_percentage_band() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let values = timestamped(vec![
+			(1000, 12345),
+			(1050, 12345),
+			(950, 12345),
+			(2000, 12345),
+			(100, 12000),
+		]);
+		assert_eq!(
+			PercentCombineData::combine_data(&50, values, None),
+			Some(TimestampedValue {
+				value: 1000,
+				timestamp: 12345,
+			})
+		);
+		// combining has no side effects
+		assert_eq!(rejected_data(), vec![]);
+		assert!(System::events().is_empty());
+
+		// not enough values left
+		let prev_value = Some(TimestampedValue {
+			value: 1000,
+			timestamp: 12000,
+		});
+		let values = timestamped(vec![(1000, 12345), (1200, 12345), (1400, 12345)]);
+		assert_eq!(PercentCombineData::combine_data(&50, values, prev_value), prev_value);
+	});
+}
+
+#[test]
+fn should_reject_values_out_of_mad_band() {
+	new_test_ext().execute_with(|| {
+		let values = timestamped(vec![
+			(1000, 12345),
+			(1010, 12345),
+			(990, 12345),
+			(1005, 12345),
+			(1500, 12345),
+		]);
+		assert_eq!(
+			MadCombineData::combine_data(&50, values, None),
+			Some(TimestampedValue {
+				value: 1005,
+				timestamp: 12345,
+			})
+		);
+	});
+}
+
+#[test]
+fn should_keep_values_within_min_tolerance_when_mad_is_zero() {
+	new_test_ext().execute_with(|| {
+		// most values are equal, so their median absolute deviation is zero
+		let values = timestamped(vec![(1000, 12345), (1000, 12345), (1005, 12345)]);
+		assert_eq!(
+			MadCombineData::combine_data(&50, values, None).map(|x| x.value),
+			Some(1000)
+		);
+
+		// values beyond the minimum tolerance are still dropped, leaving too few
+		let values = timestamped(vec![(1000, 12345), (1000, 12345), (1050, 12345)]);
+		assert_eq!(MadCombineData::combine_data(&50, values, None), None);
+	});
+}
+
+#[test]
+fn should_limit_moves_per_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let prev_value = |value| {
+			Some(TimestampedValue {
+				value,
+				timestamp: 12000,
+			})
+		};
+		let values = |value| timestamped(vec![(value, 12345); 3]);
+
+		assert_eq!(
+			PercentCombineData::combine_data(&50, values(1300), prev_value(1000)),
+			prev_value(1000)
+		);
+		assert_eq!(
+			PercentCombineData::combine_data(&50, values(1150), prev_value(1000)).map(|x| x.value),
+			Some(1150)
+		);
+
+		// moves add up within a block
+		BlockStartValues::<Test>::insert(50, (1, Some(1000)));
+		assert_eq!(
+			PercentCombineData::combine_data(&50, values(1250), prev_value(1150)),
+			prev_value(1150)
+		);
+
+		System::set_block_number(2);
+		assert_eq!(
+			PercentCombineData::combine_data(&50, values(1250), prev_value(1150)).map(|x| x.value),
+			Some(1250)
+		);
+
+		// governance confirms a large move
+		assert_noop!(
+			ModuleOracle::confirm_value(RuntimeOrigin::signed(1), 50, 2000),
+			BadOrigin
+		);
+		assert_ok!(ModuleOracle::confirm_value(RuntimeOrigin::root(), 50, 2000));
+		System::assert_last_event(RuntimeEvent::ModuleOracle(crate::Event::ValueConfirmed {
+			key: 50,
+			value: 2000,
+		}));
+		assert_eq!(
+			ModuleOracle::get(&50),
+			Some(TimestampedValue {
+				value: 2000,
+				timestamp: 12345,
+			})
+		);
+		assert_eq!(
+			PercentCombineData::combine_data(&50, values(2100), prev_value(2000)).map(|x| x.value),
+			Some(2100)
+		);
+	});
+}
+
+#[test]
+fn should_not_limit_moves_without_start_value() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let prev_value = |value| {
+			Some(TimestampedValue {
+				value,
+				timestamp: 12000,
+			})
+		};
+		let values = |value| timestamped(vec![(value, 12345); 3]);
+
+		// no combined value at the start of the block
+		BlockStartValues::<Test>::insert(50, (1, None::<u32>));
+		assert_eq!(
+			PercentCombineData::combine_data(&50, values(5000), prev_value(1000)).map(|x| x.value),
+			Some(5000)
+		);
+
+		// zero combined value at the start of the block
+		assert_eq!(
+			PercentCombineData::combine_data(&51, values(1000), prev_value(0)).map(|x| x.value),
+			Some(1000)
+		);
+
+		// limited from the next block
+		System::set_block_number(2);
+		assert_eq!(
+			PercentCombineData::combine_data(&50, values(7000), prev_value(5000)),
+			prev_value(5000)
+		);
+	});
+}
+
+fn feed_outlier_oracle(who: AccountId, value: u32) {
+	let origin = if who == RootOperatorAccountId::get() {
+		RuntimeOrigin::root()
+	} else {
+		RuntimeOrigin::signed(who)
+	};
+	assert_ok!(OutlierOracle::feed_values(
+		origin,
+		vec![(50, value)].try_into().unwrap()
+	));
+}
+
+#[test]
+fn should_report_outliers_when_fed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		feed_outlier_oracle(1, 1000);
+		feed_outlier_oracle(2, 1050);
+		feed_outlier_oracle(3, 950);
+		assert_eq!(rejected_data(), vec![]);
+
+		feed_outlier_oracle(4, 2000);
+		assert_eq!(rejected_data(), vec![(50, 2000, RejectionReason::Outlier)]);
+		System::assert_has_event(RuntimeEvent::OutlierOracle(crate::Event::DataRejected {
+			key: 50,
+			value: 2000,
+			reason: RejectionReason::Outlier,
+		}));
+		assert_eq!(OutlierOracle::get(&50).map(|x| x.value), Some(1000));
+	});
+}
+
+#[test]
+fn should_record_block_start_values_and_report_excessive_moves_when_fed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for who in 1..=3 {
+			feed_outlier_oracle(who, 1000);
+		}
+		assert_eq!(BlockStartValues::<Test, Instance1>::get(50), Some((1, None)));
+		assert_eq!(OutlierOracle::get(&50).map(|x| x.value), Some(1000));
+
+		// the values of the previous block expire
+		OutlierOracle::on_finalize(1);
+		System::set_block_number(2);
+		Timestamp::set_timestamp(12945);
+		for who in 1..=3 {
+			feed_outlier_oracle(who, 1300);
+		}
+		assert_eq!(rejected_data(), vec![(50, 1300, RejectionReason::ExcessiveMove)]);
+		System::assert_has_event(RuntimeEvent::OutlierOracle(crate::Event::DataRejected {
+			key: 50,
+			value: 1300,
+			reason: RejectionReason::ExcessiveMove,
+		}));
+		assert_eq!(OutlierOracle::get(&50).map(|x| x.value), Some(1000));
+		// the combined value didn't change, so the start value isn't recorded
+		assert_eq!(BlockStartValues::<Test, Instance1>::get(50), Some((1, None)));
+
+		OutlierOracle::on_finalize(2);
+		System::set_block_number(3);
+		Timestamp::set_timestamp(13545);
+		for who in 1..=3 {
+			feed_outlier_oracle(who, 1150);
+		}
+		assert_eq!(OutlierOracle::get(&50).map(|x| x.value), Some(1150));
+		assert_eq!(BlockStartValues::<Test, Instance1>::get(50), Some((3, Some(1000))));
+	});
+}
+
+fn expect_get(state: &mut OffchainState, uri: &str, response: &[u8]) {
+	state.expect_request(PendingRequest {
+		method: "GET".into(),
+		uri: uri.into(),
+		response: Some(response.to_vec()),
+		sent: true,
+		..Default::default()
+	});
+}
+
+fn http_source(url: &str, field: Option<&str>) -> HttpSource {
+	HttpSource {
+		url: url.into(),
+		field: field.map(Into::into),
+	}
+}
+
+fn set_offchain_sources() {
+	OffchainFeeder::set_sources(&[
+		(
+			50,
+			vec![
+				http_source("https://a.test/50", None),
+				http_source("https://b.test/50", None),
+				http_source("https://c.test/50", Some("price")),
+			],
+		),
+		(51, vec![http_source("https://a.test/51", Some("price"))]),
+	]);
+}
+
+#[test]
+fn offchain_worker_should_feed_fetched_values() {
+	let (offchain, state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	ext.register_extension(OffchainDbExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	{
+		let mut state = state.write();
+		expect_get(&mut state, "https://a.test/50", b"10");
+		// decimals beyond the value's are rounded down
+		expect_get(&mut state, "https://b.test/50", b"12.005\n");
+		// failing sources are skipped
+		expect_get(&mut state, "https://c.test/50", br#"{"price": "oops"}"#);
+		expect_get(
+			&mut state,
+			"https://a.test/51",
+			br#"{"last_price": "1.5", "price": "3.00"}"#,
+		);
+	}
+
+	ext.execute_with(|| {
+		set_offchain_sources();
+		UintAuthorityId::set_all_keys(vec![1]);
+		<ModuleOracle as Hooks<_>>::offchain_worker(1);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(
+			tx.function,
+			RuntimeCall::ModuleOracle(crate::Call::feed_values {
+				values: vec![(50, 1200), (51, 300)].try_into().unwrap()
+			})
+		);
+
+		// values are fed at most once per block
+		<ModuleOracle as Hooks<_>>::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn offchain_worker_should_not_feed_without_values() {
+	let (offchain, state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	ext.register_extension(OffchainDbExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	{
+		let mut state = state.write();
+		for uri in [
+			"https://a.test/50",
+			"https://b.test/50",
+			"https://c.test/50",
+			"https://a.test/51",
+		] {
+			expect_get(&mut state, uri, b"");
+		}
+	}
+
+	ext.execute_with(|| {
+		set_offchain_sources();
+		UintAuthorityId::set_all_keys(vec![1]);
+		<ModuleOracle as Hooks<_>>::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn offchain_worker_should_skip_failing_sources() {
+	let (offchain, state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainWorkerExt::new(HttpStatusOffchainExt {
+		inner: offchain.clone(),
+		statuses: vec![
+			("https://b.test/50", HttpRequestStatus::Finished(500)),
+			("https://c.test/50", HttpRequestStatus::DeadlineReached),
+			("https://a.test/51", HttpRequestStatus::DeadlineReached),
+		],
+	}));
+	ext.register_extension(OffchainDbExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	{
+		let mut state = state.write();
+		expect_get(&mut state, "https://a.test/50", b"10");
+		expect_get(&mut state, "https://b.test/50", b"12");
+		expect_get(&mut state, "https://c.test/50", br#"{"price": 13}"#);
+		expect_get(&mut state, "https://a.test/51", br#"{"price": 3}"#);
+	}
+
+	ext.execute_with(|| {
+		set_offchain_sources();
+		UintAuthorityId::set_all_keys(vec![1]);
+		<ModuleOracle as Hooks<_>>::offchain_worker(1);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(
+			tx.function,
+			RuntimeCall::ModuleOracle(crate::Call::feed_values {
+				values: vec![(50, 1000)].try_into().unwrap()
+			})
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_should_not_feed_without_sources() {
+	let (offchain, state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	ext.register_extension(OffchainDbExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		UintAuthorityId::set_all_keys(vec![1]);
+		<ModuleOracle as Hooks<_>>::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+		assert!(state.read().requests.is_empty());
+	});
+}
+
+#[test]
+fn should_parse_decimal_values() {
+	use crate::offchain::{json_field, parse_decimal};
+
+	assert_eq!(parse_decimal("12", 2), Some(1200));
+	assert_eq!(parse_decimal("12.3", 2), Some(1230));
+	assert_eq!(parse_decimal("12.345", 2), Some(1234));
+	assert_eq!(parse_decimal("0.000000000000000001", 18), Some(1));
+	assert_eq!(parse_decimal("12.", 2), Some(1200));
+	assert_eq!(parse_decimal(".5", 2), None);
+	assert_eq!(parse_decimal("-1", 2), None);
+	assert_eq!(parse_decimal("+1", 2), None);
+	assert_eq!(parse_decimal("1e3", 2), None);
+	assert_eq!(parse_decimal("340282366920938463463374607431768211455", 2), None);
+
+	let json = r#"{"data": {"last_price": "1.5", "price" : 2.25, "symbol": "price"}}"#;
+	assert_eq!(json_field(json, "price"), Some("2.25"));
+	assert_eq!(json_field(json, "last_price"), Some("1.5"));
+	assert_eq!(json_field(json, "data"), None);
+	assert_eq!(json_field(json, "volume"), None);
+	assert_eq!(json_field(r#"{"price": "1.5}"#, "price"), None);
+}
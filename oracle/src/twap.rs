@@ -0,0 +1,87 @@
+use crate::{Config, MomentOf, Pallet, TwapHistory};
+use frame_support::traits::Time;
+use orml_traits::TwapProvider;
+use sp_runtime::{traits::Saturating, FixedU128, FixedU64, SaturatedConversion};
+
+/// Values which can be averaged over time, through their inner `u128`
+/// representation.
+pub trait TwapValue: Sized {
+	/// The inner representation of the value.
+	fn into_accumulator(self) -> u128;
+	/// The value of an inner representation, if it's in range.
+	fn from_accumulator(inner: u128) -> Option<Self>;
+}
+
+macro_rules! impl_twap_value_for_unsigned {
+	($($t:ty),*) => {
+		$(
+			impl TwapValue for $t {
+				fn into_accumulator(self) -> u128 {
+					self.into()
+				}
+
+				fn from_accumulator(inner: u128) -> Option<Self> {
+					inner.try_into().ok()
+				}
+			}
+		)*
+	};
+}
+
+impl_twap_value_for_unsigned!(u8, u16, u32, u64, u128);
+
+impl TwapValue for FixedU128 {
+	fn into_accumulator(self) -> u128 {
+		self.into_inner()
+	}
+
+	fn from_accumulator(inner: u128) -> Option<Self> {
+		Some(Self::from_inner(inner))
+	}
+}
+
+impl TwapValue for FixedU64 {
+	fn into_accumulator(self) -> u128 {
+		self.into_inner().into()
+	}
+
+	fn from_accumulator(inner: u128) -> Option<Self> {
+		inner.try_into().ok().map(Self::from_inner)
+	}
+}
+
+/// Each value in the history holds from its timestamp until the next one,
+/// and the last one until now. Values are weighted by how long they held
+/// within the window. If the history doesn't cover the whole window, the
+/// average is over the part it covers. Returns `None` without any history,
+/// or on overflow.
+impl<T: Config<I>, I: 'static> TwapProvider<T::OracleKey, T::OracleValue, MomentOf<T, I>> for Pallet<T, I>
+where
+	T::OracleValue: TwapValue,
+{
+	fn twap(key: &T::OracleKey, window: MomentOf<T, I>) -> Option<T::OracleValue> {
+		let history = TwapHistory::<T, I>::get(key);
+		let now = T::Time::now();
+		let start = now.saturating_sub(window);
+
+		let mut accumulated: u128 = 0;
+		let mut total_weight: u128 = 0;
+		for (i, entry) in history.iter().enumerate() {
+			let end = history.get(i + 1).map_or(now, |next| next.timestamp);
+			let begin = entry.timestamp.max(start);
+			if end <= begin {
+				continue;
+			}
+
+			let weight: u128 = (end - begin).saturated_into();
+			accumulated = accumulated.checked_add(entry.value.clone().into_accumulator().checked_mul(weight)?)?;
+			total_weight = total_weight.saturating_add(weight);
+		}
+
+		if total_weight == 0 {
+			// an empty window, or the last value was just fed
+			return history.last().map(|entry| entry.value.clone());
+		}
+		T::OracleValue::from_accumulator(accumulated / total_weight)
+	}
+}
@@ -1,25 +1,3 @@
-//! Autogenerated weights for orml_oracle
-//!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 3.0.0
-//! DATE: 2021-05-04, STEPS: [50, ], REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
-
-// Executed Command:
-// /Users/xiliangchen/projects/acala/target/release/acala
-// benchmark
-// --chain=dev
-// --steps=50
-// --repeat=20
-// --pallet=orml_oracle
-// --extrinsic=*
-// --execution=wasm
-// --wasm-execution=compiled
-// --heap-pages=4096
-// --output=./oracle/src/weights.rs
-// --template
-// ../templates/orml-weight-template.hbs
-
-
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -30,22 +8,39 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for orml_oracle.
 pub trait WeightInfo {
-	fn feed_values(c: u32, ) -> Weight;
+	fn feed_values(x: u32, ) -> Weight;
 	fn on_finalize() -> Weight;
+	fn set_feeder_group() -> Weight;
+	fn set_key_feeder_group() -> Weight;
+	fn confirm_value() -> Weight;
 }
 
 /// Default weights.
 impl WeightInfo for () {
-	fn feed_values(c: u32, ) -> Weight {
-		Weight::from_parts(16_800_000, 0)
-			// Standard Error: 84_000
-			.saturating_add(Weight::from_parts(3_600_000, 0).saturating_mul(c as u64))
-			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+	fn feed_values(x: u32, ) -> Weight {
+		Weight::from_parts(4_190_976, 0)
+			// Standard Error: 220_256
+			.saturating_add(Weight::from_parts(30_149_782, 0).saturating_mul(x as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((7 as u64).saturating_mul(x as u64)))
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
-			.saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes((4 as u64).saturating_mul(x as u64)))
 	}
 	fn on_finalize() -> Weight {
-		Weight::from_parts(3_000_000, 0)
+		Weight::from_parts(848_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_feeder_group() -> Weight {
+		Weight::from_parts(9_971_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_key_feeder_group() -> Weight {
+		Weight::from_parts(9_406_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
+	fn confirm_value() -> Weight {
+		Weight::from_parts(14_271_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
 }
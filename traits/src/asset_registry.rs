@@ -1,6 +1,6 @@
 use frame_support::pallet_prelude::*;
 use sp_runtime::DispatchResult;
-use sp_std::fmt::Debug;
+use sp_std::{fmt::Debug, vec::Vec};
 use xcm::v5::prelude::*;
 use xcm::VersionedLocation;
 
@@ -8,10 +8,28 @@ pub trait WeightToFeeConverter {
 	fn convert_weight_to_fee(location: &Location, weight: Weight) -> Option<u128>;
 }
 
+impl WeightToFeeConverter for () {
+	fn convert_weight_to_fee(_location: &Location, _weight: Weight) -> Option<u128> {
+		None
+	}
+}
+
 pub trait FixedConversionRateProvider {
 	fn get_fee_per_second(location: &Location) -> Option<u128>;
 }
 
+/// Fields of the custom metadata of an asset which are read by the
+/// registry-backed XCM adapters.
+pub trait XcmCustomMetadata {
+	/// The minimum XCM execution fee of the chain at the location of the
+	/// asset, i.e. the chain this is the native asset of. `None` by default.
+	fn min_xcm_fee(&self) -> Option<u128> {
+		None
+	}
+}
+
+impl XcmCustomMetadata for () {}
+
 pub trait AssetProcessor<AssetId, Metadata> {
 	fn pre_register(id: Option<AssetId>, asset_metadata: Metadata) -> Result<(AssetId, Metadata), DispatchError>;
 	fn post_register(_id: AssetId, _asset_metadata: Metadata) -> Result<(), DispatchError> {
@@ -64,6 +82,11 @@ pub trait Inspect {
 		location: &Location,
 	) -> Option<AssetMetadata<Self::Balance, Self::CustomMetadata, Self::StringLimit>>;
 	fn location(asset_id: &Self::AssetId) -> Result<Option<Location>, DispatchError>;
+	/// The alias locations of an asset, which `asset_id` also recognizes.
+	/// `location` is the canonical one.
+	fn location_aliases(_asset_id: &Self::AssetId) -> Vec<Location> {
+		Vec::new()
+	}
 }
 
 pub trait Mutate: Inspect {
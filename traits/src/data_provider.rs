@@ -22,6 +22,12 @@ pub trait DataProviderExtended<Key, TimestampedValue> {
 	fn get_all_values() -> Vec<(Key, Option<TimestampedValue>)>;
 }
 
+/// Data provider of time-weighted averages of data.
+pub trait TwapProvider<Key, Value, Moment> {
+	/// Get the time-weighted average value of `key` over the last `window`
+	fn twap(key: &Key, window: Moment) -> Option<Value>;
+}
+
 #[allow(dead_code)] // rust cannot detect usage in macro_rules
 pub fn median<T: Ord + Clone>(mut items: Vec<T>) -> Option<T> {
 	if items.is_empty() {
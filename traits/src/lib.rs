@@ -15,7 +15,7 @@ pub use currency::{
 	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency,
 	NamedBasicReservableCurrency, NamedMultiReservableCurrency,
 };
-pub use data_provider::{DataFeeder, DataProvider, DataProviderExtended};
+pub use data_provider::{DataFeeder, DataProvider, DataProviderExtended, TwapProvider};
 pub use get_by_key::GetByKey;
 pub use multi_asset::ConcreteFungibleAsset;
 pub use nft::InspectExtended;
@@ -25,7 +25,7 @@ pub use rewards::RewardHandler;
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-pub use xcm_transfer::{XcmTransfer, XtokensWeightInfo};
+pub use xcm_transfer::{FeeSwap, XcmTransfer, XtokensWeightInfo};
 
 pub mod arithmetic;
 pub mod asset_registry;
@@ -49,6 +49,22 @@ pub trait OnNewData<AccountId, Key, Value> {
 	fn on_new_data(who: &AccountId, key: &Key, value: &Value);
 }
 
+/// The reason data is rejected when combining
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum RejectionReason {
+	/// The value deviates too much from other values.
+	Outlier,
+	/// The combined value moves too much at once.
+	ExcessiveMove,
+}
+
+/// Rejected data handler
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait OnRejectedData<Key, Value> {
+	/// Data is rejected when combining
+	fn on_rejected_data(key: &Key, value: &Value, reason: RejectionReason);
+}
+
 /// Combine data provided by operators
 pub trait CombineData<Key, TimestampedValue> {
 	/// Combine data provided by operators
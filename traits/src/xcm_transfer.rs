@@ -1,4 +1,4 @@
-use sp_runtime::DispatchError;
+use sp_runtime::{DispatchError, Perbill, RuntimeDebug};
 use sp_std::vec::Vec;
 use xcm::{
 	v5::{prelude::*, Weight},
@@ -11,6 +11,48 @@ pub struct Transferred<AccountId> {
 	pub dest: Location,
 }
 
+/// A single intermediate hop of an explicit multi-hop transfer route.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct RouteHop {
+	/// The intermediate chain, as seen from the sending chain.
+	pub chain: Location,
+	/// The part of the total fee used to buy execution on `chain`. The dest
+	/// chain gets whatever is left after all hops.
+	pub fee_share: Perbill,
+	/// The weight limit of the execution bought on `chain`.
+	pub weight_limit: WeightLimit,
+	/// Forward the assets from `chain` to the next chain by teleport. `chain`
+	/// must be a reserve of the assets, and the next chain must trust it as a
+	/// teleporter.
+	pub teleport: bool,
+}
+
+/// Swaps assets on the spot to pay cross-chain transfer fees.
+pub trait FeeSwap<AccountId, CurrencyId, Balance> {
+	/// Swap `supply_currency_id` of `who` for exactly `target_amount` of
+	/// `target_currency_id`, spending at most `max_supply_amount`. Returns the
+	/// amount of `supply_currency_id` actually spent.
+	fn swap_for_exact_target(
+		who: &AccountId,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		max_supply_amount: Balance,
+		target_amount: Balance,
+	) -> Result<Balance, DispatchError>;
+}
+
+impl<AccountId, CurrencyId, Balance> FeeSwap<AccountId, CurrencyId, Balance> for () {
+	fn swap_for_exact_target(
+		_who: &AccountId,
+		_supply_currency_id: CurrencyId,
+		_target_currency_id: CurrencyId,
+		_max_supply_amount: Balance,
+		_target_amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("FeeSwap not supported"))
+	}
+}
+
 /// Abstraction over cross-chain token transfers.
 pub trait XcmTransfer<AccountId, Balance, CurrencyId> {
 	/// Transfer local assets with given `CurrencyId` and `Amount`.
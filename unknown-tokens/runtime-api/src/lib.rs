@@ -0,0 +1,17 @@
+//! Runtime API definition for orml unknown tokens pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use xcm::{VersionedAssets, VersionedLocation};
+
+sp_api::decl_runtime_apis! {
+	pub trait UnknownTokensApi {
+		/// Unknown assets held by `who`, or `None` if `who` can't be
+		/// interpreted.
+		fn unknown_holdings(who: VersionedLocation) -> Option<VersionedAssets>;
+	}
+}
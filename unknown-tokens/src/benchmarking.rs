@@ -0,0 +1,104 @@
+pub use crate::*;
+
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+
+/// Helper trait for benchmarking.
+pub trait BenchmarkHelper {
+	/// Returns the location of an asset registered with
+	/// `CurrencyIdConvert`, whose reserve can be sent to, and the location
+	/// of a holder which converts to an account with
+	/// `LocationToAccountId`.
+	fn registered_asset_and_holder() -> Option<(Location, Location)>;
+}
+
+impl BenchmarkHelper for () {
+	fn registered_asset_and_holder() -> Option<(Location, Location)> {
+		None
+	}
+}
+
+/// Deposits an unknown balance of the registered asset to the holder,
+/// returning both locations.
+fn deposit_unknown<T: Config>() -> Result<(Location, Location), BenchmarkError> {
+	let (asset_id, holder) = T::BenchmarkHelper::registered_asset_and_holder().ok_or(BenchmarkError::Skip)?;
+	Pallet::<T>::deposit(&(asset_id.clone(), 1_000_000u128).into(), &holder)?;
+	Ok((asset_id, holder))
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn convert() -> Result<(), BenchmarkError> {
+		let (asset_id, holder) = deposit_unknown::<T>()?;
+		let who = T::LocationToAccountId::convert_location(&holder).ok_or(BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(who),
+			Box::new(holder.clone().into()),
+			Box::new(asset_id.clone().into()),
+		);
+
+		assert_eq!(ConcreteFungibleBalances::<T>::get(&holder, &asset_id), 0);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn force_withdraw() -> Result<(), BenchmarkError> {
+		let (asset_id, holder) = deposit_unknown::<T>()?;
+		let origin = T::ForceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let to: T::AccountId = account("to", 0, 0);
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			Box::new(holder.clone().into()),
+			Box::new(asset_id.clone().into()),
+			T::Lookup::unlookup(to),
+		);
+
+		assert_eq!(ConcreteFungibleBalances::<T>::get(&holder, &asset_id), 0);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn force_withdraw_to_reserve() -> Result<(), BenchmarkError> {
+		let (asset_id, holder) = deposit_unknown::<T>()?;
+		let origin = T::ForceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			Box::new(holder.clone().into()),
+			Box::new(asset_id.clone().into()),
+			Box::new(holder.clone().into()),
+		);
+
+		assert_eq!(ConcreteFungibleBalances::<T>::get(&holder, &asset_id), 0);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn sweep_expired() -> Result<(), BenchmarkError> {
+		let expiry = T::Expiry::get().ok_or(BenchmarkError::Skip)?;
+		let (asset_id, holder) = deposit_unknown::<T>()?;
+		let origin = T::ForceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now.saturating_add(expiry));
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			Box::new(holder.clone().into()),
+			Box::new(asset_id.clone().into()),
+		);
+
+		assert_eq!(ConcreteFungibleBalances::<T>::get(&holder, &asset_id), 0);
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder.build(), crate::mock::Runtime);
+}
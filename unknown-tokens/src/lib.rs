@@ -2,22 +2,72 @@
 #![allow(clippy::unused_unit)]
 
 use frame_support::pallet_prelude::*;
-use sp_std::vec::Vec;
-use xcm::v5::prelude::*;
+use frame_system::pallet_prelude::*;
+use sp_runtime::traits::{Convert, Saturating, StaticLookup};
+use sp_std::{boxed::Box, vec, vec::Vec};
+use xcm::{v5::prelude::*, VersionedLocation};
+use xcm_executor::traits::ConvertLocation;
 
+use orml_traits::{location::Reserve, MultiCurrency};
 use orml_xcm_support::UnknownAsset;
 
+#[cfg(feature = "runtime-benchmarks")]
+pub use benchmarking::BenchmarkHelper;
 pub use module::*;
+pub use weights::WeightInfo;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 mod mock;
 mod tests;
+mod weights;
 
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
 
+	pub(crate) type CurrencyIdOf<T> =
+		<<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+
 	#[pallet::config]
-	pub trait Config: frame_system::Config {}
+	pub trait Config: frame_system::Config {
+		/// The currency to convert unknown assets into, once registered.
+		type MultiCurrency: MultiCurrency<Self::AccountId>;
+
+		/// Convert `Location` to `CurrencyId`, returning `None` for assets
+		/// which are still unknown.
+		type CurrencyIdConvert: Convert<Location, Option<CurrencyIdOf<Self>>>;
+
+		/// Convert the `Location` unknown assets were deposited to into
+		/// `T::AccountId`, to check the holder of a balance being converted.
+		/// It should match the conversion of the asset transactor depositing
+		/// known assets.
+		type LocationToAccountId: ConvertLocation<Self::AccountId>;
+
+		/// The origin which may force withdraw unknown balances.
+		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The number of blocks after the latest deposit, after which unknown
+		/// balances can be swept. `None` to disable sweeping.
+		#[pallet::constant]
+		type Expiry: Get<Option<BlockNumberFor<Self>>>;
+
+		/// The reserve provider, to send unknown assets back to their reserve.
+		type ReserveProvider: Reserve;
+
+		/// Self chain location.
+		#[pallet::constant]
+		type UniversalLocation: Get<InteriorLocation>;
+
+		/// The XCM router, to send unknown assets back to their reserve.
+		type XcmRouter: SendXcm;
+
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper;
+	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
@@ -26,6 +76,8 @@ pub mod module {
 		Deposited { asset: Asset, who: Location },
 		/// Withdraw success.
 		Withdrawn { asset: Asset, who: Location },
+		/// Unknown balance converted into the registered currency.
+		Converted { asset: Asset, who: Location },
 	}
 
 	#[pallet::error]
@@ -36,6 +88,26 @@ pub mod module {
 		BalanceOverflow,
 		/// Unhandled asset.
 		UnhandledAsset,
+		/// The version of the `Versioned` value used is not able to be
+		/// interpreted.
+		BadVersion,
+		/// The asset is not registered as a currency yet.
+		AssetNotRegistered,
+		/// No unknown balance of the asset.
+		NothingToConvert,
+		/// Sweeping is disabled as there is no expiry.
+		NoExpiry,
+		/// The unknown balance has not expired.
+		NotExpired,
+		/// The reserve of the asset is unknown.
+		AssetHasNoReserve,
+		/// Could not re-anchor the assets to declare the fees for the
+		/// destination chain.
+		CannotReanchor,
+		/// Could not send the message to the reserve.
+		XcmSendFailed,
+		/// The caller is not the account of the holder location.
+		NotHolder,
 	}
 
 	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
@@ -51,7 +123,7 @@ pub mod module {
 	/// double_map: who, asset_id => u128
 	#[pallet::storage]
 	#[pallet::getter(fn concrete_fungible_balances)]
-	pub(crate) type ConcreteFungibleBalances<T> =
+	pub type ConcreteFungibleBalances<T> =
 		StorageDoubleMap<_, Blake2_128Concat, Location, Blake2_128Concat, Location, u128, ValueQuery>;
 
 	/// Abstract fungible balances under a given location and a abstract
@@ -60,8 +132,194 @@ pub mod module {
 	/// double_map: who, asset_id => u128
 	#[pallet::storage]
 	#[pallet::getter(fn abstract_fungible_balances)]
-	pub(crate) type AbstractFungibleBalances<T> =
+	pub type AbstractFungibleBalances<T> =
 		StorageDoubleMap<_, Blake2_128Concat, Location, Blake2_128Concat, Vec<u8>, u128, ValueQuery>;
+
+	/// The block number of the latest deposit of concrete fungible balances.
+	/// Balances without it were deposited before it's tracked, and are
+	/// considered deposited at block zero.
+	///
+	/// double_map: who, asset_id => BlockNumber
+	#[pallet::storage]
+	pub type LastDeposited<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, Location, Blake2_128Concat, Location, BlockNumberFor<T>, OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Convert the unknown balance of `asset_id` held by `holder` into
+		/// the currency it's registered as, deposited to the caller.
+		///
+		/// `holder` is the location the balance was deposited to, as given
+		/// by `unknown_holdings`, which may differ from other locations of
+		/// the same account, e.g. by its network.
+		///
+		/// The dispatch origin for this call must be `Signed` by the account
+		/// `holder` converts to with `LocationToAccountId`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::convert())]
+		pub fn convert(
+			origin: OriginFor<T>,
+			holder: Box<VersionedLocation>,
+			asset_id: Box<VersionedLocation>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let holder: Location = (*holder).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let asset_id: Location = (*asset_id).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			ensure!(
+				T::LocationToAccountId::convert_location(&holder).as_ref() == Some(&who),
+				Error::<T>::NotHolder
+			);
+			let currency_id = T::CurrencyIdConvert::convert(asset_id.clone()).ok_or(Error::<T>::AssetNotRegistered)?;
+
+			let amount = Self::take_balance(&holder, &asset_id);
+			ensure!(amount != 0, Error::<T>::NothingToConvert);
+			let balance = amount.try_into().map_err(|_| Error::<T>::BalanceOverflow)?;
+			T::MultiCurrency::deposit(currency_id, &who, balance)?;
+
+			Self::deposit_event(Event::Converted {
+				asset: (asset_id, amount).into(),
+				who: holder,
+			});
+			Ok(())
+		}
+
+		/// Withdraw the unknown balance of `asset_id` held by `who`, and
+		/// deposit it to `to` in the currency it's registered as.
+		///
+		/// The dispatch origin for this call must be `ForceOrigin`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::force_withdraw())]
+		pub fn force_withdraw(
+			origin: OriginFor<T>,
+			who: Box<VersionedLocation>,
+			asset_id: Box<VersionedLocation>,
+			to: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let who: Location = (*who).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let asset_id: Location = (*asset_id).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let to = T::Lookup::lookup(to)?;
+			let currency_id = T::CurrencyIdConvert::convert(asset_id.clone()).ok_or(Error::<T>::AssetNotRegistered)?;
+
+			let amount = Self::take_balance(&who, &asset_id);
+			ensure!(amount != 0, Error::<T>::BalanceTooLow);
+			let balance = amount.try_into().map_err(|_| Error::<T>::BalanceOverflow)?;
+			T::MultiCurrency::deposit(currency_id, &to, balance)?;
+
+			Self::deposit_event(Event::Withdrawn {
+				asset: (asset_id, amount).into(),
+				who,
+			});
+			Ok(())
+		}
+
+		/// Withdraw the unknown balance of `asset_id` held by `who`, and send
+		/// it back to its reserve, to be deposited to `beneficiary`.
+		///
+		/// `beneficiary` is relative to this chain. Remote execution is paid
+		/// with the asset itself.
+		///
+		/// The dispatch origin for this call must be `ForceOrigin`.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::force_withdraw_to_reserve())]
+		pub fn force_withdraw_to_reserve(
+			origin: OriginFor<T>,
+			who: Box<VersionedLocation>,
+			asset_id: Box<VersionedLocation>,
+			beneficiary: Box<VersionedLocation>,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let who: Location = (*who).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let asset_id: Location = (*asset_id).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let beneficiary: Location = (*beneficiary).try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			let amount = Self::take_balance(&who, &asset_id);
+			ensure!(amount != 0, Error::<T>::BalanceTooLow);
+			let asset: Asset = (asset_id, amount).into();
+			Self::send_to_reserve(asset.clone(), beneficiary)?;
+
+			Self::deposit_event(Event::Withdrawn { asset, who });
+			Ok(())
+		}
+
+		/// Sweep the expired unknown balance of `asset_id` held by `who`, which
+		/// was not deposited to within `Expiry`.
+		///
+		/// Balances deposited before `LastDeposited` is tracked are considered
+		/// deposited at block zero, so sweeping is left to governance.
+		///
+		/// The dispatch origin for this call must be `ForceOrigin`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::sweep_expired())]
+		pub fn sweep_expired(
+			origin: OriginFor<T>,
+			who: Box<VersionedLocation>,
+			asset_id: Box<VersionedLocation>,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let who: Location = (*who).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let asset_id: Location = (*asset_id).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let expiry = T::Expiry::get().ok_or(Error::<T>::NoExpiry)?;
+
+			let last_deposited = LastDeposited::<T>::get(&who, &asset_id).unwrap_or_default();
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= last_deposited.saturating_add(expiry),
+				Error::<T>::NotExpired
+			);
+
+			let amount = Self::take_balance(&who, &asset_id);
+			ensure!(amount != 0, Error::<T>::BalanceTooLow);
+
+			Self::deposit_event(Event::Withdrawn {
+				asset: (asset_id, amount).into(),
+				who,
+			});
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Remove the unknown balance of `asset_id` held by `who`, returning the
+	/// amount.
+	fn take_balance(who: &Location, asset_id: &Location) -> u128 {
+		LastDeposited::<T>::remove(who, asset_id);
+		ConcreteFungibleBalances::<T>::take(who, asset_id)
+	}
+
+	/// Send `asset` back to its reserve, to be deposited to `beneficiary`.
+	fn send_to_reserve(asset: Asset, beneficiary: Location) -> DispatchResult {
+		let reserve = T::ReserveProvider::reserve(&asset).ok_or(Error::<T>::AssetHasNoReserve)?;
+		let context = T::UniversalLocation::get();
+		let fees = asset
+			.reanchored(&reserve, &context)
+			.map_err(|_| Error::<T>::CannotReanchor)?;
+		let beneficiary = beneficiary
+			.reanchored(&reserve, &context)
+			.map_err(|_| Error::<T>::CannotReanchor)?;
+
+		let message = Xcm(vec![
+			WithdrawAsset(fees.clone().into()),
+			BuyExecution {
+				fees,
+				weight_limit: Unlimited,
+			},
+			DepositAsset {
+				assets: Wild(AllCounted(1)),
+				beneficiary,
+			},
+		]);
+		send_xcm::<T::XcmRouter>(reserve, message).map_err(|_| Error::<T>::XcmSendFailed)?;
+		Ok(())
+	}
+
+	/// Unknown assets held by `who`.
+	pub fn unknown_holdings(who: &Location) -> Vec<Asset> {
+		ConcreteFungibleBalances::<T>::iter_prefix(who)
+			.filter(|(_, amount)| *amount != 0)
+			.map(|(asset_id, amount)| (asset_id, amount).into())
+			.collect()
+	}
 }
 
 impl<T: Config> UnknownAsset for Pallet<T> {
@@ -72,6 +330,7 @@ impl<T: Config> UnknownAsset for Pallet<T> {
 				id: AssetId(location),
 			} => ConcreteFungibleBalances::<T>::try_mutate(to, location, |b| -> DispatchResult {
 				*b = b.checked_add(*amount).ok_or(Error::<T>::BalanceOverflow)?;
+				LastDeposited::<T>::insert(to, location, frame_system::Pallet::<T>::block_number());
 				Ok(())
 			}),
 			_ => Err(Error::<T>::UnhandledAsset.into()),
@@ -90,8 +349,18 @@ impl<T: Config> UnknownAsset for Pallet<T> {
 			Asset {
 				fun: Fungible(amount),
 				id: AssetId(location),
-			} => ConcreteFungibleBalances::<T>::try_mutate(from, location, |b| -> DispatchResult {
-				*b = b.checked_sub(*amount).ok_or(Error::<T>::BalanceTooLow)?;
+			} => ConcreteFungibleBalances::<T>::try_mutate_exists(from, location, |b| -> DispatchResult {
+				let balance = b
+					.unwrap_or_default()
+					.checked_sub(*amount)
+					.ok_or(Error::<T>::BalanceTooLow)?;
+				if balance == 0 {
+					// remove stale entries
+					*b = None;
+					LastDeposited::<T>::remove(from, location);
+				} else {
+					*b = Some(balance);
+				}
 				Ok(())
 			}),
 			_ => Err(Error::<T>::UnhandledAsset.into()),
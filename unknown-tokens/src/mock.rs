@@ -5,10 +5,19 @@
 use super::*;
 use crate as unknown_tokens;
 
-use frame_support::{construct_runtime, derive_impl};
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types,
+	storage::unhashed,
+	traits::{ConstU32, Nothing},
+};
+use frame_system::EnsureRoot;
+use orml_traits::{location::Reserve, parameter_type_with_key};
 use sp_runtime::{traits::IdentityLookup, AccountId32, BuildStorage};
+use std::cell::RefCell;
 
 pub type AccountId = AccountId32;
+pub type CurrencyId = u32;
+pub type Balance = u64;
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
 impl frame_system::Config for Runtime {
@@ -17,13 +26,142 @@ impl frame_system::Config for Runtime {
 	type Block = Block;
 }
 
-impl Config for Runtime {}
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Balance = Balance;
+	type Amount = i64;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type CurrencyHooks = ();
+	type MaxLocks = ConstU32<100>;
+	type MaxReserves = ConstU32<100>;
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = Nothing;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+pub const ALICE: AccountId = AccountId32::new([1u8; 32]);
+pub const BOB: AccountId = AccountId32::new([2u8; 32]);
+pub const REGISTERED_CURRENCY: CurrencyId = 1;
+
+/// The location of the registered currency.
+pub fn registered_location() -> Location {
+	Location::new(1, [Parachain(2), GeneralIndex(1)])
+}
+
+pub struct CurrencyIdConvert;
+impl Convert<Location, Option<CurrencyId>> for CurrencyIdConvert {
+	fn convert(location: Location) -> Option<CurrencyId> {
+		(location == registered_location()).then_some(REGISTERED_CURRENCY)
+	}
+}
+
+/// The location of `account`, with or without a network.
+pub fn account_location(account: AccountId, network: Option<NetworkId>) -> Location {
+	Junction::AccountId32 {
+		network,
+		id: account.into(),
+	}
+	.into()
+}
+
+pub struct LocationToAccountId;
+impl ConvertLocation<AccountId> for LocationToAccountId {
+	fn convert_location(location: &Location) -> Option<AccountId> {
+		match location.unpack() {
+			(0, [Junction::AccountId32 { network: None, id }])
+			| (
+				0,
+				[Junction::AccountId32 {
+					network: Some(NetworkId::Polkadot),
+					id,
+				}],
+			) => Some((*id).into()),
+			_ => None,
+		}
+	}
+}
+
+thread_local! {
+	pub static SENT_XCM: RefCell<Vec<(Location, Xcm<()>)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Messages sent by `TestSendXcm`.
+pub fn sent_xcm() -> Vec<(Location, Xcm<()>)> {
+	SENT_XCM.with(|q| q.borrow().clone())
+}
+
+/// The keys of the queue written by `TestSendXcm`.
+const QUEUE_STATUS: &[u8] = b":orml_unknown_tokens_mock:queue_status";
+const QUEUE_PAGE: &[u8] = b":orml_unknown_tokens_mock:queue_page";
+
+/// Records sent messages. Like the XCMP queue, it also reads the queue
+/// status, writes the message to a page and updates the status, so
+/// benchmarks weigh routers writing to storage.
+pub struct TestSendXcm;
+impl SendXcm for TestSendXcm {
+	type Ticket = (Location, Xcm<()>);
+	fn validate(dest: &mut Option<Location>, msg: &mut Option<Xcm<()>>) -> SendResult<(Location, Xcm<()>)> {
+		let pair = (dest.take().unwrap(), msg.take().unwrap());
+		Ok((pair, Assets::new()))
+	}
+	fn deliver(pair: (Location, Xcm<()>)) -> Result<XcmHash, SendError> {
+		let pages: u32 = unhashed::get_or_default(QUEUE_STATUS);
+		unhashed::put(&(QUEUE_PAGE, pages).encode(), &pair);
+		unhashed::put(QUEUE_STATUS, &pages.saturating_add(1));
+		SENT_XCM.with(|q| q.borrow_mut().push(pair));
+		Ok([0; 32])
+	}
+}
+
+pub struct ReserveProvider;
+impl Reserve for ReserveProvider {
+	fn reserve(asset: &Asset) -> Option<Location> {
+		Some(asset.id.0.chain_location())
+	}
+}
+
+parameter_types! {
+	pub const Expiry: Option<u64> = Some(10);
+	pub UniversalLocation: InteriorLocation = [GlobalConsensus(NetworkId::Polkadot), Parachain(1)].into();
+}
+
+impl Config for Runtime {
+	type MultiCurrency = Tokens;
+	type CurrencyIdConvert = CurrencyIdConvert;
+	type LocationToAccountId = LocationToAccountId;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type Expiry = Expiry;
+	type ReserveProvider = ReserveProvider;
+	type UniversalLocation = UniversalLocation;
+	type XcmRouter = TestSendXcm;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = MockBenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper for MockBenchmarkHelper {
+	fn registered_asset_and_holder() -> Option<(Location, Location)> {
+		Some((registered_location(), account_location(ALICE, None)))
+	}
+}
 
 type Block = frame_system::mocking::MockBlock<Runtime>;
 
 construct_runtime!(
 	pub enum Runtime {
 		System: frame_system,
+		Tokens: orml_tokens,
 		UnknownTokens: unknown_tokens,
 	}
 );
@@ -5,7 +5,8 @@
 use super::*;
 use mock::*;
 
-use frame_support::{assert_err, assert_ok};
+use frame_support::{assert_err, assert_noop, assert_ok};
+use sp_runtime::traits::BadOrigin;
 
 const MOCK_RECIPIENT: Location = Location::parent();
 const MOCK_CONCRETE_FUNGIBLE_ID: Location = Location::parent();
@@ -92,3 +93,301 @@ fn withdraw_unhandled_asset_should_fail() {
 		);
 	});
 }
+
+#[test]
+fn convert_works() {
+	ExtBuilder.build().execute_with(|| {
+		let holder = account_location(ALICE, None);
+		assert_ok!(UnknownTokens::deposit(&(registered_location(), 10u128).into(), &holder));
+
+		assert_ok!(UnknownTokens::convert(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(holder.clone().into()),
+			Box::new(registered_location().into())
+		));
+		assert_eq!(Tokens::free_balance(REGISTERED_CURRENCY, &ALICE), 10);
+		assert_eq!(
+			UnknownTokens::concrete_fungible_balances(&holder, registered_location()),
+			0
+		);
+		System::assert_last_event(RuntimeEvent::UnknownTokens(crate::Event::Converted {
+			asset: (registered_location(), 10u128).into(),
+			who: holder.clone(),
+		}));
+
+		assert_noop!(
+			UnknownTokens::convert(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(holder.into()),
+				Box::new(registered_location().into())
+			),
+			Error::<Runtime>::NothingToConvert
+		);
+	});
+}
+
+#[test]
+fn convert_looks_up_the_deposit_location() {
+	ExtBuilder.build().execute_with(|| {
+		// deposited with a network, which the holder's other location lacks
+		let holder = account_location(ALICE, Some(NetworkId::Polkadot));
+		assert_ok!(UnknownTokens::deposit(&(registered_location(), 10u128).into(), &holder));
+
+		assert_noop!(
+			UnknownTokens::convert(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(account_location(ALICE, None).into()),
+				Box::new(registered_location().into())
+			),
+			Error::<Runtime>::NothingToConvert
+		);
+
+		assert_ok!(UnknownTokens::convert(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(holder.clone().into()),
+			Box::new(registered_location().into())
+		));
+		assert_eq!(Tokens::free_balance(REGISTERED_CURRENCY, &ALICE), 10);
+		assert_eq!(UnknownTokens::unknown_holdings(&holder), vec![]);
+	});
+}
+
+#[test]
+fn convert_by_other_account_should_fail() {
+	ExtBuilder.build().execute_with(|| {
+		let holder = account_location(ALICE, None);
+		assert_ok!(UnknownTokens::deposit(&(registered_location(), 10u128).into(), &holder));
+
+		assert_noop!(
+			UnknownTokens::convert(
+				RuntimeOrigin::signed(BOB),
+				Box::new(holder.clone().into()),
+				Box::new(registered_location().into())
+			),
+			Error::<Runtime>::NotHolder
+		);
+		// a location without an account
+		assert_noop!(
+			UnknownTokens::convert(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(MOCK_RECIPIENT.into()),
+				Box::new(registered_location().into())
+			),
+			Error::<Runtime>::NotHolder
+		);
+	});
+}
+
+#[test]
+fn convert_unregistered_asset_should_fail() {
+	ExtBuilder.build().execute_with(|| {
+		let holder = account_location(ALICE, None);
+		assert_ok!(UnknownTokens::deposit(&concrete_fungible(10), &holder));
+
+		assert_noop!(
+			UnknownTokens::convert(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(holder.into()),
+				Box::new(MOCK_CONCRETE_FUNGIBLE_ID.into())
+			),
+			Error::<Runtime>::AssetNotRegistered
+		);
+	});
+}
+
+#[test]
+fn unknown_holdings_works() {
+	ExtBuilder.build().execute_with(|| {
+		assert_eq!(UnknownTokens::unknown_holdings(&MOCK_RECIPIENT), vec![]);
+
+		assert_ok!(UnknownTokens::deposit(&concrete_fungible(3), &MOCK_RECIPIENT));
+		assert_ok!(UnknownTokens::deposit(
+			&(registered_location(), 5u128).into(),
+			&MOCK_RECIPIENT
+		));
+		assert_ok!(UnknownTokens::withdraw(&concrete_fungible(3), &MOCK_RECIPIENT));
+
+		assert_eq!(
+			UnknownTokens::unknown_holdings(&MOCK_RECIPIENT),
+			vec![(registered_location(), 5u128).into()]
+		);
+	});
+}
+
+#[test]
+fn withdraw_all_removes_entries() {
+	ExtBuilder.build().execute_with(|| {
+		assert_ok!(UnknownTokens::deposit(&concrete_fungible(3), &MOCK_RECIPIENT));
+		assert_eq!(
+			LastDeposited::<Runtime>::get(&MOCK_RECIPIENT, &MOCK_CONCRETE_FUNGIBLE_ID),
+			Some(1)
+		);
+
+		assert_ok!(UnknownTokens::withdraw(&concrete_fungible(3), &MOCK_RECIPIENT));
+		assert!(!ConcreteFungibleBalances::<Runtime>::contains_key(
+			&MOCK_RECIPIENT,
+			&MOCK_CONCRETE_FUNGIBLE_ID
+		));
+		assert!(!LastDeposited::<Runtime>::contains_key(
+			&MOCK_RECIPIENT,
+			&MOCK_CONCRETE_FUNGIBLE_ID
+		));
+	});
+}
+
+#[test]
+fn force_withdraw_works() {
+	ExtBuilder.build().execute_with(|| {
+		assert_ok!(UnknownTokens::deposit(
+			&(registered_location(), 10u128).into(),
+			&MOCK_RECIPIENT
+		));
+
+		assert_noop!(
+			UnknownTokens::force_withdraw(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(MOCK_RECIPIENT.into()),
+				Box::new(registered_location().into()),
+				BOB,
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			UnknownTokens::force_withdraw(
+				RuntimeOrigin::root(),
+				Box::new(MOCK_RECIPIENT.into()),
+				Box::new(MOCK_CONCRETE_FUNGIBLE_ID.into()),
+				BOB,
+			),
+			Error::<Runtime>::AssetNotRegistered
+		);
+
+		assert_ok!(UnknownTokens::force_withdraw(
+			RuntimeOrigin::root(),
+			Box::new(MOCK_RECIPIENT.into()),
+			Box::new(registered_location().into()),
+			BOB,
+		));
+		assert_eq!(Tokens::free_balance(REGISTERED_CURRENCY, &BOB), 10);
+		assert_eq!(UnknownTokens::unknown_holdings(&MOCK_RECIPIENT), vec![]);
+		System::assert_last_event(RuntimeEvent::UnknownTokens(crate::Event::Withdrawn {
+			asset: (registered_location(), 10u128).into(),
+			who: MOCK_RECIPIENT,
+		}));
+
+		assert_noop!(
+			UnknownTokens::force_withdraw(
+				RuntimeOrigin::root(),
+				Box::new(MOCK_RECIPIENT.into()),
+				Box::new(registered_location().into()),
+				BOB,
+			),
+			Error::<Runtime>::BalanceTooLow
+		);
+	});
+}
+
+#[test]
+fn force_withdraw_to_reserve_works() {
+	ExtBuilder.build().execute_with(|| {
+		let asset_id = Location::new(1, [Parachain(2), GeneralIndex(5)]);
+		assert_ok!(UnknownTokens::deposit(
+			&(asset_id.clone(), 10u128).into(),
+			&MOCK_RECIPIENT
+		));
+
+		let beneficiary: Location = Junction::AccountId32 {
+			network: None,
+			id: BOB.into(),
+		}
+		.into();
+		assert_ok!(UnknownTokens::force_withdraw_to_reserve(
+			RuntimeOrigin::root(),
+			Box::new(MOCK_RECIPIENT.into()),
+			Box::new(asset_id.clone().into()),
+			Box::new(beneficiary.into()),
+		));
+
+		let fees: Asset = (Location::new(0, [GeneralIndex(5)]), 10u128).into();
+		assert_eq!(
+			sent_xcm(),
+			vec![(
+				Location::new(1, [Parachain(2)]),
+				Xcm(vec![
+					WithdrawAsset(fees.clone().into()),
+					BuyExecution {
+						fees,
+						weight_limit: Unlimited,
+					},
+					DepositAsset {
+						assets: Wild(AllCounted(1)),
+						beneficiary: Location::new(
+							1,
+							[
+								Parachain(1),
+								Junction::AccountId32 {
+									network: None,
+									id: BOB.into(),
+								}
+							]
+						),
+					},
+				])
+			)]
+		);
+		assert_eq!(UnknownTokens::unknown_holdings(&MOCK_RECIPIENT), vec![]);
+		System::assert_last_event(RuntimeEvent::UnknownTokens(crate::Event::Withdrawn {
+			asset: (asset_id, 10u128).into(),
+			who: MOCK_RECIPIENT,
+		}));
+	});
+}
+
+#[test]
+fn sweep_expired_works() {
+	ExtBuilder.build().execute_with(|| {
+		assert_ok!(UnknownTokens::deposit(&concrete_fungible(3), &MOCK_RECIPIENT));
+
+		System::set_block_number(5);
+		assert_ok!(UnknownTokens::deposit(&concrete_fungible(2), &MOCK_RECIPIENT));
+
+		System::set_block_number(14);
+		assert_noop!(
+			UnknownTokens::sweep_expired(
+				RuntimeOrigin::root(),
+				Box::new(MOCK_RECIPIENT.into()),
+				Box::new(MOCK_CONCRETE_FUNGIBLE_ID.into()),
+			),
+			Error::<Runtime>::NotExpired
+		);
+
+		System::set_block_number(15);
+		assert_noop!(
+			UnknownTokens::sweep_expired(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(MOCK_RECIPIENT.into()),
+				Box::new(MOCK_CONCRETE_FUNGIBLE_ID.into()),
+			),
+			BadOrigin
+		);
+		assert_ok!(UnknownTokens::sweep_expired(
+			RuntimeOrigin::root(),
+			Box::new(MOCK_RECIPIENT.into()),
+			Box::new(MOCK_CONCRETE_FUNGIBLE_ID.into()),
+		));
+		assert_eq!(UnknownTokens::unknown_holdings(&MOCK_RECIPIENT), vec![]);
+		System::assert_last_event(RuntimeEvent::UnknownTokens(crate::Event::Withdrawn {
+			asset: concrete_fungible(5),
+			who: MOCK_RECIPIENT,
+		}));
+
+		assert_noop!(
+			UnknownTokens::sweep_expired(
+				RuntimeOrigin::root(),
+				Box::new(MOCK_RECIPIENT.into()),
+				Box::new(MOCK_CONCRETE_FUNGIBLE_ID.into()),
+			),
+			Error::<Runtime>::BalanceTooLow
+		);
+	});
+}
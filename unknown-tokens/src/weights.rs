@@ -0,0 +1,39 @@
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for orml_unknown_tokens.
+pub trait WeightInfo {
+	fn convert() -> Weight;
+	fn force_withdraw() -> Weight;
+	fn force_withdraw_to_reserve() -> Weight;
+	fn sweep_expired() -> Weight;
+}
+
+/// Default weights.
+impl WeightInfo for () {
+	fn convert() -> Weight {
+		Weight::from_parts(16_139_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+	}
+	fn force_withdraw() -> Weight {
+		Weight::from_parts(15_436_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+	}
+	fn force_withdraw_to_reserve() -> Weight {
+		Weight::from_parts(8_929_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn sweep_expired() -> Weight {
+		Weight::from_parts(11_073_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+}
@@ -1,3 +1,10 @@
+use parity_scale_codec::{Decode, Encode};
+use sp_runtime::offchain::{
+	storage::StorageValueRef,
+	storage_lock::{StorageLock, Time},
+	Duration,
+};
+
 /// Error which may occur while executing the off-chain code.
 #[derive(PartialEq, Eq)]
 pub enum OffchainErr {
@@ -5,6 +12,7 @@ pub enum OffchainErr {
 	SubmitTransaction,
 	NotValidator,
 	OffchainLock,
+	Http,
 }
 
 impl sp_std::fmt::Debug for OffchainErr {
@@ -14,6 +22,96 @@ impl sp_std::fmt::Debug for OffchainErr {
 			OffchainErr::SubmitTransaction => write!(fmt, "Failed to submit transaction"),
 			OffchainErr::NotValidator => write!(fmt, "Is not validator"),
 			OffchainErr::OffchainLock => write!(fmt, "Failed to manipulate offchain lock"),
+			OffchainErr::Http => write!(fmt, "Failed to fetch data over HTTP"),
+		}
+	}
+}
+
+/// Run `f` while holding the offchain lock at `lock_key`. The lock expires
+/// after `expiration` in case the worker is killed while holding it, so `f`
+/// should finish well within it.
+pub fn with_offchain_lock<R>(
+	lock_key: &[u8],
+	expiration: Duration,
+	f: impl FnOnce() -> Result<R, OffchainErr>,
+) -> Result<R, OffchainErr> {
+	let mut lock = StorageLock::<Time>::with_deadline(lock_key, expiration);
+	let _guard = lock.try_lock().map_err(|_| OffchainErr::OffchainLock)?;
+	f()
+}
+
+/// Run `f` unless it already succeeded at block `now` or later. The last
+/// block it succeeded at is kept in the persistent offchain storage at `key`.
+/// Returns `None` if `f` is skipped.
+///
+/// It should be called while holding an offchain lock, see
+/// `with_offchain_lock`.
+pub fn once_per_block<BlockNumber, R>(
+	key: &[u8],
+	now: BlockNumber,
+	f: impl FnOnce() -> Result<R, OffchainErr>,
+) -> Result<Option<R>, OffchainErr>
+where
+	BlockNumber: Encode + Decode + PartialOrd,
+{
+	let last_run = StorageValueRef::persistent(key);
+	if let Some(last) = last_run.get::<BlockNumber>().map_err(|_| OffchainErr::OffchainStore)? {
+		if last >= now {
+			return Ok(None);
 		}
 	}
+
+	let result = f()?;
+	last_run.set(&now);
+	Ok(Some(result))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::offchain::{testing::TestOffchainExt, OffchainDbExt, OffchainWorkerExt};
+	use sp_io::TestExternalities;
+
+	fn offchain_ext() -> TestExternalities {
+		let (offchain, _state) = TestOffchainExt::new();
+		let mut ext = TestExternalities::default();
+		ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+		ext.register_extension(OffchainDbExt::new(offchain));
+		ext
+	}
+
+	#[test]
+	fn with_offchain_lock_works() {
+		offchain_ext().execute_with(|| {
+			let expiration = Duration::from_millis(1_000);
+			assert_eq!(
+				with_offchain_lock(b"lock", expiration, || {
+					// the lock is held while running
+					assert_eq!(
+						with_offchain_lock(b"lock", expiration, || Ok(())),
+						Err(OffchainErr::OffchainLock)
+					);
+					Ok(1)
+				}),
+				Ok(1)
+			);
+			// and released afterwards
+			assert_eq!(with_offchain_lock(b"lock", expiration, || Ok(2)), Ok(2));
+		});
+	}
+
+	#[test]
+	fn once_per_block_works() {
+		offchain_ext().execute_with(|| {
+			assert_eq!(
+				once_per_block(b"last", 1u32, || Err::<(), _>(OffchainErr::Http)),
+				Err(OffchainErr::Http)
+			);
+			// failures don't count
+			assert_eq!(once_per_block(b"last", 1u32, || Ok(1)), Ok(Some(1)));
+			assert_eq!(once_per_block(b"last", 1u32, || Ok(2)), Ok(None));
+			assert_eq!(once_per_block(b"last", 0u32, || Ok(3)), Ok(None));
+			assert_eq!(once_per_block(b"last", 2u32, || Ok(4)), Ok(Some(4)));
+		});
+	}
 }
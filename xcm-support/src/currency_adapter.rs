@@ -1,5 +1,5 @@
 use frame_support::traits::{ExistenceRequirement, Get};
-use parity_scale_codec::FullCodec;
+use parity_scale_codec::{Encode, FullCodec};
 use sp_runtime::{
 	traits::{Convert, MaybeSerializeDeserialize, SaturatedConversion},
 	DispatchError,
@@ -18,6 +18,8 @@ use xcm_executor::{
 	AssetsInHolding,
 };
 
+use orml_traits::{Happened, RateLimiter as RateLimiterT};
+
 use crate::UnknownAsset as UnknownAssetT;
 
 /// Asset transaction errors.
@@ -55,6 +57,12 @@ pub trait OnDepositFail<CurrencyId, AccountId, Balance> {
 	fn on_deposit_unknown_asset_fail(err: DispatchError, _asset: &Asset, _location: &Location) -> Result {
 		Err(XcmError::FailedToTransactAsset(err.into()))
 	}
+
+	/// Called on deposits refused by the rate limiter. Returns a
+	/// `RateLimited` error by default, so the assets are trapped.
+	fn on_deposit_rate_limited(_currency_id: CurrencyId, _who: &AccountId, _amount: Balance) -> Result {
+		Err(XcmError::FailedToTransactAsset("RateLimited"))
+	}
 }
 
 impl<CurrencyId, AccountId, Balance> OnDepositFail<CurrencyId, AccountId, Balance> for () {
@@ -70,17 +78,29 @@ impl<CurrencyId, AccountId, Balance> OnDepositFail<CurrencyId, AccountId, Balanc
 
 /// `OnDepositFail` impl, will deposit known currencies to an alternative
 /// account.
-pub struct DepositToAlternative<Alternative, MultiCurrency, CurrencyId, AccountId, Balance>(
-	PhantomData<(Alternative, MultiCurrency, CurrencyId, AccountId, Balance)>,
+///
+/// Deposits refused by the rate limiter are deposited to the alternative
+/// account too, and reported to `OnRateLimited` with the currency id,
+/// beneficiary and amount, e.g. to deposit an event.
+pub struct DepositToAlternative<Alternative, MultiCurrency, CurrencyId, AccountId, Balance, OnRateLimited = ()>(
+	PhantomData<(
+		Alternative,
+		MultiCurrency,
+		CurrencyId,
+		AccountId,
+		Balance,
+		OnRateLimited,
+	)>,
 );
 impl<
 		Alternative: Get<AccountId>,
 		MultiCurrency: orml_traits::MultiCurrency<AccountId, CurrencyId = CurrencyId, Balance = Balance>,
 		AccountId: sp_std::fmt::Debug + Clone,
 		CurrencyId: FullCodec + Eq + PartialEq + Copy + MaybeSerializeDeserialize + Debug,
-		Balance,
+		Balance: Clone,
+		OnRateLimited: Happened<(CurrencyId, AccountId, Balance)>,
 	> OnDepositFail<CurrencyId, AccountId, Balance>
-	for DepositToAlternative<Alternative, MultiCurrency, CurrencyId, AccountId, Balance>
+	for DepositToAlternative<Alternative, MultiCurrency, CurrencyId, AccountId, Balance, OnRateLimited>
 {
 	fn on_deposit_currency_fail(
 		_err: DispatchError,
@@ -91,6 +111,13 @@ impl<
 		MultiCurrency::deposit(currency_id, &Alternative::get(), amount)
 			.map_err(|e| XcmError::FailedToTransactAsset(e.into()))
 	}
+
+	fn on_deposit_rate_limited(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> Result {
+		MultiCurrency::deposit(currency_id, &Alternative::get(), amount.clone())
+			.map_err(|e| XcmError::FailedToTransactAsset(e.into()))?;
+		OnRateLimited::happened(&(currency_id, who.clone(), amount));
+		Ok(())
+	}
 }
 
 /// The `TransactAsset` implementation, to handle `Asset` deposit/withdraw.
@@ -101,6 +128,12 @@ impl<
 ///
 /// If the asset is known, deposit/withdraw will be handled by `MultiCurrency`,
 /// else by `UnknownAsset` if unknown.
+///
+/// Deposits of known assets consume the quota of `RateLimiter`, keyed by the
+/// `AssetId` and whitelisted by the beneficiary account. Deposits over the
+/// limit are passed to `DepositFailureHandler::on_deposit_rate_limited`, which
+/// could park them in a quarantine account, or leave them trapped. By default
+/// there is no limit.
 #[allow(clippy::type_complexity)]
 pub struct MultiCurrencyAdapter<
 	MultiCurrency,
@@ -111,6 +144,8 @@ pub struct MultiCurrencyAdapter<
 	CurrencyId,
 	CurrencyIdConvert,
 	DepositFailureHandler,
+	RateLimiter = (),
+	RateLimiterId = (),
 >(
 	PhantomData<(
 		MultiCurrency,
@@ -121,6 +156,8 @@ pub struct MultiCurrencyAdapter<
 		CurrencyId,
 		CurrencyIdConvert,
 		DepositFailureHandler,
+		RateLimiter,
+		RateLimiterId,
 	)>,
 );
 
@@ -128,11 +165,13 @@ impl<
 		MultiCurrency: orml_traits::MultiCurrency<AccountId, CurrencyId = CurrencyId>,
 		UnknownAsset: UnknownAssetT,
 		Match: MatchesFungible<MultiCurrency::Balance>,
-		AccountId: sp_std::fmt::Debug + Clone,
+		AccountId: sp_std::fmt::Debug + Clone + Encode,
 		AccountIdConvert: ConvertLocation<AccountId>,
 		CurrencyId: FullCodec + Eq + PartialEq + Copy + MaybeSerializeDeserialize + Debug,
 		CurrencyIdConvert: Convert<Asset, Option<CurrencyId>>,
 		DepositFailureHandler: OnDepositFail<CurrencyId, AccountId, MultiCurrency::Balance>,
+		RateLimiter: RateLimiterT,
+		RateLimiterId: Get<RateLimiter::RateLimiterId>,
 	> TransactAsset
 	for MultiCurrencyAdapter<
 		MultiCurrency,
@@ -143,6 +182,8 @@ impl<
 		CurrencyId,
 		CurrencyIdConvert,
 		DepositFailureHandler,
+		RateLimiter,
+		RateLimiterId,
 	>
 {
 	fn deposit_asset(asset: &Asset, location: &Location, _context: Option<&XcmContext>) -> Result {
@@ -152,8 +193,18 @@ impl<
 			Match::matches_fungible(asset),
 		) {
 			// known asset
-			(Some(who), Some(currency_id), Some(amount)) => MultiCurrency::deposit(currency_id, &who, amount)
-				.or_else(|err| DepositFailureHandler::on_deposit_currency_fail(err, currency_id, &who, amount)),
+			(Some(who), Some(currency_id), Some(amount)) => {
+				let value = match asset.fun {
+					Fungible(value) => value,
+					NonFungible(_) => 1,
+				};
+				if RateLimiter::try_consume(RateLimiterId::get(), asset.id.clone(), value, Some(&who)).is_err() {
+					return DepositFailureHandler::on_deposit_rate_limited(currency_id, &who, amount);
+				}
+
+				MultiCurrency::deposit(currency_id, &who, amount)
+					.or_else(|err| DepositFailureHandler::on_deposit_currency_fail(err, currency_id, &who, amount))
+			}
 			// unknown asset
 			_ => UnknownAsset::deposit(asset, location)
 				.or_else(|err| DepositFailureHandler::on_deposit_unknown_asset_fail(err, asset, location)),
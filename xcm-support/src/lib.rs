@@ -14,16 +14,21 @@ use sp_runtime::{
 	traits::{CheckedConversion, Convert},
 	DispatchError,
 };
-use sp_std::marker::PhantomData;
+use sp_std::{marker::PhantomData, prelude::*};
 
 use xcm::v5::prelude::*;
 use xcm_executor::traits::MatchesFungible;
 
-use orml_traits::{location::Reserve, GetByKey};
+use orml_traits::{location::Reserve, xcm_transfer::RouteHop, GetByKey};
 
 pub use currency_adapter::{DepositToAlternative, MultiCurrencyAdapter, OnDepositFail};
+#[cfg(feature = "nft")]
+pub use nft_adapter::NonFungibleAdapter;
 
 mod currency_adapter;
+pub mod migration;
+#[cfg(feature = "nft")]
+mod nft_adapter;
 
 mod tests;
 
@@ -45,6 +50,37 @@ where
 	}
 }
 
+/// A `Convert` implementation. Converts alias locations into their canonical
+/// location by `Aliases`, before converting them by `CurrencyIdConvert`, so
+/// assets arriving under several locations map to the same `CurrencyId`.
+///
+/// Could be used as the `CurrencyIdConvert` of both `IsNativeConcrete` and
+/// `MultiCurrencyAdapter`. Converting currency ids back to locations is left
+/// to `CurrencyIdConvert`, which should return the canonical location.
+pub struct AliasedCurrencyIdConvert<Aliases, CurrencyIdConvert>(PhantomData<(Aliases, CurrencyIdConvert)>);
+impl<Aliases, CurrencyIdConvert, CurrencyId> Convert<Location, Option<CurrencyId>>
+	for AliasedCurrencyIdConvert<Aliases, CurrencyIdConvert>
+where
+	Aliases: GetByKey<Location, Option<Location>>,
+	CurrencyIdConvert: Convert<Location, Option<CurrencyId>>,
+{
+	fn convert(location: Location) -> Option<CurrencyId> {
+		let canonical = Aliases::get(&location).unwrap_or(location);
+		CurrencyIdConvert::convert(canonical)
+	}
+}
+impl<Aliases, CurrencyIdConvert, CurrencyId> Convert<Asset, Option<CurrencyId>>
+	for AliasedCurrencyIdConvert<Aliases, CurrencyIdConvert>
+where
+	Aliases: GetByKey<Location, Option<Location>>,
+	CurrencyIdConvert: Convert<Location, Option<CurrencyId>>,
+{
+	fn convert(asset: Asset) -> Option<CurrencyId> {
+		let AssetId(location) = asset.id;
+		<Self as Convert<Location, Option<CurrencyId>>>::convert(location)
+	}
+}
+
 /// A `ContainsPair` implementation. Filters multi native assets whose
 /// reserve is same with `origin`.
 pub struct MultiNativeAsset<ReserveProvider>(PhantomData<ReserveProvider>);
@@ -89,3 +125,11 @@ impl GetByKey<Location, Option<u128>> for DisabledParachainFee {
 		None
 	}
 }
+
+// Default implementation for xTokens::AssetRoutes
+pub struct DisabledAssetRoutes;
+impl GetByKey<Location, Option<Vec<RouteHop>>> for DisabledAssetRoutes {
+	fn get(_key: &Location) -> Option<Vec<RouteHop>> {
+		None
+	}
+}
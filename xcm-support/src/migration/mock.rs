@@ -0,0 +1,48 @@
+//! Mocks for the location keys migration.
+
+#![cfg(test)]
+
+use super::*;
+
+use frame_support::{
+	construct_runtime, derive_impl, storage_alias, weights::constants::RocksDbWeight, Blake2_128Concat, Twox64Concat,
+};
+use sp_runtime::BuildStorage;
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type Block = Block;
+	type DbWeight = RocksDbWeight;
+}
+
+/// Storage as written before the migration, keyed by `xcm::v4::Location`.
+pub mod v4 {
+	use super::*;
+
+	#[storage_alias]
+	pub type LocationToId = StorageMap<Test, Twox64Concat, xcm::v4::Location, u32>;
+
+	#[storage_alias]
+	pub type Balances = StorageDoubleMap<Test, Blake2_128Concat, xcm::v4::Location, Blake2_128Concat, Vec<u8>, u128>;
+}
+
+#[storage_alias]
+pub type LocationToId = StorageMap<Test, Twox64Concat, Location, u32>;
+
+#[storage_alias]
+pub type Balances = StorageDoubleMap<Test, Blake2_128Concat, Location, Blake2_128Concat, Vec<u8>, u128>;
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+	}
+);
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	frame_system::GenesisConfig::<Runtime>::default()
+		.build_storage()
+		.unwrap()
+		.into()
+}
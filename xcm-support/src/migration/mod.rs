@@ -0,0 +1,300 @@
+//! Migration of storage keyed by `Location`.
+//!
+//! Storage keys are hashed from the encoded `Location`, so an XCM version
+//! which changes the encoding of some locations leaves their entries
+//! unreachable. `MigrateLocationKeys` re-encodes and rehashes such keys, for
+//! any `StorageMap` or `StorageDoubleMap` whose keys are described by
+//! `MigrateKey`. For instance:
+//!
+//! ```ignore
+//! type MultiBlockMigrations = (
+//!     MigrateLocationKeys<
+//!         Runtime,
+//!         orml_asset_registry::LocationToAssetId<Runtime>,
+//!         AssetId,
+//!         LocationKey<Twox64Concat, xcm::v4::Location>,
+//!     >,
+//!     MigrateLocationKeys<
+//!         Runtime,
+//!         orml_unknown_tokens::ConcreteFungibleBalances<Runtime>,
+//!         u128,
+//!         (
+//!             LocationKey<Blake2_128Concat, xcm::v4::Location>,
+//!             LocationKey<Blake2_128Concat, xcm::v4::Location>,
+//!         ),
+//!     >,
+//!     MigrateLocationKeys<
+//!         Runtime,
+//!         orml_unknown_tokens::AbstractFungibleBalances<Runtime>,
+//!         u128,
+//!         (
+//!             LocationKey<Blake2_128Concat, xcm::v4::Location>,
+//!             PlainKey<Blake2_128Concat, Vec<u8>>,
+//!         ),
+//!     >,
+//! );
+//! ```
+//!
+//! `MigrateLocationKeys` is a `SteppedMigration`, to be run over multiple
+//! blocks by `pallet_migrations`, which also makes sure it's run only once.
+//! The old encoding is decoded unconditionally, so it must not be run again.
+//! Entries are first moved to a temporary prefix under their new keys, then
+//! moved back, so that no entry is migrated twice. The storage is incomplete
+//! until the migration is done.
+//!
+//! Without `pallet_migrations`, `migrate_location_keys` migrates a whole
+//! storage at once, e.g. in an `OnRuntimeUpgrade` of a small map, and
+//! `migrate_key` converts a single raw key, e.g. for a hand-written migration.
+//! Keys stored as a `VersionedLocation` are described by
+//! `LocationKey<H, VersionedLocation>`.
+
+use frame_support::{
+	migrations::{SteppedMigration, SteppedMigrationError},
+	storage::{unhashed, PrefixIterator, StoragePrefixedMap},
+	traits::Get,
+	weights::WeightMeter,
+	ReversibleStorageHasher, StorageHasher, Twox128,
+};
+use parity_scale_codec::{Decode, Encode, FullCodec, MaxEncodedLen};
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm::v5::Location;
+
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+mod mock;
+mod tests;
+
+/// A storage key, or a part of it, to migrate.
+pub trait MigrateKey {
+	/// Read the key from `input`, and write it re-encoded and rehashed to
+	/// `output`.
+	fn migrate(input: &mut &[u8], output: &mut Vec<u8>) -> Option<()>;
+
+	/// Read the key from `input`, checking it's in the new encoding.
+	fn check(input: &mut &[u8]) -> Option<()>;
+}
+
+/// A `Location` key hashed with `H`, previously encoded as `OldLocation`.
+pub struct LocationKey<H, OldLocation>(PhantomData<(H, OldLocation)>);
+
+impl<H, OldLocation> MigrateKey for LocationKey<H, OldLocation>
+where
+	H: ReversibleStorageHasher,
+	OldLocation: Decode,
+	Location: TryFrom<OldLocation>,
+{
+	fn migrate(input: &mut &[u8], output: &mut Vec<u8>) -> Option<()> {
+		*input = H::reverse(input);
+		let old = OldLocation::decode(input).ok()?;
+		let location = Location::try_from(old).ok()?;
+		output.extend_from_slice(H::hash(&location.encode()).as_ref());
+		Some(())
+	}
+
+	fn check(input: &mut &[u8]) -> Option<()> {
+		*input = H::reverse(input);
+		Location::decode(input).ok().map(|_| ())
+	}
+}
+
+/// A key of type `K` hashed with `H`, which is kept as is.
+pub struct PlainKey<H, K>(PhantomData<(H, K)>);
+
+impl<H: ReversibleStorageHasher, K: FullCodec> MigrateKey for PlainKey<H, K> {
+	fn migrate(input: &mut &[u8], output: &mut Vec<u8>) -> Option<()> {
+		*input = H::reverse(input);
+		let key = K::decode(input).ok()?;
+		output.extend_from_slice(H::hash(&key.encode()).as_ref());
+		Some(())
+	}
+
+	fn check(input: &mut &[u8]) -> Option<()> {
+		*input = H::reverse(input);
+		K::decode(input).ok().map(|_| ())
+	}
+}
+
+/// The two keys of a double map.
+impl<K1: MigrateKey, K2: MigrateKey> MigrateKey for (K1, K2) {
+	fn migrate(input: &mut &[u8], output: &mut Vec<u8>) -> Option<()> {
+		K1::migrate(input, output)?;
+		K2::migrate(input, output)
+	}
+
+	fn check(input: &mut &[u8]) -> Option<()> {
+		K1::check(input)?;
+		K2::check(input)
+	}
+}
+
+/// Re-encode and rehash a raw storage `key`, without the storage prefix, as
+/// described by `Keys`. Returns `None` if it can't be migrated.
+pub fn migrate_key<Keys: MigrateKey>(key: &[u8]) -> Option<Vec<u8>> {
+	let mut input = key;
+	let mut new_key = Vec::with_capacity(key.len());
+	Keys::migrate(&mut input, &mut new_key)?;
+	input.is_empty().then_some(new_key)
+}
+
+/// Re-encode and rehash all keys of `Storage` at once, as described by
+/// `Keys`, and return the number of entries.
+///
+/// Like `MigrateLocationKeys`, but in a single call, without a weight limit,
+/// so only for storage small enough to fit in a block. It must be run only
+/// once.
+pub fn migrate_location_keys<Storage, Value, Keys>() -> u32
+where
+	Storage: StoragePrefixedMap<Value>,
+	Value: FullCodec,
+	Keys: MigrateKey,
+{
+	let mut count = 0;
+	while MigrateLocationKeys::<(), Storage, Value, Keys>::rekey_first() {
+		count += 1;
+	}
+	while MigrateLocationKeys::<(), Storage, Value, Keys>::restore_first() {}
+	count
+}
+
+/// The stage of `MigrateLocationKeys`, which is its cursor.
+#[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MigrationStage {
+	/// Entries are moved to the temporary prefix, under their new keys.
+	Rekey,
+	/// Entries are moved back from the temporary prefix.
+	Restore,
+}
+
+/// Re-encode and rehash the keys of `Storage`, as described by `Keys`.
+///
+/// Values are moved as is. Entries which can't be migrated are left
+/// untouched, and reported by the try-runtime post-check.
+pub struct MigrateLocationKeys<T, Storage, Value, Keys>(PhantomData<(T, Storage, Value, Keys)>);
+
+impl<T, Storage, Value, Keys> MigrateLocationKeys<T, Storage, Value, Keys>
+where
+	Storage: StoragePrefixedMap<Value>,
+	Value: FullCodec,
+{
+	/// The prefix entries are moved to while their keys are migrated.
+	fn temporary_prefix() -> Vec<u8> {
+		[&Twox128::hash(b"MigrateLocationKeys")[..], &Storage::final_prefix()[..]].concat()
+	}
+
+	/// Remove the first entry under `prefix`, returning its raw key, without
+	/// the prefix, and raw value.
+	fn take_first(prefix: &[u8]) -> Option<(Vec<u8>, Vec<u8>)> {
+		PrefixIterator::<(Vec<u8>, Vec<u8>)>::new(prefix.to_vec(), prefix.to_vec(), |key, value| {
+			Ok((key.to_vec(), value.to_vec()))
+		})
+		.drain()
+		.next()
+	}
+
+	/// Move the first entry of `Storage` to the temporary prefix, under its
+	/// new key. Returns `false` if there are no entries left.
+	fn rekey_first() -> bool
+	where
+		Keys: MigrateKey,
+	{
+		let Some((key, value)) = Self::take_first(&Storage::final_prefix()) else {
+			return false;
+		};
+		let new_key = migrate_key::<Keys>(&key).unwrap_or_else(|| {
+			log::warn!(
+				target: "xcm-support::migration",
+				"MigrateLocationKeys: failed to migrate key {key:?}",
+			);
+			key
+		});
+		unhashed::put_raw(&[&Self::temporary_prefix()[..], &new_key].concat(), &value);
+		true
+	}
+
+	/// Move the first entry under the temporary prefix back to `Storage`.
+	/// Returns `false` if there are no entries left.
+	fn restore_first() -> bool {
+		let Some((key, value)) = Self::take_first(&Self::temporary_prefix()) else {
+			return false;
+		};
+		unhashed::put_raw(&[&Storage::final_prefix()[..], &key].concat(), &value);
+		true
+	}
+
+	/// All raw keys, without the storage prefix, and raw values of `Storage`.
+	#[cfg(feature = "try-runtime")]
+	fn raw_entries() -> Vec<(Vec<u8>, Vec<u8>)> {
+		let prefix = Storage::final_prefix().to_vec();
+		PrefixIterator::<(Vec<u8>, Vec<u8>)>::new(prefix.clone(), prefix, |key, value| {
+			Ok((key.to_vec(), value.to_vec()))
+		})
+		.collect()
+	}
+}
+
+impl<T, Storage, Value, Keys> SteppedMigration for MigrateLocationKeys<T, Storage, Value, Keys>
+where
+	T: frame_system::Config,
+	Storage: StoragePrefixedMap<Value>,
+	Value: FullCodec,
+	Keys: MigrateKey,
+{
+	type Cursor = MigrationStage;
+	type Identifier = ([u8; 19], [u8; 32]);
+
+	fn id() -> Self::Identifier {
+		(*b"MigrateLocationKeys", Storage::final_prefix())
+	}
+
+	fn step(
+		cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+		// an entry is read, removed and written
+		let required = T::DbWeight::get().reads_writes(1, 2);
+		if meter.remaining().any_lt(required) {
+			return Err(SteppedMigrationError::InsufficientWeight { required });
+		}
+
+		let mut stage = cursor.unwrap_or(MigrationStage::Rekey);
+		while meter.try_consume(required).is_ok() {
+			match stage {
+				MigrationStage::Rekey => {
+					if !Self::rekey_first() {
+						stage = MigrationStage::Restore;
+					}
+				}
+				MigrationStage::Restore => {
+					if !Self::restore_first() {
+						return Ok(None);
+					}
+				}
+			}
+		}
+		Ok(Some(stage))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+		Ok((Self::raw_entries().len() as u32).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+		let count = u32::decode(&mut &state[..]).map_err(|_| "MigrateLocationKeys: invalid pre-upgrade state")?;
+		let entries = Self::raw_entries();
+		frame_support::ensure!(
+			entries.len() as u32 == count,
+			"MigrateLocationKeys: entries lost, keys may have collided"
+		);
+		for (key, _) in entries.iter() {
+			let mut input = &key[..];
+			frame_support::ensure!(
+				Keys::check(&mut input).is_some() && input.is_empty(),
+				"MigrateLocationKeys: key not in the new encoding"
+			);
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,215 @@
+//! Unit tests for the location keys migration.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	traits::Get,
+	weights::{RuntimeDbWeight, Weight},
+	Blake2_128Concat, StorageHasher, Twox64Concat,
+};
+use mock::*;
+
+type MigrateLocationToId =
+	MigrateLocationKeys<Runtime, LocationToId, u32, LocationKey<Twox64Concat, xcm::v4::Location>>;
+
+type MigrateBalances = MigrateLocationKeys<
+	Runtime,
+	Balances,
+	u128,
+	(
+		LocationKey<Blake2_128Concat, xcm::v4::Location>,
+		PlainKey<Blake2_128Concat, Vec<u8>>,
+	),
+>;
+
+/// The weight of migrating an entry.
+fn entry_weight() -> Weight {
+	<<Runtime as frame_system::Config>::DbWeight as Get<RuntimeDbWeight>>::get().reads_writes(1, 2)
+}
+
+/// Run `M` to completion, migrating `entries_per_step` entries per step, and
+/// return the number of steps.
+fn run_migration<M: SteppedMigration<Cursor = MigrationStage>>(entries_per_step: u64) -> u32 {
+	let entry_weight = entry_weight();
+	let mut cursor = None;
+	let mut steps = 0;
+	loop {
+		steps += 1;
+		let mut meter = WeightMeter::with_limit(entry_weight.saturating_mul(entries_per_step));
+		match M::step(cursor, &mut meter).unwrap() {
+			Some(next) => cursor = Some(next),
+			None => return steps,
+		}
+	}
+}
+
+/// Its encoding changes in v5, where `Westend` is identified by its genesis
+/// hash.
+fn westend_v4() -> xcm::v4::Location {
+	xcm::v4::Location::new(1, [xcm::v4::Junction::GlobalConsensus(xcm::v4::NetworkId::Westend)])
+}
+
+fn westend() -> Location {
+	westend_v4().try_into().unwrap()
+}
+
+#[test]
+fn location_encoding_changes() {
+	assert_ne!(westend_v4().encode(), westend().encode());
+	assert_eq!(xcm::v4::Location::parent().encode(), Location::parent().encode());
+}
+
+#[test]
+fn migrate_map_keys() {
+	new_test_ext().execute_with(|| {
+		v4::LocationToId::insert(westend_v4(), 1);
+		v4::LocationToId::insert(xcm::v4::Location::parent(), 2);
+		assert_eq!(LocationToId::get(westend()), None);
+
+		run_migration::<MigrateLocationToId>(10);
+
+		assert_eq!(LocationToId::get(westend()), Some(1));
+		assert_eq!(LocationToId::get(Location::parent()), Some(2));
+		assert_eq!(LocationToId::iter().count(), 2);
+		assert_eq!(v4::LocationToId::get(westend_v4()), None);
+	});
+}
+
+#[test]
+fn migrate_double_map_keys() {
+	new_test_ext().execute_with(|| {
+		v4::Balances::insert(westend_v4(), b"A".to_vec(), 10);
+		v4::Balances::insert(westend_v4(), b"B".to_vec(), 20);
+		v4::Balances::insert(xcm::v4::Location::parent(), b"A".to_vec(), 30);
+
+		assert_eq!(run_migration::<MigrateBalances>(1), 8);
+
+		assert_eq!(Balances::get(westend(), b"A".to_vec()), Some(10));
+		assert_eq!(Balances::get(westend(), b"B".to_vec()), Some(20));
+		assert_eq!(Balances::get(Location::parent(), b"A".to_vec()), Some(30));
+		assert_eq!(Balances::iter_prefix(westend()).count(), 2);
+		assert_eq!(Balances::iter().count(), 3);
+	});
+}
+
+#[test]
+fn undecodable_keys_are_left_untouched() {
+	new_test_ext().execute_with(|| {
+		let prefix = LocationToId::final_prefix();
+		let bad_key = [&prefix[..], &Twox64Concat::hash(&[0xff])].concat();
+		frame_support::storage::unhashed::put(&bad_key, &3u32);
+		v4::LocationToId::insert(westend_v4(), 1);
+
+		run_migration::<MigrateLocationToId>(10);
+
+		assert_eq!(LocationToId::get(westend()), Some(1));
+		assert_eq!(frame_support::storage::unhashed::get::<u32>(&bad_key), Some(3));
+	});
+}
+
+#[test]
+fn migration_is_stepped() {
+	new_test_ext().execute_with(|| {
+		v4::LocationToId::insert(westend_v4(), 1);
+		v4::LocationToId::insert(xcm::v4::Location::parent(), 2);
+
+		assert!(matches!(
+			MigrateLocationToId::step(None, &mut WeightMeter::with_limit(Weight::zero())),
+			Err(SteppedMigrationError::InsufficientWeight { .. })
+		));
+
+		let entry_weight = entry_weight();
+		let mut meter = WeightMeter::with_limit(entry_weight.saturating_mul(2));
+		assert_eq!(
+			MigrateLocationToId::step(None, &mut meter).unwrap(),
+			Some(MigrationStage::Rekey)
+		);
+		// the entries are out of the storage while being migrated
+		assert_eq!(LocationToId::iter().count(), 0);
+		assert_eq!(meter.consumed(), entry_weight.saturating_mul(2));
+
+		let mut meter = WeightMeter::with_limit(entry_weight.saturating_mul(3));
+		assert_eq!(
+			MigrateLocationToId::step(Some(MigrationStage::Rekey), &mut meter).unwrap(),
+			Some(MigrationStage::Restore)
+		);
+		assert_eq!(LocationToId::iter().count(), 2);
+
+		let mut meter = WeightMeter::with_limit(entry_weight);
+		assert_eq!(
+			MigrateLocationToId::step(Some(MigrationStage::Restore), &mut meter).unwrap(),
+			None
+		);
+		assert_eq!(LocationToId::get(westend()), Some(1));
+		assert_eq!(LocationToId::get(Location::parent()), Some(2));
+	});
+}
+
+#[test]
+fn migrate_raw_keys() {
+	let new_key = Twox64Concat::hash(&westend().encode());
+	assert_eq!(
+		migrate_key::<LocationKey<Twox64Concat, xcm::v4::Location>>(&Twox64Concat::hash(&westend_v4().encode())),
+		Some(new_key.clone())
+	);
+
+	let versioned = xcm::VersionedLocation::V4(westend_v4());
+	assert_eq!(
+		migrate_key::<LocationKey<Twox64Concat, xcm::VersionedLocation>>(&Twox64Concat::hash(&versioned.encode())),
+		Some(new_key)
+	);
+
+	// undecodable keys, and keys with trailing bytes, can't be migrated
+	assert_eq!(
+		migrate_key::<LocationKey<Twox64Concat, xcm::v4::Location>>(&Twox64Concat::hash(&[0xff])),
+		None
+	);
+	let trailing = [&Twox64Concat::hash(&westend_v4().encode())[..], &[0]].concat();
+	assert_eq!(
+		migrate_key::<LocationKey<Twox64Concat, xcm::v4::Location>>(&trailing),
+		None
+	);
+}
+
+#[test]
+fn migrate_all_keys_at_once() {
+	new_test_ext().execute_with(|| {
+		v4::Balances::insert(westend_v4(), b"A".to_vec(), 10);
+		v4::Balances::insert(westend_v4(), b"B".to_vec(), 20);
+		v4::Balances::insert(xcm::v4::Location::parent(), b"A".to_vec(), 30);
+
+		assert_eq!(
+			migrate_location_keys::<
+				Balances,
+				u128,
+				(
+					LocationKey<Blake2_128Concat, xcm::v4::Location>,
+					PlainKey<Blake2_128Concat, Vec<u8>>,
+				),
+			>(),
+			3
+		);
+
+		assert_eq!(Balances::get(westend(), b"A".to_vec()), Some(10));
+		assert_eq!(Balances::get(westend(), b"B".to_vec()), Some(20));
+		assert_eq!(Balances::get(Location::parent(), b"A".to_vec()), Some(30));
+		assert_eq!(Balances::iter().count(), 3);
+	});
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn try_runtime_checks() {
+	new_test_ext().execute_with(|| {
+		v4::Balances::insert(westend_v4(), b"A".to_vec(), 10);
+		v4::Balances::insert(xcm::v4::Location::parent(), b"A".to_vec(), 30);
+
+		let state = MigrateBalances::pre_upgrade().unwrap();
+		// the old encoding fails the check
+		assert!(MigrateBalances::post_upgrade(state.clone()).is_err());
+
+		run_migration::<MigrateBalances>(10);
+		assert!(MigrateBalances::post_upgrade(state).is_ok());
+	});
+}
@@ -0,0 +1,110 @@
+//! Mocks for the non-fungible adapter.
+
+#![cfg(test)]
+
+use super::*;
+
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types, storage_alias, traits::ConstU32, Blake2_128Concat, Twox64Concat,
+};
+use sp_runtime::{traits::IdentityLookup, AccountId32, BuildStorage};
+
+pub type AccountId = AccountId32;
+pub type ClassId = u32;
+pub type TokenId = u64;
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+}
+
+impl orml_nft::Config for Runtime {
+	type ClassId = ClassId;
+	type TokenId = TokenId;
+	type ClassData = ();
+	type TokenData = ();
+	type MaxClassMetadata = ConstU32<1>;
+	type MaxTokenMetadata = ConstU32<1>;
+}
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Nft: orml_nft,
+	}
+);
+
+pub const ALICE: AccountId = AccountId32::new([1u8; 32]);
+pub const BOB: AccountId = AccountId32::new([2u8; 32]);
+
+parameter_types! {
+	pub const CheckingAccount: AccountId = AccountId32::new([0xffu8; 32]);
+	pub SelfLocation: Location = Location::new(1, [Parachain(2000)]);
+}
+
+/// The asset id of a local class.
+pub fn class_location(class_id: ClassId) -> Location {
+	Location::new(0, [PalletInstance(1), GeneralIndex(class_id.into())])
+}
+
+/// The local class of the derivatives of a sibling class.
+pub const DERIVATIVE_CLASS: ClassId = 0;
+
+/// The asset id of the sibling class of `DERIVATIVE_CLASS`.
+pub fn sibling_class_location() -> Location {
+	Location::new(1, [Parachain(1000), PalletInstance(1), GeneralIndex(5)])
+}
+
+pub struct ClassIdConvert;
+impl Convert<Location, Option<ClassId>> for ClassIdConvert {
+	fn convert(location: Location) -> Option<ClassId> {
+		if location == sibling_class_location() {
+			return Some(DERIVATIVE_CLASS);
+		}
+		match location.unpack() {
+			(0, [PalletInstance(1), GeneralIndex(index)])
+			| (1, [Parachain(2000), PalletInstance(1), GeneralIndex(index)]) => (*index).try_into().ok(),
+			_ => None,
+		}
+	}
+}
+
+pub struct AccountIdConvert;
+impl ConvertLocation<AccountId> for AccountIdConvert {
+	fn convert_location(location: &Location) -> Option<AccountId> {
+		match location.unpack() {
+			(0, [Junction::AccountId32 { id, .. }]) => Some((*id).into()),
+			_ => None,
+		}
+	}
+}
+
+/// The local token ids of derivative tokens.
+///
+/// map: (class_id, asset_instance_index) => token_id
+#[storage_alias(pallet_name)]
+pub type DerivativeTokens = StorageDoubleMap<Nft, Twox64Concat, ClassId, Blake2_128Concat, u128, TokenId>;
+
+pub type NftTransactor =
+	NonFungibleAdapter<Runtime, ClassIdConvert, AccountIdConvert, CheckingAccount, SelfLocation, DerivativeTokens>;
+
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::<Runtime>::default()
+			.build_storage()
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			assert_eq!(Nft::create_class(&ALICE, vec![], ()), Ok(DERIVATIVE_CLASS));
+		});
+		ext
+	}
+}
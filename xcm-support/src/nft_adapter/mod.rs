@@ -0,0 +1,197 @@
+//! # Non-fungible Adapter
+//!
+//! A `TransactAsset` implementation for `orml_nft`. Non-fungible assets are
+//! mapped onto `orml_nft` tokens, the asset id by `ClassIdConvert` onto the
+//! class id, and the `AssetInstance::Index` onto the token id.
+//!
+//! Tokens of local classes, whose asset ids are relative to this chain or
+//! start with `SelfLocation`, are withdrawn into the holding register by
+//! moving them to `CheckingAccount`, and released from it on deposits. They
+//! are transferred to the sovereign account of the destination on reserve
+//! transfers, so `CheckingAccount` only holds tokens in transit.
+//!
+//! Derivative tokens, whose reserve is on another chain, are minted on
+//! deposits and burned on withdrawals. As `orml_nft` assigns token ids, the
+//! token id of each deposited instance is recorded in the `DerivativeTokens`
+//! storage supplied by the runtime. The classes of derivative tokens are
+//! expected to be created when the assets are registered.
+//!
+//! Only `AssetInstance::Index` instances are supported, as they map onto
+//! token ids. Other instances of known classes fail with
+//! `XcmError::FailedToTransactAsset("UnsupportedAssetInstance")`.
+
+use frame_support::{storage::StorageDoubleMap, traits::Get};
+use sp_runtime::traits::{CheckedConversion, Convert};
+use sp_std::{marker::PhantomData, result, vec::Vec};
+
+use xcm::v5::{prelude::*, Asset, Error as XcmError, Location, Result as XcmResult};
+use xcm_executor::{
+	traits::{ConvertLocation, TransactAsset},
+	AssetsInHolding,
+};
+
+mod mock;
+mod tests;
+
+/// Asset transaction errors.
+enum Error {
+	/// `Location` to `AccountId` Conversion failed.
+	AccountIdConversionFailed,
+	/// The asset instance isn't an `AssetInstance::Index`.
+	UnsupportedAssetInstance,
+}
+
+impl From<Error> for XcmError {
+	fn from(e: Error) -> Self {
+		match e {
+			Error::AccountIdConversionFailed => XcmError::FailedToTransactAsset("AccountIdConversionFailed"),
+			Error::UnsupportedAssetInstance => XcmError::FailedToTransactAsset("UnsupportedAssetInstance"),
+		}
+	}
+}
+
+/// The `TransactAsset` implementation, to handle non-fungible assets by
+/// `orml_nft`.
+///
+/// Assets which are not non-fungible, or whose class is unknown, are not
+/// matched and `XcmError::AssetNotFound` is returned, so this adapter could be
+/// used in a tuple before `MultiCurrencyAdapter`.
+///
+/// `DerivativeTokens` maps `(class_id, asset_instance_index)` to the local
+/// token id of each derivative token. Declare it as a `StorageDoubleMap` with
+/// an `OptionQuery` in one of the runtime's pallets, so that it is part of
+/// the metadata and migrated with the pallet.
+pub struct NonFungibleAdapter<
+	Runtime,
+	ClassIdConvert,
+	AccountIdConvert,
+	CheckingAccount,
+	SelfLocation,
+	DerivativeTokens,
+>(
+	PhantomData<(
+		Runtime,
+		ClassIdConvert,
+		AccountIdConvert,
+		CheckingAccount,
+		SelfLocation,
+		DerivativeTokens,
+	)>,
+);
+
+impl<Runtime, ClassIdConvert, AccountIdConvert, CheckingAccount, SelfLocation, DerivativeTokens>
+	NonFungibleAdapter<Runtime, ClassIdConvert, AccountIdConvert, CheckingAccount, SelfLocation, DerivativeTokens>
+where
+	Runtime: orml_nft::Config,
+	ClassIdConvert: Convert<Location, Option<Runtime::ClassId>>,
+	AccountIdConvert: ConvertLocation<Runtime::AccountId>,
+	SelfLocation: Get<Location>,
+	DerivativeTokens: StorageDoubleMap<Runtime::ClassId, u128, Runtime::TokenId, Query = Option<Runtime::TokenId>>,
+{
+	/// Convert `asset` into its class id and instance index.
+	fn match_instance(asset: &Asset) -> result::Result<(Runtime::ClassId, u128), XcmError> {
+		let NonFungible(instance) = &asset.fun else {
+			return Err(XcmError::AssetNotFound);
+		};
+		let class_id = ClassIdConvert::convert(asset.id.0.clone()).ok_or(XcmError::AssetNotFound)?;
+		match instance {
+			AssetInstance::Index(index) => Ok((class_id, *index)),
+			_ => Err(Error::UnsupportedAssetInstance.into()),
+		}
+	}
+
+	/// Whether `asset` is a derivative of a token on another chain.
+	fn is_derivative(asset: &Asset) -> bool {
+		let AssetId(location) = &asset.id;
+		location.parents > 0 && !location.starts_with(&SelfLocation::get())
+	}
+
+	/// Convert `asset` into the `(ClassId, TokenId)` of an existing `orml_nft`
+	/// token.
+	fn match_token(asset: &Asset) -> result::Result<(Runtime::ClassId, Runtime::TokenId), XcmError> {
+		let (class_id, index) = Self::match_instance(asset)?;
+		let token_id = if Self::is_derivative(asset) {
+			DerivativeTokens::get(class_id, index)
+		} else {
+			index.checked_into()
+		}
+		.ok_or(XcmError::AssetNotFound)?;
+		Ok((class_id, token_id))
+	}
+
+	fn account_of(location: &Location) -> result::Result<Runtime::AccountId, XcmError> {
+		AccountIdConvert::convert_location(location).ok_or_else(|| XcmError::from(Error::AccountIdConversionFailed))
+	}
+
+	fn transfer(
+		from: &Runtime::AccountId,
+		to: &Runtime::AccountId,
+		token: (Runtime::ClassId, Runtime::TokenId),
+	) -> XcmResult {
+		orml_nft::Pallet::<Runtime>::transfer(from, to, token).map_err(|e| XcmError::FailedToTransactAsset(e.into()))
+	}
+}
+
+impl<Runtime, ClassIdConvert, AccountIdConvert, CheckingAccount, SelfLocation, DerivativeTokens> TransactAsset
+	for NonFungibleAdapter<Runtime, ClassIdConvert, AccountIdConvert, CheckingAccount, SelfLocation, DerivativeTokens>
+where
+	Runtime: orml_nft::Config,
+	ClassIdConvert: Convert<Location, Option<Runtime::ClassId>>,
+	AccountIdConvert: ConvertLocation<Runtime::AccountId>,
+	CheckingAccount: Get<Runtime::AccountId>,
+	SelfLocation: Get<Location>,
+	DerivativeTokens: StorageDoubleMap<Runtime::ClassId, u128, Runtime::TokenId, Query = Option<Runtime::TokenId>>,
+	Runtime::TokenData: Default,
+{
+	fn deposit_asset(asset: &Asset, location: &Location, _context: Option<&XcmContext>) -> XcmResult {
+		let (class_id, index) = Self::match_instance(asset)?;
+		let who = Self::account_of(location)?;
+
+		if !Self::is_derivative(asset) {
+			let token_id = index.checked_into().ok_or(XcmError::AssetNotFound)?;
+			return Self::transfer(&CheckingAccount::get(), &who, (class_id, token_id));
+		}
+
+		// an instance is minted at most once, until it is burned on withdrawal
+		if DerivativeTokens::contains_key(class_id, index) {
+			return Err(XcmError::FailedToTransactAsset("DerivativeAlreadyExists"));
+		}
+		let token_id = orml_nft::Pallet::<Runtime>::mint(&who, class_id, Vec::new(), Default::default())
+			.map_err(|e| XcmError::FailedToTransactAsset(e.into()))?;
+		DerivativeTokens::insert(class_id, index, token_id);
+		Ok(())
+	}
+
+	fn withdraw_asset(
+		asset: &Asset,
+		location: &Location,
+		_maybe_context: Option<&XcmContext>,
+	) -> result::Result<AssetsInHolding, XcmError> {
+		let token = Self::match_token(asset)?;
+		let who = Self::account_of(location)?;
+
+		if Self::is_derivative(asset) {
+			orml_nft::Pallet::<Runtime>::burn(&who, token).map_err(|e| XcmError::FailedToTransactAsset(e.into()))?;
+			let (class_id, index) = Self::match_instance(asset)?;
+			DerivativeTokens::remove(class_id, index);
+		} else {
+			Self::transfer(&who, &CheckingAccount::get(), token)?;
+		}
+
+		Ok(asset.clone().into())
+	}
+
+	fn transfer_asset(
+		asset: &Asset,
+		from: &Location,
+		to: &Location,
+		_context: &XcmContext,
+	) -> result::Result<AssetsInHolding, XcmError> {
+		let token = Self::match_token(asset)?;
+		let from_account = Self::account_of(from)?;
+		let to_account = Self::account_of(to)?;
+		Self::transfer(&from_account, &to_account, token)?;
+
+		Ok(asset.clone().into())
+	}
+}
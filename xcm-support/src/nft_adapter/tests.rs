@@ -0,0 +1,235 @@
+//! Unit tests for the non-fungible adapter.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::assert_ok;
+use mock::*;
+
+fn account_location(who: AccountId) -> Location {
+	Junction::AccountId32 {
+		network: None,
+		id: who.into(),
+	}
+	.into()
+}
+
+fn nft(class_id: ClassId, token_id: TokenId) -> Asset {
+	(class_location(class_id), AssetInstance::Index(token_id.into())).into()
+}
+
+fn mint_to(who: &AccountId) -> (ClassId, TokenId) {
+	let class_id = Nft::create_class(&ALICE, vec![], ()).unwrap();
+	let token_id = Nft::mint(who, class_id, vec![], ()).unwrap();
+	(class_id, token_id)
+}
+
+#[test]
+fn withdraw_and_deposit_works() {
+	ExtBuilder.build().execute_with(|| {
+		let (class_id, token_id) = mint_to(&ALICE);
+
+		assert_eq!(
+			NftTransactor::withdraw_asset(&nft(class_id, token_id), &account_location(BOB), None).map(|_| ()),
+			Err(XcmError::FailedToTransactAsset("NoPermission"))
+		);
+
+		assert_ok!(NftTransactor::withdraw_asset(
+			&nft(class_id, token_id),
+			&account_location(ALICE),
+			None
+		));
+		assert!(Nft::is_owner(&CheckingAccount::get(), (class_id, token_id)));
+
+		assert_ok!(NftTransactor::deposit_asset(
+			&nft(class_id, token_id),
+			&account_location(BOB),
+			None
+		));
+		assert!(Nft::is_owner(&BOB, (class_id, token_id)));
+		assert!(!Nft::is_owner(&CheckingAccount::get(), (class_id, token_id)));
+
+		// the token is not in transit
+		assert_eq!(
+			NftTransactor::deposit_asset(&nft(class_id, token_id), &account_location(ALICE), None),
+			Err(XcmError::FailedToTransactAsset("NoPermission"))
+		);
+		assert_eq!(
+			NftTransactor::deposit_asset(&nft(class_id, token_id + 1), &account_location(ALICE), None),
+			Err(XcmError::FailedToTransactAsset("TokenNotFound"))
+		);
+	});
+}
+
+fn derivative(index: u128) -> Asset {
+	(sibling_class_location(), AssetInstance::Index(index)).into()
+}
+
+#[test]
+fn derivatives_are_minted_and_burned() {
+	ExtBuilder.build().execute_with(|| {
+		// the sibling instance index is not the local token id
+		assert_ok!(NftTransactor::deposit_asset(
+			&derivative(42),
+			&account_location(ALICE),
+			None
+		));
+		let token_id = DerivativeTokens::get(DERIVATIVE_CLASS, 42).unwrap();
+		assert_eq!(token_id, 0);
+		assert!(Nft::is_owner(&ALICE, (DERIVATIVE_CLASS, token_id)));
+
+		assert_eq!(
+			NftTransactor::deposit_asset(&derivative(42), &account_location(BOB), None),
+			Err(XcmError::FailedToTransactAsset("DerivativeAlreadyExists"))
+		);
+
+		assert_ok!(NftTransactor::transfer_asset(
+			&derivative(42),
+			&account_location(ALICE),
+			&account_location(BOB),
+			&XcmContext::with_message_id([0; 32]),
+		));
+		assert!(Nft::is_owner(&BOB, (DERIVATIVE_CLASS, token_id)));
+
+		assert_eq!(
+			NftTransactor::withdraw_asset(&derivative(42), &account_location(ALICE), None).map(|_| ()),
+			Err(XcmError::FailedToTransactAsset("NoPermission"))
+		);
+		assert_ok!(NftTransactor::withdraw_asset(
+			&derivative(42),
+			&account_location(BOB),
+			None
+		));
+		assert!(Nft::tokens(DERIVATIVE_CLASS, token_id).is_none());
+		assert!(!DerivativeTokens::contains_key(DERIVATIVE_CLASS, 42));
+		assert_eq!(
+			NftTransactor::withdraw_asset(&derivative(42), &account_location(BOB), None).map(|_| ()),
+			Err(XcmError::AssetNotFound)
+		);
+
+		// deposited again, with a new token id
+		assert_ok!(NftTransactor::deposit_asset(
+			&derivative(42),
+			&account_location(ALICE),
+			None
+		));
+		assert_eq!(DerivativeTokens::get(DERIVATIVE_CLASS, 42), Some(1));
+	});
+}
+
+#[test]
+fn absolute_local_class_is_not_derivative() {
+	ExtBuilder.build().execute_with(|| {
+		let (class_id, token_id) = mint_to(&ALICE);
+		let absolute: Asset = (
+			Location::new(1, [Parachain(2000), PalletInstance(1), GeneralIndex(class_id.into())]),
+			AssetInstance::Index(token_id.into()),
+		)
+			.into();
+
+		assert_ok!(NftTransactor::withdraw_asset(&absolute, &account_location(ALICE), None));
+		assert!(Nft::is_owner(&CheckingAccount::get(), (class_id, token_id)));
+		assert_ok!(NftTransactor::deposit_asset(&absolute, &account_location(BOB), None));
+		assert!(Nft::is_owner(&BOB, (class_id, token_id)));
+		assert!(!DerivativeTokens::contains_key(class_id, u128::from(token_id)));
+	});
+}
+
+#[test]
+fn transfer_asset_works() {
+	ExtBuilder.build().execute_with(|| {
+		let (class_id, token_id) = mint_to(&ALICE);
+		let sibling_sovereign = AccountId::new([3u8; 32]);
+
+		assert_ok!(NftTransactor::transfer_asset(
+			&nft(class_id, token_id),
+			&account_location(ALICE),
+			&account_location(sibling_sovereign.clone()),
+			&XcmContext::with_message_id([0; 32]),
+		));
+		assert!(Nft::is_owner(&sibling_sovereign, (class_id, token_id)));
+		assert!(!Nft::is_owner(&ALICE, (class_id, token_id)));
+
+		assert_eq!(
+			NftTransactor::transfer_asset(
+				&nft(class_id, token_id),
+				&account_location(ALICE),
+				&Location::parent(),
+				&XcmContext::with_message_id([0; 32]),
+			)
+			.map(|_| ()),
+			Err(XcmError::FailedToTransactAsset("AccountIdConversionFailed"))
+		);
+	});
+}
+
+#[test]
+fn unmatched_assets_are_not_found() {
+	ExtBuilder.build().execute_with(|| {
+		let (class_id, _) = mint_to(&ALICE);
+
+		// fungible
+		assert_eq!(
+			NftTransactor::deposit_asset(
+				&(class_location(class_id), 1u128).into(),
+				&account_location(ALICE),
+				None
+			),
+			Err(XcmError::AssetNotFound)
+		);
+		// unknown class
+		assert_eq!(
+			NftTransactor::deposit_asset(
+				&(Location::parent(), AssetInstance::Index(0)).into(),
+				&account_location(ALICE),
+				None
+			),
+			Err(XcmError::AssetNotFound)
+		);
+		// unknown class of an unsupported instance
+		assert_eq!(
+			NftTransactor::deposit_asset(
+				&(Location::parent(), AssetInstance::Array4([0; 4])).into(),
+				&account_location(ALICE),
+				None
+			),
+			Err(XcmError::AssetNotFound)
+		);
+		// token id overflow
+		assert_eq!(
+			NftTransactor::withdraw_asset(
+				&(class_location(class_id), AssetInstance::Index(u128::MAX)).into(),
+				&account_location(ALICE),
+				None
+			)
+			.map(|_| ()),
+			Err(XcmError::AssetNotFound)
+		);
+	});
+}
+
+#[test]
+fn unsupported_asset_instances_fail() {
+	ExtBuilder.build().execute_with(|| {
+		let (class_id, _) = mint_to(&ALICE);
+		let unsupported = XcmError::FailedToTransactAsset("UnsupportedAssetInstance");
+
+		for instance in [AssetInstance::Undefined, AssetInstance::Array4([0; 4])] {
+			let asset: Asset = (class_location(class_id), instance).into();
+			assert_eq!(
+				NftTransactor::deposit_asset(&asset, &account_location(ALICE), None),
+				Err(unsupported)
+			);
+			assert_eq!(
+				NftTransactor::withdraw_asset(&asset, &account_location(ALICE), None).map(|_| ()),
+				Err(unsupported)
+			);
+		}
+
+		let derivative: Asset = (sibling_class_location(), AssetInstance::Array8([0; 8])).into();
+		assert_eq!(
+			NftTransactor::deposit_asset(&derivative, &account_location(ALICE), None),
+			Err(unsupported)
+		);
+	});
+}
@@ -0,0 +1,163 @@
+pub use crate::*;
+
+use frame_benchmarking::v2::*;
+
+/// Helper trait for benchmarking.
+pub trait BenchmarkHelper {
+	/// Returns a destination which is reachable from the runtime, and an asset
+	/// to pay remote execution on it.
+	fn reachable_dest_and_fee() -> Option<(Location, Asset)>;
+}
+
+impl BenchmarkHelper for () {
+	fn reachable_dest_and_fee() -> Option<(Location, Asset)> {
+		None
+	}
+}
+
+/// The maximum size of the benchmarked messages and calls.
+const MAX_MESSAGE_SIZE: u32 = 64 * 1024;
+
+/// A message of about `size` encoded bytes.
+fn message(size: u32) -> Xcm<()> {
+	Xcm(vec![Transact {
+		origin_kind: OriginKind::SovereignAccount,
+		fallback_max_weight: None,
+		call: vec![0u8; size as usize].into(),
+	}])
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn send_as_sovereign(n: Linear<0, MAX_MESSAGE_SIZE>) -> Result<(), BenchmarkError> {
+		let (dest, _) = T::BenchmarkHelper::reachable_dest_and_fee().ok_or(BenchmarkError::Skip)?;
+		let origin = T::SovereignOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			Box::new(dest.into()),
+			Box::new(VersionedXcm::from(message(n))),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn send_as_sovereign_with_fees(n: Linear<0, MAX_MESSAGE_SIZE>) -> Result<(), BenchmarkError> {
+		let (dest, fee) = T::BenchmarkHelper::reachable_dest_and_fee().ok_or(BenchmarkError::Skip)?;
+		let origin = T::SovereignOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			Box::new(dest.into()),
+			Box::new(VersionedXcm::from(message(n))),
+			Box::new(fee.into()),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn send_as_sovereign_with_response(n: Linear<0, MAX_MESSAGE_SIZE>) -> Result<(), BenchmarkError> {
+		let (dest, fee) = T::BenchmarkHelper::reachable_dest_and_fee().ok_or(BenchmarkError::Skip)?;
+		let origin = T::SovereignOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			Box::new(dest.into()),
+			Box::new(VersionedXcm::from(message(n))),
+			Some(Box::new(fee.into())),
+			10u32.into(),
+		);
+
+		assert_eq!(Queries::<T>::iter().count(), 1);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn note_query_response() -> Result<(), BenchmarkError> {
+		let origin = T::ResponseOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let responder = T::ResponseOrigin::ensure_origin(origin.clone()).map_err(|_| BenchmarkError::Weightless)?;
+		let query_id: QueryId = 0;
+		Queries::<T>::insert(
+			query_id,
+			QueryRecord {
+				dest: responder,
+				sent_at: frame_system::Pallet::<T>::block_number(),
+				response: None,
+			},
+		);
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			query_id,
+			Box::new(Response::ExecutionResult(None)),
+		);
+
+		assert!(Queries::<T>::get(query_id).and_then(|record| record.response).is_some());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_query() -> Result<(), BenchmarkError> {
+		let origin = T::SovereignOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let query_id: QueryId = 0;
+		Queries::<T>::insert(
+			query_id,
+			QueryRecord {
+				dest: Location::parent(),
+				sent_at: frame_system::Pallet::<T>::block_number(),
+				response: None,
+			},
+		);
+
+		#[extrinsic_call]
+		_(origin as <T as frame_system::Config>::RuntimeOrigin, query_id);
+
+		assert!(!Queries::<T>::contains_key(query_id));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_transact_destination() -> Result<(), BenchmarkError> {
+		let (dest, fee) = T::BenchmarkHelper::reachable_dest_and_fee().ok_or(BenchmarkError::Skip)?;
+		let origin = T::SovereignOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			Box::new(dest.clone().into()),
+			Some(Box::new(fee.into())),
+		);
+
+		assert!(TransactDestinations::<T>::contains_key(dest));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn send_transact_as_sovereign(n: Linear<0, MAX_MESSAGE_SIZE>) -> Result<(), BenchmarkError> {
+		let (dest, fee) = T::BenchmarkHelper::reachable_dest_and_fee().ok_or(BenchmarkError::Skip)?;
+		let origin = T::SovereignOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		TransactDestinations::<T>::insert(&dest, fee);
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			Box::new(dest.into()),
+			vec![0u8; n as usize],
+			Weight::from_parts(1_000_000_000, 64 * 1024),
+			OriginKind::SovereignAccount,
+		);
+
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Runtime);
+}
@@ -1,25 +1,85 @@
 //! # Xcm Module
+//!
+//! ## Overview
+//!
+//! The xcm module provides functions for sending XCM messages as parachain
+//! sovereign.
+//!
+//! - `send_as_sovereign`: send a message as it is.
+//! - `send_as_sovereign_with_fees`: pay remote execution from the sovereign
+//!   account on destination, by wrapping the message with `WithdrawAsset` and
+//!   `BuyExecution`. The surplus fee is refunded to the sovereign account.
+//! - `send_as_sovereign_with_response`: track the outcome of the message.
+//!   Destination reports errors by `QueryResponse`, which is recorded in
+//!   `Queries` for governance to inspect.
+//! - `send_transact_as_sovereign`: dispatch a call on destination, with the
+//!   origin descended to `TransactDescendOrigin`. Only destinations allowed by
+//!   `set_transact_destination` are supported, which also sets the fee asset.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::large_enum_variant)]
 
 use frame_support::{pallet_prelude::*, traits::EnsureOrigin};
 use frame_system::pallet_prelude::*;
-use sp_std::boxed::Box;
-use xcm::{v5::prelude::*, VersionedLocation, VersionedXcm};
+use sp_runtime::traits::Saturating;
+use sp_std::vec::Vec;
+use sp_std::{boxed::Box, vec};
+use xcm::{v5::prelude::*, VersionedAsset, VersionedLocation, VersionedXcm};
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod mock;
+mod tests;
+mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub use benchmarking::BenchmarkHelper;
 pub use module::*;
+pub use weights::WeightInfo;
 
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
 
+	/// A query of a sovereign XCM message.
+	#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct QueryRecord<BlockNumber> {
+		/// The destination of the message, which is expected to respond.
+		pub dest: Location,
+		/// The block number the message was sent.
+		pub sent_at: BlockNumber,
+		/// The response and the block number it was received, if any.
+		pub response: Option<(Response, BlockNumber)>,
+	}
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config + pallet_xcm::Config {
 		/// The required origin for sending XCM as parachain sovereign.
 		///
 		/// Typically root or the majority of collective.
 		type SovereignOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+
+		/// The origin of query responses, which is notified by `pallet_xcm`.
+		///
+		/// Typically `pallet_xcm::EnsureResponse<Everything>`.
+		type ResponseOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin, Success = Location>;
+
+		/// The interior location to descend to before `Transact`, which
+		/// identifies the governance body on destinations. `Here` to transact
+		/// as the sovereign account.
+		///
+		/// Remote execution of `Transact` is paid by the account of this
+		/// location on destinations.
+		type TransactDescendOrigin: Get<InteriorLocation>;
+
+		/// The overarching call type, to be notified of query responses.
+		type RuntimeCall: From<Call<Self>> + Into<<Self as pallet_xcm::Config>::RuntimeCall>;
+
+		/// Weight information for extrinsics in this module.
+		type WeightInfo: WeightInfo;
+
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper;
 	}
 
 	#[pallet::pallet]
@@ -30,6 +90,22 @@ pub mod module {
 	pub enum Event<T: Config> {
 		/// XCM message sent. \[to, message\]
 		Sent { to: Location, message: Xcm<()> },
+		/// A response of the message sent is expected. \[to, query_id\]
+		ResponseExpected { to: Location, query_id: QueryId },
+		/// A query response was received. \[query_id, response\]
+		ResponseReceived { query_id: QueryId, response: Response },
+		/// A query was removed. \[query_id\]
+		QueryRemoved { query_id: QueryId },
+		/// Transact destination updated. `None` fee means it was disallowed.
+		/// \[dest, fee\]
+		TransactDestinationUpdated { dest: Location, fee: Option<Asset> },
+		/// A call was sent to be dispatched on destination. \[to,
+		/// origin_kind, fallback_max_weight\]
+		TransactSent {
+			to: Location,
+			origin_kind: OriginKind,
+			fallback_max_weight: Weight,
+		},
 	}
 
 	#[pallet::error]
@@ -43,14 +119,36 @@ pub mod module {
 		/// The version of the `Versioned` value used is not able to be
 		/// interpreted.
 		BadVersion,
+		/// The location of this chain can't be inverted from destination.
+		InvalidDest,
+		/// The query doesn't exist.
+		QueryNotFound,
+		/// The query response is not from the destination of the message.
+		UnexpectedResponder,
+		/// The destination is not allowed to send `Transact` to.
+		TransactDestinationNotAllowed,
 	}
 
+	/// Queries of sovereign XCM messages, and their responses if received.
+	///
+	/// map: query_id => QueryRecord
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type Queries<T: Config> = StorageMap<_, Twox64Concat, QueryId, QueryRecord<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Destinations allowed to send `Transact` to, and the fee asset to pay
+	/// remote execution, relative to the destination.
+	///
+	/// map: dest => fee
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type TransactDestinations<T: Config> = StorageMap<_, Blake2_128Concat, Location, Asset, OptionQuery>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Send an XCM message as parachain sovereign.
 		#[pallet::call_index(0)]
-		// FIXME: Benchmark send
-		#[pallet::weight(Weight::from_parts(100_000_000, 0))]
+		#[pallet::weight(<T as Config>::WeightInfo::send_as_sovereign(message.encoded_size() as u32))]
 		pub fn send_as_sovereign(
 			origin: OriginFor<T>,
 			dest: Box<VersionedLocation>,
@@ -60,12 +158,228 @@ pub mod module {
 			let dest = Location::try_from(*dest).map_err(|()| Error::<T>::BadVersion)?;
 			let message: Xcm<()> = (*message).try_into().map_err(|()| Error::<T>::BadVersion)?;
 
-			pallet_xcm::Pallet::<T>::send_xcm(Here, dest.clone(), message.clone()).map_err(|e| match e {
-				SendError::Unroutable => Error::<T>::Unreachable,
-				_ => Error::<T>::SendFailure,
+			Self::do_send(dest, message)
+		}
+
+		/// Send an XCM message as parachain sovereign, paying remote execution
+		/// with `fee` withdrawn from the sovereign account on destination.
+		///
+		/// `fee` is relative to destination. The surplus fee is deposited back
+		/// to the sovereign account after execution.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::send_as_sovereign_with_fees(message.encoded_size() as u32))]
+		pub fn send_as_sovereign_with_fees(
+			origin: OriginFor<T>,
+			dest: Box<VersionedLocation>,
+			message: Box<VersionedXcm<()>>,
+			fee: Box<VersionedAsset>,
+		) -> DispatchResult {
+			let _ = T::SovereignOrigin::ensure_origin(origin)?;
+			let dest = Location::try_from(*dest).map_err(|()| Error::<T>::BadVersion)?;
+			let message: Xcm<()> = (*message).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let fee: Asset = (*fee).try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			let message = Self::wrap_message(&dest, Here, message, Some(fee), None)?;
+			Self::do_send(dest, message)
+		}
+
+		/// Send an XCM message as parachain sovereign, and track its outcome.
+		///
+		/// Destination reports the outcome by `QueryResponse`, which is
+		/// recorded in `Queries`. The query expires after `timeout` blocks.
+		/// Remote execution is paid with `fee` if given, same as
+		/// `send_as_sovereign_with_fees`.
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as Config>::WeightInfo::send_as_sovereign_with_response(message.encoded_size() as u32))]
+		pub fn send_as_sovereign_with_response(
+			origin: OriginFor<T>,
+			dest: Box<VersionedLocation>,
+			message: Box<VersionedXcm<()>>,
+			fee: Option<Box<VersionedAsset>>,
+			timeout: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let _ = T::SovereignOrigin::ensure_origin(origin)?;
+			let dest = Location::try_from(*dest).map_err(|()| Error::<T>::BadVersion)?;
+			let message: Xcm<()> = (*message).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let fee: Option<Asset> = fee
+				.map(|fee| (*fee).try_into().map_err(|()| Error::<T>::BadVersion))
+				.transpose()?;
+
+			let notify = <T as Config>::RuntimeCall::from(Call::<T>::note_query_response {
+				query_id: Default::default(),
+				response: Box::new(Response::Null),
+			});
+			let now = frame_system::Pallet::<T>::block_number();
+			let query_id =
+				pallet_xcm::Pallet::<T>::new_notify_query(dest.clone(), notify, now.saturating_add(timeout), Here);
+			let response_info = QueryResponseInfo {
+				destination: Self::self_location(&dest)?,
+				query_id,
+				max_weight: <T as Config>::WeightInfo::note_query_response(),
+			};
+
+			let message = Self::wrap_message(&dest, Here, message, fee, Some(response_info))?;
+			Self::do_send(dest.clone(), message)?;
+
+			Queries::<T>::insert(
+				query_id,
+				QueryRecord {
+					dest: dest.clone(),
+					sent_at: now,
+					response: None,
+				},
+			);
+			Self::deposit_event(Event::ResponseExpected { to: dest, query_id });
+			Ok(())
+		}
+
+		/// Record the response of a query.
+		///
+		/// The dispatch origin for this call must be `ResponseOrigin`. It's
+		/// dispatched by `pallet_xcm` on receiving query responses.
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as Config>::WeightInfo::note_query_response())]
+		pub fn note_query_response(origin: OriginFor<T>, query_id: QueryId, response: Box<Response>) -> DispatchResult {
+			let responder = T::ResponseOrigin::ensure_origin(origin)?;
+
+			Queries::<T>::try_mutate(query_id, |maybe_record| -> DispatchResult {
+				let record = maybe_record.as_mut().ok_or(Error::<T>::QueryNotFound)?;
+				ensure!(record.dest == responder, Error::<T>::UnexpectedResponder);
+				record.response = Some((*response.clone(), frame_system::Pallet::<T>::block_number()));
+				Ok(())
 			})?;
-			Self::deposit_event(Event::Sent { to: dest, message });
+
+			Self::deposit_event(Event::ResponseReceived {
+				query_id,
+				response: *response,
+			});
+			Ok(())
+		}
+
+		/// Remove a query and its response once inspected.
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as Config>::WeightInfo::remove_query())]
+		pub fn remove_query(origin: OriginFor<T>, query_id: QueryId) -> DispatchResult {
+			let _ = T::SovereignOrigin::ensure_origin(origin)?;
+			Queries::<T>::take(query_id).ok_or(Error::<T>::QueryNotFound)?;
+
+			Self::deposit_event(Event::QueryRemoved { query_id });
+			Ok(())
+		}
+
+		/// Allow `dest` to send `Transact` to, paying remote execution with
+		/// `fee`, or disallow it if `fee` is `None`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_transact_destination())]
+		pub fn set_transact_destination(
+			origin: OriginFor<T>,
+			dest: Box<VersionedLocation>,
+			fee: Option<Box<VersionedAsset>>,
+		) -> DispatchResult {
+			let _ = T::SovereignOrigin::ensure_origin(origin)?;
+			let dest = Location::try_from(*dest).map_err(|()| Error::<T>::BadVersion)?;
+			let fee: Option<Asset> = fee
+				.map(|fee| (*fee).try_into().map_err(|()| Error::<T>::BadVersion))
+				.transpose()?;
+
+			TransactDestinations::<T>::set(&dest, fee.clone());
+			Self::deposit_event(Event::TransactDestinationUpdated { dest, fee });
+			Ok(())
+		}
+
+		/// Send `call` to be dispatched on `dest` with `origin_kind`.
+		///
+		/// The message descends origin to `TransactDescendOrigin`, buys
+		/// execution with the fee of the destination, transacts `call` and
+		/// refunds the surplus fee.
+		#[pallet::call_index(6)]
+		#[pallet::weight(<T as Config>::WeightInfo::send_transact_as_sovereign(call.len() as u32))]
+		pub fn send_transact_as_sovereign(
+			origin: OriginFor<T>,
+			dest: Box<VersionedLocation>,
+			call: Vec<u8>,
+			fallback_max_weight: Weight,
+			origin_kind: OriginKind,
+		) -> DispatchResult {
+			let _ = T::SovereignOrigin::ensure_origin(origin)?;
+			let dest = Location::try_from(*dest).map_err(|()| Error::<T>::BadVersion)?;
+			let fee = TransactDestinations::<T>::get(&dest).ok_or(Error::<T>::TransactDestinationNotAllowed)?;
+
+			let transact = Xcm(vec![Transact {
+				origin_kind,
+				fallback_max_weight: Some(fallback_max_weight),
+				call: call.into(),
+			}]);
+			let message = Self::wrap_message(&dest, T::TransactDescendOrigin::get(), transact, Some(fee), None)?;
+			Self::do_send(dest.clone(), message)?;
+
+			Self::deposit_event(Event::TransactSent {
+				to: dest,
+				origin_kind,
+				fallback_max_weight,
+			});
 			Ok(())
 		}
 	}
 }
+
+impl<T: Config> Pallet<T> {
+	fn do_send(dest: Location, message: Xcm<()>) -> DispatchResult {
+		pallet_xcm::Pallet::<T>::send_xcm(Here, dest.clone(), message.clone()).map_err(|e| match e {
+			SendError::Unroutable => Error::<T>::Unreachable,
+			_ => Error::<T>::SendFailure,
+		})?;
+		Self::deposit_event(Event::Sent { to: dest, message });
+		Ok(())
+	}
+
+	/// The location of this chain, relative to `dest`.
+	fn self_location(dest: &Location) -> Result<Location, DispatchError> {
+		<T as pallet_xcm::Config>::UniversalLocation::get()
+			.invert_target(dest)
+			.map_err(|()| Error::<T>::InvalidDest.into())
+	}
+
+	/// Wrap `message` with origin descending to `interior` if it's not `Here`,
+	/// with fee payment if `fee` is given, and with error reporting if
+	/// `response_info` is given.
+	fn wrap_message(
+		dest: &Location,
+		interior: InteriorLocation,
+		message: Xcm<()>,
+		fee: Option<Asset>,
+		response_info: Option<QueryResponseInfo>,
+	) -> Result<Xcm<()>, DispatchError> {
+		let mut appendix = vec![];
+		if let Some(response_info) = response_info {
+			appendix.push(ReportError(response_info));
+		}
+
+		let mut instructions = vec![];
+		if interior != Here {
+			instructions.push(DescendOrigin(interior.clone()));
+		}
+		if let Some(fee) = fee {
+			// refund to the descended origin, which pays the fee
+			let beneficiary = Self::self_location(dest)?
+				.appended_with(interior)
+				.map_err(|_| Error::<T>::InvalidDest)?;
+			appendix.push(RefundSurplus);
+			appendix.push(DepositAsset {
+				assets: Wild(AllCounted(1)),
+				beneficiary,
+			});
+			instructions.push(WithdrawAsset(fee.clone().into()));
+			instructions.push(BuyExecution {
+				fees: fee,
+				weight_limit: Unlimited,
+			});
+		}
+
+		if !appendix.is_empty() {
+			instructions.push(SetAppendix(Xcm(appendix)));
+		}
+		instructions.extend(message.0);
+		Ok(Xcm(instructions))
+	}
+}
@@ -0,0 +1,191 @@
+//! Mocks for the xcm module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types,
+	storage::unhashed,
+	traits::{ConstU32, ConstU64, Everything, Nothing},
+};
+use frame_system::EnsureRoot;
+use sp_runtime::{traits::IdentityLookup, BuildStorage};
+use sp_std::cell::RefCell;
+use xcm_builder::{EnsureXcmOrigin, FixedWeightBounds};
+use xcm_executor::XcmExecutor;
+
+use crate as orml_xcm;
+
+pub type AccountId = u64;
+pub type Balance = u64;
+
+pub const ALICE: AccountId = 1;
+pub const PARA_ID: u32 = 2000;
+pub const SIBLING_ID: u32 = 1000;
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+}
+
+thread_local! {
+	pub static SENT_XCM: RefCell<Vec<(Location, Xcm<()>)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The messages sent, and clear them.
+pub fn take_sent_xcm() -> Vec<(Location, Xcm<()>)> {
+	SENT_XCM.with(|q| q.take())
+}
+
+/// The keys of the queue written by `TestSendXcm`.
+const QUEUE_STATUS: &[u8] = b":orml_xcm_mock:queue_status";
+const QUEUE_PAGE: &[u8] = b":orml_xcm_mock:queue_page";
+
+/// Routes messages to the relay chain and siblings, by recording them. Like
+/// the XCMP queue, it also reads the queue status, writes the message to a
+/// page and updates the status, so benchmarks weigh routers writing to
+/// storage.
+pub struct TestSendXcm;
+impl SendXcm for TestSendXcm {
+	type Ticket = (Location, Xcm<()>);
+
+	fn validate(dest: &mut Option<Location>, msg: &mut Option<Xcm<()>>) -> SendResult<Self::Ticket> {
+		let dest = dest.take().ok_or(SendError::MissingArgument)?;
+		if dest.parents != 1 {
+			return Err(SendError::Unroutable);
+		}
+		let msg = msg.take().ok_or(SendError::MissingArgument)?;
+		Ok(((dest, msg), Assets::new()))
+	}
+
+	fn deliver((dest, msg): Self::Ticket) -> Result<XcmHash, SendError> {
+		let hash = msg.using_encoded(sp_io::hashing::blake2_256);
+		let pages: u32 = unhashed::get_or_default(QUEUE_STATUS);
+		unhashed::put(&(QUEUE_PAGE, pages).encode(), &(&dest, &msg));
+		unhashed::put(QUEUE_STATUS, &pages.saturating_add(1));
+		SENT_XCM.with(|q| q.borrow_mut().push((dest, msg)));
+		Ok(hash)
+	}
+}
+
+parameter_types! {
+	pub UniversalLocation: InteriorLocation = [GlobalConsensus(NetworkId::Kusama), Parachain(PARA_ID)].into();
+	pub const UnitWeightCost: Weight = Weight::from_parts(10, 10);
+	pub const MaxInstructions: u32 = 100;
+	pub const MaxAssetsIntoHolding: u32 = 64;
+	pub TransactDescendOrigin: InteriorLocation = Plurality { id: BodyId::Technical, part: BodyPart::Voice }.into();
+}
+
+pub struct XcmConfig;
+impl xcm_executor::Config for XcmConfig {
+	type RuntimeCall = RuntimeCall;
+	type XcmSender = TestSendXcm;
+	type AssetTransactor = ();
+	type OriginConverter = ();
+	type IsReserve = ();
+	type IsTeleporter = ();
+	type UniversalLocation = UniversalLocation;
+	type Barrier = ();
+	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+	type Trader = ();
+	type ResponseHandler = PolkadotXcm;
+	type AssetTrap = ();
+	type AssetClaims = ();
+	type SubscriptionService = PolkadotXcm;
+	type AssetLocker = ();
+	type AssetExchanger = ();
+	type PalletInstancesInfo = ();
+	type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
+	type FeeManager = ();
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
+	type CallDispatcher = RuntimeCall;
+	type SafeCallFilter = Everything;
+	type Aliasers = ();
+	type TransactionalProcessor = ();
+	type HrmpNewChannelOpenRequestHandler = ();
+	type HrmpChannelAcceptedHandler = ();
+	type HrmpChannelClosingHandler = ();
+	type XcmRecorder = ();
+	type XcmEventEmitter = ();
+}
+
+impl pallet_xcm::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type SendXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, ()>;
+	type XcmRouter = TestSendXcm;
+	type ExecuteXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, ()>;
+	type XcmExecuteFilter = Nothing;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type XcmTeleportFilter = Nothing;
+	type XcmReserveTransferFilter = Nothing;
+	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+	type UniversalLocation = UniversalLocation;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+	type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+	type Currency = Balances;
+	type CurrencyMatcher = ();
+	type TrustedLockers = ();
+	type SovereignAccountOf = ();
+	type MaxLockers = ConstU32<8>;
+	type WeightInfo = pallet_xcm::TestWeightInfo;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type MaxRemoteLockConsumers = ConstU32<0>;
+	type RemoteLockConsumerIdentifier = ();
+	type AuthorizedAliasConsideration = ();
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper for MockBenchmarkHelper {
+	fn reachable_dest_and_fee() -> Option<(Location, Asset)> {
+		Some((Location::parent(), (Parent, 1_000).into()))
+	}
+}
+
+impl Config for Runtime {
+	type SovereignOrigin = EnsureRoot<AccountId>;
+	type ResponseOrigin = pallet_xcm::EnsureResponse<Everything>;
+	type TransactDescendOrigin = TransactDescendOrigin;
+	type RuntimeCall = RuntimeCall;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = MockBenchmarkHelper;
+}
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Balances: pallet_balances,
+		PolkadotXcm: pallet_xcm,
+		OrmlXcm: orml_xcm,
+	}
+);
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<Runtime>::default()
+		.build_storage()
+		.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		take_sent_xcm();
+	});
+	ext
+}
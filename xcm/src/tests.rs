@@ -0,0 +1,424 @@
+//! Unit tests for the xcm module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::*;
+use sp_runtime::traits::BadOrigin;
+
+fn message() -> Xcm<()> {
+	Xcm(vec![ClearOrigin])
+}
+
+fn sibling() -> Location {
+	Location::new(1, [Parachain(SIBLING_ID)])
+}
+
+fn fee() -> Asset {
+	(Parent, 1_000).into()
+}
+
+fn response_origin(responder: Location) -> RuntimeOrigin {
+	pallet_xcm::Origin::Response(responder).into()
+}
+
+fn insert_query(query_id: QueryId, dest: Location) {
+	Queries::<Runtime>::insert(
+		query_id,
+		QueryRecord {
+			dest,
+			sent_at: 1,
+			response: None,
+		},
+	);
+}
+
+#[test]
+fn send_as_sovereign_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			OrmlXcm::send_as_sovereign(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(Location::parent().into()),
+				Box::new(VersionedXcm::from(message())),
+			),
+			BadOrigin
+		);
+
+		assert_ok!(OrmlXcm::send_as_sovereign(
+			RuntimeOrigin::root(),
+			Box::new(Location::parent().into()),
+			Box::new(VersionedXcm::from(message())),
+		));
+		assert_eq!(take_sent_xcm(), vec![(Location::parent(), message())]);
+		System::assert_last_event(RuntimeEvent::OrmlXcm(crate::Event::Sent {
+			to: Location::parent(),
+			message: message(),
+		}));
+	});
+}
+
+#[test]
+fn send_as_sovereign_fails_if_unroutable() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			OrmlXcm::send_as_sovereign(
+				RuntimeOrigin::root(),
+				Box::new(Location::new(2, [GlobalConsensus(NetworkId::Polkadot)]).into()),
+				Box::new(VersionedXcm::from(message())),
+			),
+			Error::<Runtime>::Unreachable
+		);
+		assert!(take_sent_xcm().is_empty());
+	});
+}
+
+#[test]
+fn send_as_sovereign_with_fees_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			OrmlXcm::send_as_sovereign_with_fees(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(sibling().into()),
+				Box::new(VersionedXcm::from(message())),
+				Box::new(fee().into()),
+			),
+			BadOrigin
+		);
+
+		assert_ok!(OrmlXcm::send_as_sovereign_with_fees(
+			RuntimeOrigin::root(),
+			Box::new(sibling().into()),
+			Box::new(VersionedXcm::from(message())),
+			Box::new(fee().into()),
+		));
+		assert_eq!(
+			take_sent_xcm(),
+			vec![(
+				sibling(),
+				Xcm(vec![
+					WithdrawAsset(fee().into()),
+					BuyExecution {
+						fees: fee(),
+						weight_limit: Unlimited,
+					},
+					SetAppendix(Xcm(vec![
+						RefundSurplus,
+						DepositAsset {
+							assets: Wild(AllCounted(1)),
+							beneficiary: Location::new(1, [Parachain(PARA_ID)]),
+						},
+					])),
+					ClearOrigin,
+				])
+			)]
+		);
+	});
+}
+
+#[test]
+fn send_as_sovereign_with_response_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			OrmlXcm::send_as_sovereign_with_response(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(Location::parent().into()),
+				Box::new(VersionedXcm::from(message())),
+				None,
+				10,
+			),
+			BadOrigin
+		);
+
+		assert_ok!(OrmlXcm::send_as_sovereign_with_response(
+			RuntimeOrigin::root(),
+			Box::new(Location::parent().into()),
+			Box::new(VersionedXcm::from(message())),
+			None,
+			10,
+		));
+		let query_id = 0;
+		assert_eq!(
+			take_sent_xcm(),
+			vec![(
+				Location::parent(),
+				Xcm(vec![
+					SetAppendix(Xcm(vec![ReportError(QueryResponseInfo {
+						destination: Location::new(0, [Parachain(PARA_ID)]),
+						query_id,
+						max_weight: <Runtime as Config>::WeightInfo::note_query_response(),
+					})])),
+					ClearOrigin,
+				])
+			)]
+		);
+		assert_eq!(
+			Queries::<Runtime>::get(query_id),
+			Some(QueryRecord {
+				dest: Location::parent(),
+				sent_at: 1,
+				response: None,
+			})
+		);
+		System::assert_last_event(RuntimeEvent::OrmlXcm(crate::Event::ResponseExpected {
+			to: Location::parent(),
+			query_id,
+		}));
+	});
+}
+
+#[test]
+fn send_as_sovereign_with_response_and_fees_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(OrmlXcm::send_as_sovereign_with_response(
+			RuntimeOrigin::root(),
+			Box::new(sibling().into()),
+			Box::new(VersionedXcm::from(message())),
+			Some(Box::new(fee().into())),
+			10,
+		));
+		assert_eq!(
+			take_sent_xcm(),
+			vec![(
+				sibling(),
+				Xcm(vec![
+					WithdrawAsset(fee().into()),
+					BuyExecution {
+						fees: fee(),
+						weight_limit: Unlimited,
+					},
+					SetAppendix(Xcm(vec![
+						ReportError(QueryResponseInfo {
+							destination: Location::new(1, [Parachain(PARA_ID)]),
+							query_id: 0,
+							max_weight: <Runtime as Config>::WeightInfo::note_query_response(),
+						}),
+						RefundSurplus,
+						DepositAsset {
+							assets: Wild(AllCounted(1)),
+							beneficiary: Location::new(1, [Parachain(PARA_ID)]),
+						},
+					])),
+					ClearOrigin,
+				])
+			)]
+		);
+		assert!(Queries::<Runtime>::contains_key(0));
+	});
+}
+
+#[test]
+fn note_query_response_works() {
+	new_test_ext().execute_with(|| {
+		insert_query(0, Location::parent());
+		let response = Response::ExecutionResult(Some((1, XcmError::Unroutable)));
+
+		assert_noop!(
+			OrmlXcm::note_query_response(RuntimeOrigin::signed(ALICE), 0, Box::new(response.clone())),
+			BadOrigin
+		);
+		assert_noop!(
+			OrmlXcm::note_query_response(response_origin(Location::parent()), 1, Box::new(response.clone())),
+			Error::<Runtime>::QueryNotFound
+		);
+		assert_noop!(
+			OrmlXcm::note_query_response(response_origin(sibling()), 0, Box::new(response.clone())),
+			Error::<Runtime>::UnexpectedResponder
+		);
+
+		System::set_block_number(5);
+		assert_ok!(OrmlXcm::note_query_response(
+			response_origin(Location::parent()),
+			0,
+			Box::new(response.clone())
+		));
+		assert_eq!(
+			Queries::<Runtime>::get(0),
+			Some(QueryRecord {
+				dest: Location::parent(),
+				sent_at: 1,
+				response: Some((response.clone(), 5)),
+			})
+		);
+		System::assert_last_event(RuntimeEvent::OrmlXcm(crate::Event::ResponseReceived {
+			query_id: 0,
+			response,
+		}));
+	});
+}
+
+#[test]
+fn remove_query_works() {
+	new_test_ext().execute_with(|| {
+		insert_query(0, Location::parent());
+
+		assert_noop!(OrmlXcm::remove_query(RuntimeOrigin::signed(ALICE), 0), BadOrigin);
+		assert_noop!(
+			OrmlXcm::remove_query(RuntimeOrigin::root(), 1),
+			Error::<Runtime>::QueryNotFound
+		);
+
+		assert_ok!(OrmlXcm::remove_query(RuntimeOrigin::root(), 0));
+		assert!(!Queries::<Runtime>::contains_key(0));
+		System::assert_last_event(RuntimeEvent::OrmlXcm(crate::Event::QueryRemoved { query_id: 0 }));
+	});
+}
+
+#[test]
+fn set_transact_destination_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			OrmlXcm::set_transact_destination(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(sibling().into()),
+				Some(Box::new(fee().into())),
+			),
+			BadOrigin
+		);
+
+		assert_ok!(OrmlXcm::set_transact_destination(
+			RuntimeOrigin::root(),
+			Box::new(sibling().into()),
+			Some(Box::new(fee().into())),
+		));
+		assert_eq!(TransactDestinations::<Runtime>::get(sibling()), Some(fee()));
+		System::assert_last_event(RuntimeEvent::OrmlXcm(crate::Event::TransactDestinationUpdated {
+			dest: sibling(),
+			fee: Some(fee()),
+		}));
+
+		assert_ok!(OrmlXcm::set_transact_destination(
+			RuntimeOrigin::root(),
+			Box::new(sibling().into()),
+			None,
+		));
+		assert!(!TransactDestinations::<Runtime>::contains_key(sibling()));
+		System::assert_last_event(RuntimeEvent::OrmlXcm(crate::Event::TransactDestinationUpdated {
+			dest: sibling(),
+			fee: None,
+		}));
+	});
+}
+
+#[test]
+fn send_transact_as_sovereign_works() {
+	new_test_ext().execute_with(|| {
+		let call = vec![1u8, 2, 3];
+		let fallback_max_weight = Weight::from_parts(1_000_000, 1_000);
+
+		assert_noop!(
+			OrmlXcm::send_transact_as_sovereign(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(sibling().into()),
+				call.clone(),
+				fallback_max_weight,
+				OriginKind::SovereignAccount,
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			OrmlXcm::send_transact_as_sovereign(
+				RuntimeOrigin::root(),
+				Box::new(sibling().into()),
+				call.clone(),
+				fallback_max_weight,
+				OriginKind::SovereignAccount,
+			),
+			Error::<Runtime>::TransactDestinationNotAllowed
+		);
+
+		assert_ok!(OrmlXcm::set_transact_destination(
+			RuntimeOrigin::root(),
+			Box::new(sibling().into()),
+			Some(Box::new(fee().into())),
+		));
+		assert_ok!(OrmlXcm::send_transact_as_sovereign(
+			RuntimeOrigin::root(),
+			Box::new(sibling().into()),
+			call.clone(),
+			fallback_max_weight,
+			OriginKind::SovereignAccount,
+		));
+		assert_eq!(
+			take_sent_xcm(),
+			vec![(
+				sibling(),
+				Xcm(vec![
+					DescendOrigin(TransactDescendOrigin::get()),
+					WithdrawAsset(fee().into()),
+					BuyExecution {
+						fees: fee(),
+						weight_limit: Unlimited,
+					},
+					SetAppendix(Xcm(vec![
+						RefundSurplus,
+						DepositAsset {
+							assets: Wild(AllCounted(1)),
+							beneficiary: Location::new(
+								1,
+								[
+									Parachain(PARA_ID),
+									Plurality {
+										id: BodyId::Technical,
+										part: BodyPart::Voice,
+									},
+								],
+							),
+						},
+					])),
+					Transact {
+						origin_kind: OriginKind::SovereignAccount,
+						fallback_max_weight: Some(fallback_max_weight),
+						call: call.clone().into(),
+					},
+				])
+			)]
+		);
+		System::assert_last_event(RuntimeEvent::OrmlXcm(crate::Event::TransactSent {
+			to: sibling(),
+			origin_kind: OriginKind::SovereignAccount,
+			fallback_max_weight,
+		}));
+
+		// disallowed again
+		assert_ok!(OrmlXcm::set_transact_destination(
+			RuntimeOrigin::root(),
+			Box::new(sibling().into()),
+			None,
+		));
+		assert_noop!(
+			OrmlXcm::send_transact_as_sovereign(
+				RuntimeOrigin::root(),
+				Box::new(sibling().into()),
+				call,
+				fallback_max_weight,
+				OriginKind::SovereignAccount,
+			),
+			Error::<Runtime>::TransactDestinationNotAllowed
+		);
+	});
+}
+
+#[test]
+fn send_transact_as_sovereign_only_to_allowed_destination() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(OrmlXcm::set_transact_destination(
+			RuntimeOrigin::root(),
+			Box::new(sibling().into()),
+			Some(Box::new(fee().into())),
+		));
+
+		assert_noop!(
+			OrmlXcm::send_transact_as_sovereign(
+				RuntimeOrigin::root(),
+				Box::new(Location::parent().into()),
+				vec![],
+				Weight::zero(),
+				OriginKind::Native,
+			),
+			Error::<Runtime>::TransactDestinationNotAllowed
+		);
+		assert!(take_sent_xcm().is_empty());
+	});
+}
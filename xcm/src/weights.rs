@@ -0,0 +1,64 @@
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for orml_xcm.
+pub trait WeightInfo {
+	fn send_as_sovereign(n: u32, ) -> Weight;
+	fn send_as_sovereign_with_fees(n: u32, ) -> Weight;
+	fn send_as_sovereign_with_response(n: u32, ) -> Weight;
+	fn note_query_response() -> Weight;
+	fn remove_query() -> Weight;
+	fn set_transact_destination() -> Weight;
+	fn send_transact_as_sovereign(n: u32, ) -> Weight;
+}
+
+/// Default weights.
+impl WeightInfo for () {
+	fn send_as_sovereign(n: u32, ) -> Weight {
+		Weight::from_parts(13_278_000, 0)
+			// Standard Error: 32
+			.saturating_add(Weight::from_parts(2_377, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn send_as_sovereign_with_fees(n: u32, ) -> Weight {
+		Weight::from_parts(14_811_000, 0)
+			// Standard Error: 44
+			.saturating_add(Weight::from_parts(2_555, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn send_as_sovereign_with_response(n: u32, ) -> Weight {
+		Weight::from_parts(21_557_000, 0)
+			// Standard Error: 37
+			.saturating_add(Weight::from_parts(2_700, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+	}
+	fn note_query_response() -> Weight {
+		Weight::from_parts(9_768_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn remove_query() -> Weight {
+		Weight::from_parts(7_665_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_transact_destination() -> Weight {
+		Weight::from_parts(9_451_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn send_transact_as_sovereign(n: u32, ) -> Weight {
+		Weight::from_parts(11_389_014, 0)
+			// Standard Error: 38
+			.saturating_add(Weight::from_parts(2_037, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+}
@@ -0,0 +1,401 @@
+pub use crate::*;
+
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use sp_runtime::traits::Saturating;
+
+/// The transfer path to benchmark, see `TransferKind`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TransferPath {
+	/// The asset is reserved on this chain.
+	SelfReserve,
+	/// The asset is reserved on the dest chain.
+	ToReserve,
+	/// The asset is reserved on a third chain.
+	ToNonReserve,
+}
+
+/// Helper trait for benchmarking.
+pub trait BenchmarkHelper<AccountId, CurrencyId, Balance> {
+	/// Returns a currency, an amount of it and a dest, including the
+	/// beneficiary, which transfer along `path`.
+	fn transfer_case(path: TransferPath) -> Option<(CurrencyId, Balance, Location)>;
+
+	/// Returns currencies and amounts which could be transferred together
+	/// along `path`, using the first one as fee, and a dest including the
+	/// beneficiary.
+	fn multi_transfer_case(path: TransferPath) -> Option<(Vec<(CurrencyId, Balance)>, Location)>;
+
+	/// Returns a currency which `T::FeeSwap` could swap to the currency of
+	/// the `ToReserve` transfer case, to pay the fee.
+	fn fee_swap_case() -> Option<CurrencyId>;
+
+	/// Make `who` own at least `amount` of `currency_id`.
+	fn fund(who: &AccountId, currency_id: CurrencyId, amount: Balance);
+}
+
+impl<AccountId, CurrencyId, Balance> BenchmarkHelper<AccountId, CurrencyId, Balance> for () {
+	fn transfer_case(_path: TransferPath) -> Option<(CurrencyId, Balance, Location)> {
+		None
+	}
+
+	fn multi_transfer_case(_path: TransferPath) -> Option<(Vec<(CurrencyId, Balance)>, Location)> {
+		None
+	}
+
+	fn fee_swap_case() -> Option<CurrencyId> {
+		None
+	}
+
+	fn fund(_who: &AccountId, _currency_id: CurrencyId, _amount: Balance) {}
+}
+
+fn funded_transfer_case<T: Config>(
+	who: &T::AccountId,
+	path: TransferPath,
+) -> Result<(T::CurrencyId, T::Balance, Location), BenchmarkError> {
+	let (currency_id, amount, dest) = T::BenchmarkHelper::transfer_case(path).ok_or(BenchmarkError::Skip)?;
+	// enough for both the transferred amount and the fee
+	T::BenchmarkHelper::fund(who, currency_id.clone(), amount.saturating_mul(2u32.into()));
+	Ok((currency_id, amount, dest))
+}
+
+/// Currencies and amounts to transfer together, and the dest.
+type MultiTransferCase<T> = (Vec<(<T as Config>::CurrencyId, <T as Config>::Balance)>, Location);
+
+fn funded_multi_transfer_case<T: Config>(
+	who: &T::AccountId,
+	path: TransferPath,
+) -> Result<MultiTransferCase<T>, BenchmarkError> {
+	let (currencies, dest) = T::BenchmarkHelper::multi_transfer_case(path).ok_or(BenchmarkError::Skip)?;
+	for (currency_id, amount) in currencies.iter() {
+		T::BenchmarkHelper::fund(who, currency_id.clone(), *amount);
+	}
+	Ok((currencies, dest))
+}
+
+/// Returns the assets of `currencies`, and the index of the first one, the
+/// fee, once sorted.
+fn assets_of<T: Config>(currencies: Vec<(T::CurrencyId, T::Balance)>) -> Result<(Assets, u32), BenchmarkError> {
+	let mut assets = Vec::new();
+	for (currency_id, amount) in currencies {
+		assets.push(asset_of::<T>(currency_id, amount)?);
+	}
+	let fee = assets.first().cloned().ok_or(BenchmarkError::Skip)?;
+	let assets = Assets::from(assets);
+	let fee_item = assets
+		.inner()
+		.iter()
+		.position(|asset| *asset == fee)
+		.ok_or(BenchmarkError::Stop("fee asset not found"))? as u32;
+	Ok((assets, fee_item))
+}
+
+fn asset_of<T: Config>(currency_id: T::CurrencyId, amount: T::Balance) -> Result<Asset, BenchmarkError> {
+	let location = T::CurrencyIdConvert::convert(currency_id).ok_or(BenchmarkError::Skip)?;
+	Ok((location, amount.into()).into())
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn transfer_self_reserve() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (currency_id, amount, dest) = funded_transfer_case::<T>(&caller, TransferPath::SelfReserve)?;
+
+		#[extrinsic_call]
+		transfer(
+			RawOrigin::Signed(caller),
+			currency_id,
+			amount,
+			Box::new(dest.into()),
+			WeightLimit::Unlimited,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_to_reserve() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (currency_id, amount, dest) = funded_transfer_case::<T>(&caller, TransferPath::ToReserve)?;
+
+		#[extrinsic_call]
+		transfer(
+			RawOrigin::Signed(caller),
+			currency_id,
+			amount,
+			Box::new(dest.into()),
+			WeightLimit::Unlimited,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_to_non_reserve() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (currency_id, amount, dest) = funded_transfer_case::<T>(&caller, TransferPath::ToNonReserve)?;
+
+		#[extrinsic_call]
+		transfer(
+			RawOrigin::Signed(caller),
+			currency_id,
+			amount,
+			Box::new(dest.into()),
+			WeightLimit::Unlimited,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_multiasset_self_reserve() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (currency_id, amount, dest) = funded_transfer_case::<T>(&caller, TransferPath::SelfReserve)?;
+		let asset = asset_of::<T>(currency_id, amount)?;
+
+		#[extrinsic_call]
+		transfer_multiasset(
+			RawOrigin::Signed(caller),
+			Box::new(asset.into()),
+			Box::new(dest.into()),
+			WeightLimit::Unlimited,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_multiasset_to_reserve() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (currency_id, amount, dest) = funded_transfer_case::<T>(&caller, TransferPath::ToReserve)?;
+		let asset = asset_of::<T>(currency_id, amount)?;
+
+		#[extrinsic_call]
+		transfer_multiasset(
+			RawOrigin::Signed(caller),
+			Box::new(asset.into()),
+			Box::new(dest.into()),
+			WeightLimit::Unlimited,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_multiasset_to_non_reserve() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (currency_id, amount, dest) = funded_transfer_case::<T>(&caller, TransferPath::ToNonReserve)?;
+		let asset = asset_of::<T>(currency_id, amount)?;
+
+		#[extrinsic_call]
+		transfer_multiasset(
+			RawOrigin::Signed(caller),
+			Box::new(asset.into()),
+			Box::new(dest.into()),
+			WeightLimit::Unlimited,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_with_fee() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (currency_id, amount, dest) = funded_transfer_case::<T>(&caller, TransferPath::ToReserve)?;
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller),
+			currency_id,
+			amount,
+			amount,
+			Box::new(dest.into()),
+			WeightLimit::Unlimited,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_multiasset_with_fee() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (currency_id, amount, dest) = funded_transfer_case::<T>(&caller, TransferPath::ToReserve)?;
+		let asset = asset_of::<T>(currency_id, amount)?;
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller),
+			Box::new(asset.clone().into()),
+			Box::new(asset.into()),
+			Box::new(dest.into()),
+			WeightLimit::Unlimited,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_multicurrencies_self_reserve() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (currencies, dest) = funded_multi_transfer_case::<T>(&caller, TransferPath::SelfReserve)?;
+
+		#[extrinsic_call]
+		transfer_multicurrencies(
+			RawOrigin::Signed(caller),
+			currencies,
+			0,
+			Box::new(dest.into()),
+			WeightLimit::Unlimited,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_multicurrencies_to_reserve() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (currencies, dest) = funded_multi_transfer_case::<T>(&caller, TransferPath::ToReserve)?;
+
+		#[extrinsic_call]
+		transfer_multicurrencies(
+			RawOrigin::Signed(caller),
+			currencies,
+			0,
+			Box::new(dest.into()),
+			WeightLimit::Unlimited,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_multicurrencies_to_non_reserve() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (currencies, dest) = funded_multi_transfer_case::<T>(&caller, TransferPath::ToNonReserve)?;
+
+		#[extrinsic_call]
+		transfer_multicurrencies(
+			RawOrigin::Signed(caller),
+			currencies,
+			0,
+			Box::new(dest.into()),
+			WeightLimit::Unlimited,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_multiassets_self_reserve() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (currencies, dest) = funded_multi_transfer_case::<T>(&caller, TransferPath::SelfReserve)?;
+		let (assets, fee_item) = assets_of::<T>(currencies)?;
+
+		#[extrinsic_call]
+		transfer_multiassets(
+			RawOrigin::Signed(caller),
+			Box::new(assets.into()),
+			fee_item,
+			Box::new(dest.into()),
+			WeightLimit::Unlimited,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_multiassets_to_reserve() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (currencies, dest) = funded_multi_transfer_case::<T>(&caller, TransferPath::ToReserve)?;
+		let (assets, fee_item) = assets_of::<T>(currencies)?;
+
+		#[extrinsic_call]
+		transfer_multiassets(
+			RawOrigin::Signed(caller),
+			Box::new(assets.into()),
+			fee_item,
+			Box::new(dest.into()),
+			WeightLimit::Unlimited,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_multiassets_to_non_reserve() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (currencies, dest) = funded_multi_transfer_case::<T>(&caller, TransferPath::ToNonReserve)?;
+		let (assets, fee_item) = assets_of::<T>(currencies)?;
+
+		#[extrinsic_call]
+		transfer_multiassets(
+			RawOrigin::Signed(caller),
+			Box::new(assets.into()),
+			fee_item,
+			Box::new(dest.into()),
+			WeightLimit::Unlimited,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_assets_using_type_and_then() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (currency_id, amount, dest) = funded_transfer_case::<T>(&caller, TransferPath::ToReserve)?;
+		let asset = asset_of::<T>(currency_id, amount)?;
+		let chain = chain_part(&dest).ok_or(BenchmarkError::Skip)?;
+		let beneficiary = non_chain_part(&dest).ok_or(BenchmarkError::Skip)?;
+		let custom_xcm_on_dest = Xcm::<()>(vec![DepositAsset {
+			assets: AllCounted(1).into(),
+			beneficiary,
+		}]);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller),
+			Box::new(vec![asset].into()),
+			0,
+			Box::new(chain.into()),
+			Box::new(TransferType::DestinationReserve),
+			None,
+			Box::new(VersionedXcm::from(custom_xcm_on_dest)),
+			WeightLimit::Unlimited,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_with_fee_swap() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let (currency_id, amount, dest) = funded_transfer_case::<T>(&caller, TransferPath::ToReserve)?;
+		let fee_supply_currency_id = T::BenchmarkHelper::fee_swap_case().ok_or(BenchmarkError::Skip)?;
+		let fee_location = T::CurrencyIdConvert::convert(currency_id.clone()).ok_or(BenchmarkError::Skip)?;
+		let supply_location =
+			T::CurrencyIdConvert::convert(fee_supply_currency_id.clone()).ok_or(BenchmarkError::Skip)?;
+		let supply_amount =
+			Pallet::<T>::quote_fee(&supply_location, &fee_location, amount).ok_or(BenchmarkError::Skip)?;
+		T::BenchmarkHelper::fund(&caller, fee_supply_currency_id.clone(), supply_amount);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller),
+			currency_id,
+			amount,
+			amount,
+			fee_supply_currency_id,
+			None,
+			Box::new(dest.into()),
+			WeightLimit::Unlimited,
+		);
+
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::para_ext(1), crate::mock::para::Runtime);
+}
@@ -24,6 +24,17 @@
 //!   item to be used as fee.
 //! - `transfer_multiassets`: Transfer several `Asset` specifying the item to be
 //!   used as fee.
+//! - `transfer_assets_using_type_and_then`: Transfer several `Asset` with an
+//!   explicit transfer type, and execute custom XCM on dest chain.
+//!
+//! ### Routes
+//!
+//! By default assets sent to a non-reserve chain go through their reserve
+//! chain only. `AssetRoutes` can configure explicit routes per asset, which
+//! forward assets through several intermediate chains, each of them buying
+//! execution with its own share of the fee and weight limit. Every hop must be
+//! able to forward the assets: by reserve transfer or teleport if it is a
+//! reserve of them, or by reserve withdraw back to the chain backing them.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::from_over_into)]
@@ -35,31 +46,40 @@
 use frame_support::{
 	pallet_prelude::*,
 	require_transactional,
-	traits::{Contains, Get},
+	traits::{Contains, ContainsPair, Get},
+	weights::constants::WEIGHT_REF_TIME_PER_SECOND,
 	Parameter,
 };
 use frame_system::{ensure_signed, pallet_prelude::*};
 use sp_runtime::{
+	helpers_128bit::multiply_by_rational_with_rounding,
 	traits::{AtLeast32BitUnsigned, Bounded, Convert, MaybeSerializeDeserialize, Member, Zero},
-	DispatchError,
+	DispatchError, Rounding,
 };
 use sp_std::{prelude::*, result::Result};
 
 use xcm::{
 	v5::{prelude::*, Weight},
-	VersionedAsset, VersionedAssets, VersionedLocation,
+	VersionedAsset, VersionedAssets, VersionedLocation, VersionedXcm,
 };
-use xcm_executor::traits::WeightBounds;
+use xcm_executor::traits::{TransferType, WeightBounds};
 
 pub use module::*;
 use orml_traits::{
 	location::{Reserve, ASSET_HUB_ID},
-	xcm_transfer::{Transferred, XtokensWeightInfo},
-	GetByKey, RateLimiter, XcmTransfer,
+	xcm_transfer::{FeeSwap, RouteHop, Transferred, XtokensWeightInfo},
+	GetByKey, RateLimiter, WeightToFeeConverter, XcmTransfer,
 };
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 mod mock;
 mod tests;
+mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub use benchmarking::{BenchmarkHelper, TransferPath};
+pub use weights::WeightInfo;
 
 enum TransferKind {
 	/// Transfer self reserve asset.
@@ -108,6 +128,10 @@ pub mod module {
 		/// Location filter
 		type LocationsFilter: Contains<Location>;
 
+		/// The assets which can be teleported to a location, checked for
+		/// `TransferType::Teleport` transfers.
+		type TeleportFilter: ContainsPair<Asset, Location>;
+
 		/// Means of measuring the weight consumed by an XCM message locally.
 		type Weigher: WeightBounds<Self::RuntimeCall>;
 
@@ -135,6 +159,35 @@ pub mod module {
 		/// The id of the RateLimiter.
 		#[pallet::constant]
 		type RateLimiterId: Get<<Self::RateLimiter as RateLimiter>::RateLimiterId>;
+
+		/// Explicit multi-hop routes, keyed by the location of the fee asset.
+		///
+		/// The first hop must be the asset's reserve, unless the asset is self
+		/// reserved. Chains holding derivatives of the asset can only forward
+		/// it back to the chain backing them. A route is cut short before the
+		/// dest chain if it passes through it. A default implementation is
+		/// provided by `DisabledAssetRoutes` in `xcm-support`.
+		type AssetRoutes: GetByKey<Location, Option<Vec<RouteHop>>>;
+
+		/// Quotes fees in another asset, by comparing what each asset would be
+		/// charged for the same weight. `FixedRateAssetRegistryTrader` of
+		/// `orml-asset-registry` converts a `FixedConversionRateProvider` into
+		/// one.
+		type FeeConverter: WeightToFeeConverter;
+
+		/// Swaps the asset a fee was quoted in to the fee asset, for
+		/// `transfer_with_fee_swap`.
+		type FeeSwap: FeeSwap<Self::AccountId, Self::CurrencyId, Self::Balance>;
+
+		/// Weight information for extrinsics in this module.
+		///
+		/// It's the whole cost of a call, including executing the XCM message
+		/// locally. The XCM execution weight estimated by `T::Weigher` is used
+		/// instead when it's higher.
+		type WeightInfo: WeightInfo;
+
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AccountId, Self::CurrencyId, Self::Balance>;
 	}
 
 	#[pallet::event]
@@ -147,6 +200,13 @@ pub mod module {
 			fee: Asset,
 			dest: Location,
 		},
+		/// Swapped `supply_amount` of `supply_currency_id` to pay `fee`.
+		FeeSwapped {
+			sender: T::AccountId,
+			supply_currency_id: T::CurrencyId,
+			supply_amount: T::Balance,
+			fee: Asset,
+		},
 	}
 
 	#[pallet::error]
@@ -194,6 +254,18 @@ pub mod module {
 		MinXcmFeeNotDefined,
 		/// Asset transfer is limited by RateLimiter.
 		RateLimited,
+		/// The configured route can't be used for this transfer.
+		InvalidRoute,
+		/// The fee can't be quoted in the supply currency.
+		FeeQuoteUnavailable,
+		/// Swapping the fee would spend more than the allowed supply amount.
+		FeeSwapLimitExceeded,
+		/// The transfer type doesn't match the reserve of an asset, or the
+		/// asset can't be teleported to the dest.
+		InvalidTransferType,
+		/// The reserve fee is set for a transfer without a remote reserve, or
+		/// leaves no fee for either chain.
+		InvalidReserveFee,
 	}
 
 	#[pallet::hooks]
@@ -278,7 +350,7 @@ pub mod module {
 		/// by the network, and if the receiving chain would handle
 		/// messages correctly.
 		#[pallet::call_index(2)]
-		#[pallet::weight(XtokensWeight::<T>::weight_of_transfer(currency_id.clone(), *amount, dest))]
+		#[pallet::weight(XtokensWeight::<T>::weight_of_transfer_with_fee(currency_id.clone(), *amount, dest))]
 		pub fn transfer_with_fee(
 			origin: OriginFor<T>,
 			currency_id: T::CurrencyId,
@@ -315,7 +387,7 @@ pub mod module {
 		/// by the network, and if the receiving chain would handle
 		/// messages correctly.
 		#[pallet::call_index(3)]
-		#[pallet::weight(XtokensWeight::<T>::weight_of_transfer_multiasset(asset, dest))]
+		#[pallet::weight(XtokensWeight::<T>::weight_of_transfer_multiasset_with_fee(asset, dest))]
 		pub fn transfer_multiasset_with_fee(
 			origin: OriginFor<T>,
 			asset: Box<VersionedAsset>,
@@ -398,9 +470,144 @@ pub mod module {
 		// #[pallet::call_index(6)]
 		// pub fn set_migration_phase(origin: OriginFor<T>, migration_phase:
 		// MigrationPhase) -> DispatchResult {}
+
+		/// Transfer several `Asset` using an explicit `transfer_type`, and
+		/// execute `custom_xcm_on_dest` on the dest chain.
+		///
+		/// `dest` is the dest chain location. Assets are not deposited on the
+		/// dest chain unless `custom_xcm_on_dest` does so, which would
+		/// typically end with a `DepositAsset` instruction.
+		///
+		/// `fee_item` is index of the Assets that we want to use for
+		/// payment.
+		///
+		/// `reserve_fee` is the amount of the fee paid on the reserve chain of
+		/// `RemoteReserve` transfers, the rest being paid on the dest chain.
+		/// Both parts must meet the `MinXcmFee` of their chain. If `None`, the
+		/// fee is split in half. It must be `None` for other transfer types.
+		///
+		/// `dest_weight_limit` is the weight for XCM execution on the dest
+		/// chain, and it would be charged from the transferred assets. If set
+		/// below requirements, the execution may fail and assets wouldn't be
+		/// received.
+		///
+		/// It's a no-op if any error on local XCM execution or message sending.
+		/// Note sending assets out per se doesn't guarantee they would be
+		/// received. Receiving depends on if the XCM message could be delivered
+		/// by the network, and if the receiving chain would handle
+		/// messages correctly.
+		#[pallet::call_index(7)]
+		#[pallet::weight(XtokensWeight::<T>::weight_of_transfer_assets_using_type_and_then(assets, transfer_type, dest))]
+		pub fn transfer_assets_using_type_and_then(
+			origin: OriginFor<T>,
+			assets: Box<VersionedAssets>,
+			fee_item: u32,
+			dest: Box<VersionedLocation>,
+			transfer_type: Box<TransferType>,
+			reserve_fee: Option<u128>,
+			custom_xcm_on_dest: Box<VersionedXcm<()>>,
+			dest_weight_limit: WeightLimit,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let assets: Assets = (*assets).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let dest: Location = (*dest).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			let custom_xcm_on_dest: Xcm<()> = (*custom_xcm_on_dest).try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			let fee: &Asset = assets.get(fee_item as usize).ok_or(Error::<T>::AssetIndexNonExistent)?;
+
+			Self::do_transfer_assets_using_type(
+				who,
+				assets.clone(),
+				fee.clone(),
+				dest,
+				*transfer_type,
+				reserve_fee,
+				custom_xcm_on_dest,
+				dest_weight_limit,
+			)
+			.map(|_| ())
+		}
+
+		/// Transfer native currencies specifying the fee and amount as
+		/// separate, swapping `fee_supply_currency_id` for the fee first.
+		///
+		/// It's for users who don't hold the fee of `currency_id`. The swap
+		/// is done through `T::FeeSwap` and spends at most `max_fee_supply`
+		/// of `fee_supply_currency_id`. If `max_fee_supply` is `None`, the
+		/// fee is quoted with `T::FeeConverter` and the swap may not spend
+		/// more than the quote.
+		///
+		/// The rest is the same as `transfer_with_fee`.
+		#[pallet::call_index(8)]
+		#[pallet::weight(XtokensWeight::<T>::weight_of_transfer_with_fee_swap(currency_id.clone(), *amount, dest))]
+		#[allow(clippy::too_many_arguments)]
+		pub fn transfer_with_fee_swap(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			amount: T::Balance,
+			fee: T::Balance,
+			fee_supply_currency_id: T::CurrencyId,
+			max_fee_supply: Option<T::Balance>,
+			dest: Box<VersionedLocation>,
+			dest_weight_limit: WeightLimit,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let dest: Location = (*dest).try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			Self::do_swap_fee(&who, currency_id.clone(), fee, fee_supply_currency_id, max_fee_supply)?;
+			Self::do_transfer_with_fee(who, currency_id, amount, fee, dest, dest_weight_limit).map(|_| ())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Quote `fee` of `fee_location` in `supply_location`, rounding up.
+		pub fn quote_fee(supply_location: &Location, fee_location: &Location, fee: T::Balance) -> Option<T::Balance> {
+			let weight = Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND, 0);
+			let supply_rate = T::FeeConverter::convert_weight_to_fee(supply_location, weight)?;
+			let fee_rate = T::FeeConverter::convert_weight_to_fee(fee_location, weight)?;
+			let supply = multiply_by_rational_with_rounding(fee.into(), supply_rate, fee_rate, Rounding::Up)?;
+			supply.try_into().ok()
+		}
+
+		/// Swap `fee_supply_currency_id` of `who` for `fee` of `currency_id`.
+		fn do_swap_fee(
+			who: &T::AccountId,
+			currency_id: T::CurrencyId,
+			fee: T::Balance,
+			fee_supply_currency_id: T::CurrencyId,
+			max_fee_supply: Option<T::Balance>,
+		) -> DispatchResult {
+			ensure!(!fee.is_zero(), Error::<T>::ZeroFee);
+
+			let fee_location: Location = T::CurrencyIdConvert::convert(currency_id.clone())
+				.ok_or(Error::<T>::NotCrossChainTransferableCurrency)?;
+			let max_fee_supply = match max_fee_supply {
+				Some(max_fee_supply) => max_fee_supply,
+				None => {
+					let supply_location: Location = T::CurrencyIdConvert::convert(fee_supply_currency_id.clone())
+						.ok_or(Error::<T>::FeeQuoteUnavailable)?;
+					Self::quote_fee(&supply_location, &fee_location, fee).ok_or(Error::<T>::FeeQuoteUnavailable)?
+				}
+			};
+
+			let supply_amount = T::FeeSwap::swap_for_exact_target(
+				who,
+				fee_supply_currency_id.clone(),
+				currency_id,
+				max_fee_supply,
+				fee,
+			)?;
+			ensure!(supply_amount <= max_fee_supply, Error::<T>::FeeSwapLimitExceeded);
+
+			Self::deposit_event(Event::<T>::FeeSwapped {
+				sender: who.clone(),
+				supply_currency_id: fee_supply_currency_id,
+				supply_amount,
+				fee: (fee_location, fee.into()).into(),
+			});
+			Ok(())
+		}
+
 		fn do_transfer(
 			who: T::AccountId,
 			currency_id: T::CurrencyId,
@@ -533,11 +740,7 @@ pub mod module {
 			for i in 0..asset_len {
 				let asset = assets.get(i).ok_or(Error::<T>::AssetIndexNonExistent)?;
 
-				match asset.fun {
-					Fungibility::Fungible(x) => ensure!(!x.is_zero(), Error::<T>::InvalidAsset),
-					Fungibility::NonFungible(AssetInstance::Undefined) => return Err(Error::<T>::InvalidAsset.into()),
-					_ => {}
-				}
+				Self::ensure_valid_asset(asset)?;
 
 				// `assets` includes fee, the reserve location is decided by non fee asset
 				if non_fee_reserve.is_none() && asset.id != fee.id {
@@ -553,16 +756,7 @@ pub mod module {
 				}
 
 				// per asset check
-				let amount = match asset.fun {
-					Fungibility::Fungible(amount) => amount,
-					Fungibility::NonFungible(_) => 1,
-				};
-
-				let rate_limiter_id = T::RateLimiterId::get();
-
-				// try consume quota of the rate limiter.
-				T::RateLimiter::try_consume(rate_limiter_id, asset.id.clone(), amount, Some(&who))
-					.map_err(|_| Error::<T>::RateLimited)?;
+				Self::try_consume_quota(&who, asset)?;
 			}
 
 			let fee_reserve = T::ReserveProvider::reserve(&fee);
@@ -627,6 +821,16 @@ pub mod module {
 					dest_weight_limit,
 					false,
 				)?;
+			} else if let Some(route) = Self::asset_route(&fee, &dest) {
+				Self::execute_and_send_routed_xcm(
+					origin_location,
+					assets.clone(),
+					fee.clone(),
+					fee_reserve,
+					&dest,
+					route,
+					dest_weight_limit,
+				)?;
 			} else {
 				Self::execute_and_send_reserve_kind_xcm(
 					origin_location,
@@ -655,6 +859,217 @@ pub mod module {
 			})
 		}
 
+		fn do_transfer_assets_using_type(
+			who: T::AccountId,
+			assets: Assets,
+			fee: Asset,
+			dest: Location,
+			transfer_type: TransferType,
+			reserve_fee: Option<u128>,
+			custom_xcm_on_dest: Xcm<()>,
+			dest_weight_limit: WeightLimit,
+		) -> Result<Transferred<T::AccountId>, DispatchError> {
+			ensure!(
+				assets.len() <= T::MaxAssetsForTransfer::get(),
+				Error::<T>::TooManyAssetsBeingSent
+			);
+			ensure!(T::LocationsFilter::contains(&dest), Error::<T>::NotSupportedLocation);
+			ensure!(
+				dest != Location::here() && dest != T::SelfLocation::get(),
+				Error::<T>::NotCrossChainTransfer
+			);
+			// `dest` should be a chain, the recipient is up to `custom_xcm_on_dest`
+			ensure!(non_chain_part(&dest).is_none(), Error::<T>::InvalidDest);
+
+			// Fee payment can only be made by using the non-zero amount of fungibles
+			ensure!(
+				matches!(fee.fun, Fungibility::Fungible(x) if !x.is_zero()),
+				Error::<T>::InvalidAsset
+			);
+
+			for asset in assets.inner() {
+				Self::ensure_valid_asset(asset)?;
+				Self::ensure_transfer_type(asset, &dest, &transfer_type)?;
+				Self::try_consume_quota(&who, asset)?;
+			}
+
+			let max_assets = assets.len() as u32;
+			let (first_chain, xcm) = match transfer_type {
+				TransferType::RemoteReserve(ref reserve) => {
+					let reserve: Location = reserve.clone().try_into().map_err(|()| Error::<T>::BadVersion)?;
+					let (reserve_fee, dest_fee) = match reserve_fee {
+						Some(reserve_fee) => {
+							let dest_fee = fungible_amount(&fee)
+								.checked_sub(reserve_fee)
+								.filter(|dest_fee| !reserve_fee.is_zero() && !dest_fee.is_zero())
+								.ok_or(Error::<T>::InvalidReserveFee)?;
+							(with_amount(&fee, reserve_fee), with_amount(&fee, dest_fee))
+						}
+						None => (half(&fee), half(&fee)),
+					};
+					Self::ensure_min_xcm_fee(&reserve, &reserve_fee)?;
+					Self::ensure_min_xcm_fee(&dest, &dest_fee)?;
+
+					let ancestry = T::UniversalLocation::get();
+					let dest_from_reserve = dest
+						.clone()
+						.reanchored(&reserve, &ancestry)
+						.map_err(|_| Error::<T>::CannotReanchor)?;
+					let on_dest =
+						Self::custom_xcm_with_fee(dest_fee, &dest, dest_weight_limit.clone(), custom_xcm_on_dest)?;
+					let xcm = Xcm(vec![
+						Self::buy_execution(reserve_fee, &reserve, dest_weight_limit)?,
+						DepositReserveAsset {
+							assets: AllCounted(max_assets).into(),
+							dest: dest_from_reserve,
+							xcm: on_dest,
+						},
+					]);
+					(reserve, xcm)
+				}
+				TransferType::LocalReserve | TransferType::DestinationReserve | TransferType::Teleport => {
+					ensure!(reserve_fee.is_none(), Error::<T>::InvalidReserveFee);
+					Self::ensure_min_xcm_fee(&dest, &fee)?;
+					let xcm = Self::custom_xcm_with_fee(fee.clone(), &dest, dest_weight_limit, custom_xcm_on_dest)?;
+					(dest.clone(), xcm)
+				}
+			};
+
+			let origin_location = T::AccountIdToLocation::convert(who.clone());
+			let msg = Self::typed_transfer_message(assets.clone(), &transfer_type, first_chain, xcm);
+			Self::execute_xcm(origin_location, msg)?;
+
+			Self::deposit_event(Event::<T>::TransferredAssets {
+				sender: who.clone(),
+				assets: assets.clone(),
+				fee: fee.clone(),
+				dest: dest.clone(),
+			});
+
+			Ok(Transferred {
+				sender: who,
+				assets,
+				fee,
+				dest,
+			})
+		}
+
+		/// Returns the locally executed part of a transfer of `transfer_type`,
+		/// sending the assets with `xcm` to `chain`.
+		///
+		/// `chain` is the first chain receiving the assets, which is the
+		/// reserve chain for `RemoteReserve` transfers, else the dest chain.
+		fn typed_transfer_message(
+			assets: Assets,
+			transfer_type: &TransferType,
+			chain: Location,
+			xcm: Xcm<()>,
+		) -> Xcm<T::RuntimeCall> {
+			match transfer_type {
+				TransferType::LocalReserve => Xcm(vec![
+					SetFeesMode { jit_withdraw: true },
+					TransferReserveAsset {
+						assets,
+						dest: chain,
+						xcm,
+					},
+				]),
+				TransferType::Teleport => Xcm(vec![
+					WithdrawAsset(assets),
+					SetFeesMode { jit_withdraw: true },
+					InitiateTeleport {
+						assets: All.into(),
+						dest: chain,
+						xcm,
+					},
+				]),
+				TransferType::DestinationReserve | TransferType::RemoteReserve(_) => Xcm(vec![
+					WithdrawAsset(assets),
+					SetFeesMode { jit_withdraw: true },
+					InitiateReserveWithdraw {
+						assets: All.into(),
+						reserve: chain,
+						xcm,
+					},
+				]),
+			}
+		}
+
+		/// Prepend buying execution with `fee` on `at` to `custom_xcm`.
+		fn custom_xcm_with_fee(
+			fee: Asset,
+			at: &Location,
+			weight_limit: WeightLimit,
+			custom_xcm: Xcm<()>,
+		) -> Result<Xcm<()>, DispatchError> {
+			let mut instructions = vec![Self::buy_execution(fee, at, weight_limit)?];
+			instructions.extend(custom_xcm.0);
+			Ok(Xcm(instructions))
+		}
+
+		/// Ensure `fee` is not less than the `MinXcmFee` of `chain`, if
+		/// defined.
+		fn ensure_min_xcm_fee(chain: &Location, fee: &Asset) -> DispatchResult {
+			if let Some(min_xcm_fee) = T::MinXcmFee::get(chain) {
+				ensure!(fungible_amount(fee) >= min_xcm_fee, Error::<T>::FeeNotEnough);
+			}
+			Ok(())
+		}
+
+		/// Ensure `asset` is a valid asset to be transferred.
+		fn ensure_valid_asset(asset: &Asset) -> DispatchResult {
+			match asset.fun {
+				Fungibility::Fungible(x) => ensure!(!x.is_zero(), Error::<T>::InvalidAsset),
+				Fungibility::NonFungible(AssetInstance::Undefined) => return Err(Error::<T>::InvalidAsset.into()),
+				_ => {}
+			}
+			Ok(())
+		}
+
+		/// Ensure `transfer_type` is the way `asset` can be transferred to
+		/// `dest`.
+		///
+		/// Reserve transfers must go through the reserve of `asset`, and
+		/// teleports must be allowed by `T::TeleportFilter`.
+		fn ensure_transfer_type(asset: &Asset, dest: &Location, transfer_type: &TransferType) -> DispatchResult {
+			if let TransferType::Teleport = transfer_type {
+				ensure!(
+					T::TeleportFilter::contains(asset, dest),
+					Error::<T>::InvalidTransferType
+				);
+				return Ok(());
+			}
+
+			let reserve = T::ReserveProvider::reserve(asset).ok_or(Error::<T>::AssetHasNoReserve)?;
+			let self_location = T::SelfLocation::get();
+			let matched = match transfer_type {
+				TransferType::LocalReserve => reserve == self_location || reserve == Location::here(),
+				TransferType::DestinationReserve => reserve == *dest,
+				TransferType::RemoteReserve(remote) => {
+					let remote: Location = remote.clone().try_into().map_err(|()| Error::<T>::BadVersion)?;
+					ensure!(T::LocationsFilter::contains(&remote), Error::<T>::NotSupportedLocation);
+					reserve == remote && remote != *dest && remote != self_location && remote != Location::here()
+				}
+				TransferType::Teleport => false,
+			};
+			ensure!(matched, Error::<T>::InvalidTransferType);
+			Ok(())
+		}
+
+		/// Try to consume the quota of the rate limiter for `asset`.
+		fn try_consume_quota(who: &T::AccountId, asset: &Asset) -> DispatchResult {
+			let amount = match asset.fun {
+				Fungibility::Fungible(amount) => amount,
+				Fungibility::NonFungible(_) => 1,
+			};
+
+			let rate_limiter_id = T::RateLimiterId::get();
+
+			// try consume quota of the rate limiter.
+			T::RateLimiter::try_consume(rate_limiter_id, asset.id.clone(), amount, Some(who))
+				.map_err(|_| Error::<T>::RateLimited.into())
+		}
+
 		/// Execute and send xcm with given assets and fee to dest chain or
 		/// reserve chain.
 		fn execute_and_send_reserve_kind_xcm(
@@ -672,7 +1087,7 @@ pub mod module {
 				Some(recipient) => recipient,
 				None => recipient,
 			};
-			let mut msg = match transfer_kind {
+			let msg = match transfer_kind {
 				SelfReserveAsset => Self::transfer_self_reserve_asset(assets, fee, dest, recipient, dest_weight_limit)?,
 				ToReserve => Self::transfer_to_reserve(assets, fee, dest, recipient, dest_weight_limit)?,
 				ToNonReserve => Self::transfer_to_non_reserve(
@@ -685,6 +1100,129 @@ pub mod module {
 					use_teleport,
 				)?,
 			};
+
+			Self::execute_xcm(origin_location, msg)
+		}
+
+		/// Execute and send xcm which forwards the assets through every hop of
+		/// `route` before depositing them on the dest chain.
+		///
+		/// Each chain forwards the assets the way their reserve allows: a
+		/// reserve sends them on with `DepositReserveAsset`, or with
+		/// `InitiateTeleport` if its hop asks for it, while a chain holding
+		/// derivatives can only send them back to the chain backing them with
+		/// `InitiateReserveWithdraw`. Any other route is invalid.
+		fn execute_and_send_routed_xcm(
+			origin_location: Location,
+			assets: Assets,
+			fee: Asset,
+			reserve: Option<Location>,
+			dest: &Location,
+			route: Vec<RouteHop>,
+			dest_weight_limit: WeightLimit,
+		) -> DispatchResult {
+			let (transfer_kind, dest, reserve, recipient) = Self::transfer_kind(reserve, dest)?;
+			let first_hop = route.first().ok_or(Error::<T>::InvalidRoute)?;
+			// the chain backing the assets held by the current hop, `None` if the
+			// hop is a reserve of them
+			let mut backing = match transfer_kind {
+				SelfReserveAsset => Some(T::SelfLocation::get()),
+				ToReserve | ToNonReserve => {
+					ensure!(first_hop.chain == reserve, Error::<T>::InvalidRoute);
+					None
+				}
+			};
+
+			let total_fee = fungible_amount(&fee);
+			let mut legs = Vec::with_capacity(route.len());
+			for (i, hop) in route.iter().enumerate() {
+				let hop_fee = hop.fee_share.mul_floor(total_fee);
+				ensure!(!hop_fee.is_zero(), Error::<T>::FeeNotEnough);
+
+				let next = route.get(i + 1).map_or(&dest, |next_hop| &next_hop.chain);
+				let transfer_type = match backing {
+					None if hop.teleport => TransferType::Teleport,
+					None => {
+						backing = Some(hop.chain.clone());
+						TransferType::LocalReserve
+					}
+					Some(ref chain) if chain == next && !hop.teleport => {
+						backing = None;
+						TransferType::DestinationReserve
+					}
+					Some(_) => return Err(Error::<T>::InvalidRoute.into()),
+				};
+				legs.push((hop_fee, transfer_type));
+			}
+			let dest_fee = legs
+				.iter()
+				.try_fold(total_fee, |left, (hop_fee, _)| left.checked_sub(*hop_fee))
+				.filter(|left| !left.is_zero())
+				.ok_or(Error::<T>::InvalidRoute)?;
+
+			// Build the message from the dest chain backwards, every hop forwarding the
+			// assets to the next chain of the route.
+			let ancestry = T::UniversalLocation::get();
+			let max_assets = assets.len() as u32;
+			let mut xcm = Xcm(vec![
+				Self::buy_execution(with_amount(&fee, dest_fee), &dest, dest_weight_limit)?,
+				Self::deposit_asset(recipient, max_assets),
+			]);
+			let mut next = dest;
+			for (hop, (hop_fee, transfer_type)) in route.into_iter().zip(legs).rev() {
+				let next_from_hop = next
+					.reanchored(&hop.chain, &ancestry)
+					.map_err(|_| Error::<T>::CannotReanchor)?;
+				let assets = AllCounted(max_assets).into();
+				let forward = match transfer_type {
+					TransferType::Teleport => InitiateTeleport {
+						assets,
+						dest: next_from_hop,
+						xcm,
+					},
+					TransferType::DestinationReserve => InitiateReserveWithdraw {
+						assets,
+						reserve: next_from_hop,
+						xcm,
+					},
+					_ => DepositReserveAsset {
+						assets,
+						dest: next_from_hop,
+						xcm,
+					},
+				};
+				xcm = Xcm(vec![
+					Self::buy_execution(with_amount(&fee, hop_fee), &hop.chain, hop.weight_limit)?,
+					forward,
+				]);
+				next = hop.chain;
+			}
+
+			let msg = match transfer_kind {
+				SelfReserveAsset => Xcm(vec![
+					SetFeesMode { jit_withdraw: true },
+					TransferReserveAsset {
+						assets,
+						dest: next,
+						xcm,
+					},
+				]),
+				ToReserve | ToNonReserve => Xcm(vec![
+					WithdrawAsset(assets),
+					SetFeesMode { jit_withdraw: true },
+					InitiateReserveWithdraw {
+						assets: All.into(),
+						reserve: next,
+						xcm,
+					},
+				]),
+			};
+
+			Self::execute_xcm(origin_location, msg)
+		}
+
+		/// Execute the transfer message locally, which sends it on.
+		fn execute_xcm(origin_location: Location, mut msg: Xcm<T::RuntimeCall>) -> DispatchResult {
 			let mut hash = msg.using_encoded(sp_io::hashing::blake2_256);
 
 			let weight = T::Weigher::weight(&mut msg, Weight::MAX).map_err(|_| Error::<T>::UnweighableMessage)?;
@@ -801,6 +1339,17 @@ pub mod module {
 			}
 		}
 
+		/// Returns the route configured for the `fee` asset, cut short before
+		/// the dest chain. Returns `None` if there are no hops left.
+		fn asset_route(fee: &Asset, dest: &Location) -> Option<Vec<RouteHop>> {
+			let dest_chain = chain_part(dest);
+			let route: Vec<RouteHop> = T::AssetRoutes::get(&fee.id.0)?
+				.into_iter()
+				.take_while(|hop| Some(&hop.chain) != dest_chain.as_ref())
+				.collect();
+			(!route.is_empty()).then_some(route)
+		}
+
 		fn deposit_asset(recipient: Location, max_assets: u32) -> Instruction<()> {
 			DepositAsset {
 				assets: AllCounted(max_assets).into(),
@@ -876,47 +1425,24 @@ pub mod module {
 	impl<T: Config> XtokensWeightInfo<T::AccountId, T::Balance, T::CurrencyId> for XtokensWeight<T> {
 		/// Returns weight of `transfer_multiasset` call.
 		fn weight_of_transfer_multiasset(asset: &VersionedAsset, dest: &VersionedLocation) -> Weight {
-			let asset: Result<Asset, _> = asset.clone().try_into();
-			let dest = dest.clone().try_into();
-			if let (Ok(asset), Ok(dest)) = (asset, dest) {
-				if let Ok((transfer_kind, dest, _, reserve)) =
-					Pallet::<T>::transfer_kind(T::ReserveProvider::reserve(&asset), &dest)
-				{
-					let mut msg = match transfer_kind {
-						SelfReserveAsset => Xcm(vec![
-							SetFeesMode { jit_withdraw: true },
-							TransferReserveAsset {
-								assets: vec![asset].into(),
-								dest,
-								xcm: Xcm(vec![]),
-							},
-						]),
-						ToReserve | ToNonReserve => Xcm(vec![
-							WithdrawAsset(Assets::from(asset)),
-							SetFeesMode { jit_withdraw: true },
-							InitiateReserveWithdraw {
-								assets: All.into(),
-								// `dest` is always (equal to) `reserve` in both cases
-								reserve,
-								xcm: Xcm(vec![]),
-							},
-						]),
-					};
-					return T::Weigher::weight(&mut msg, Weight::MAX)
-						.map_or(Weight::max_value(), |w| T::BaseXcmWeight::get().saturating_add(w));
-				}
-			}
-			Weight::zero()
+			Self::weigh_transfer_multiasset(asset, dest).map_or(Weight::zero(), |(transfer_kind, weight)| {
+				weight.max(match transfer_kind {
+					SelfReserveAsset => T::WeightInfo::transfer_multiasset_self_reserve(),
+					ToReserve => T::WeightInfo::transfer_multiasset_to_reserve(),
+					ToNonReserve => T::WeightInfo::transfer_multiasset_to_non_reserve(),
+				})
+			})
 		}
 
 		/// Returns weight of `transfer` call.
 		fn weight_of_transfer(currency_id: T::CurrencyId, amount: T::Balance, dest: &VersionedLocation) -> Weight {
-			if let Some(location) = T::CurrencyIdConvert::convert(currency_id) {
-				let asset = (location, amount.into()).into();
-				Self::weight_of_transfer_multiasset(&asset, dest)
-			} else {
-				Weight::zero()
-			}
+			Self::weigh_transfer(currency_id, amount, dest).map_or(Weight::zero(), |(transfer_kind, weight)| {
+				weight.max(match transfer_kind {
+					SelfReserveAsset => T::WeightInfo::transfer_self_reserve(),
+					ToReserve => T::WeightInfo::transfer_to_reserve(),
+					ToNonReserve => T::WeightInfo::transfer_to_non_reserve(),
+				})
+			})
 		}
 
 		/// Returns weight of `transfer` call.
@@ -935,7 +1461,16 @@ pub mod module {
 				}
 			}
 
-			Self::weight_of_transfer_multiassets(&VersionedAssets::from(Assets::from(assets)), fee_item, dest)
+			Self::weigh_transfer_multiassets(&VersionedAssets::from(Assets::from(assets)), fee_item, dest).map_or(
+				Weight::zero(),
+				|(transfer_kind, weight)| {
+					weight.max(match transfer_kind {
+						SelfReserveAsset => T::WeightInfo::transfer_multicurrencies_self_reserve(),
+						ToReserve => T::WeightInfo::transfer_multicurrencies_to_reserve(),
+						ToNonReserve => T::WeightInfo::transfer_multicurrencies_to_non_reserve(),
+					})
+				},
+			)
 		}
 
 		/// Returns weight of `transfer_multiassets` call.
@@ -943,38 +1478,145 @@ pub mod module {
 			assets: &VersionedAssets,
 			fee_item: &u32,
 			dest: &VersionedLocation,
+		) -> Weight {
+			Self::weigh_transfer_multiassets(assets, fee_item, dest).map_or(
+				Weight::zero(),
+				|(transfer_kind, weight)| {
+					weight.max(match transfer_kind {
+						SelfReserveAsset => T::WeightInfo::transfer_multiassets_self_reserve(),
+						ToReserve => T::WeightInfo::transfer_multiassets_to_reserve(),
+						ToNonReserve => T::WeightInfo::transfer_multiassets_to_non_reserve(),
+					})
+				},
+			)
+		}
+	}
+
+	impl<T: Config> XtokensWeight<T> {
+		/// Returns weight of `transfer_with_fee` call.
+		pub fn weight_of_transfer_with_fee(
+			currency_id: T::CurrencyId,
+			amount: T::Balance,
+			dest: &VersionedLocation,
+		) -> Weight {
+			Self::weigh_transfer(currency_id, amount, dest).map_or(Weight::zero(), |(_, weight)| {
+				weight.max(T::WeightInfo::transfer_with_fee())
+			})
+		}
+
+		/// Returns weight of `transfer_with_fee_swap` call.
+		pub fn weight_of_transfer_with_fee_swap(
+			currency_id: T::CurrencyId,
+			amount: T::Balance,
+			dest: &VersionedLocation,
+		) -> Weight {
+			Self::weigh_transfer(currency_id, amount, dest).map_or(Weight::zero(), |(_, weight)| {
+				weight.max(T::WeightInfo::transfer_with_fee_swap())
+			})
+		}
+
+		/// Returns weight of `transfer_multiasset_with_fee` call.
+		pub fn weight_of_transfer_multiasset_with_fee(asset: &VersionedAsset, dest: &VersionedLocation) -> Weight {
+			Self::weigh_transfer_multiasset(asset, dest).map_or(Weight::zero(), |(_, weight)| {
+				weight.max(T::WeightInfo::transfer_multiasset_with_fee())
+			})
+		}
+
+		/// Returns weight of `transfer_assets_using_type_and_then` call.
+		pub fn weight_of_transfer_assets_using_type_and_then(
+			assets: &VersionedAssets,
+			transfer_type: &TransferType,
+			dest: &VersionedLocation,
 		) -> Weight {
 			let assets: Result<Assets, ()> = assets.clone().try_into();
-			let dest = dest.clone().try_into();
+			let dest: Result<Location, ()> = dest.clone().try_into();
 			if let (Ok(assets), Ok(dest)) = (assets, dest) {
-				let reserve_location = Pallet::<T>::get_reserve_location(&assets, fee_item);
-				if let Ok((transfer_kind, dest, _, reserve)) = Pallet::<T>::transfer_kind(reserve_location, &dest) {
-					let mut msg = match transfer_kind {
-						SelfReserveAsset => Xcm(vec![
-							SetFeesMode { jit_withdraw: true },
-							TransferReserveAsset {
-								assets,
-								dest,
-								xcm: Xcm(vec![]),
-							},
-						]),
-						ToReserve | ToNonReserve => Xcm(vec![
-							WithdrawAsset(assets),
-							SetFeesMode { jit_withdraw: true },
-							InitiateReserveWithdraw {
-								assets: All.into(),
-								// `dest` is always (equal to) `reserve` in both cases
-								reserve,
-								xcm: Xcm(vec![]),
-							},
-						]),
-					};
-					return T::Weigher::weight(&mut msg, Weight::MAX)
-						.map_or(Weight::max_value(), |w| T::BaseXcmWeight::get().saturating_add(w));
-				}
+				let chain = match transfer_type {
+					TransferType::RemoteReserve(reserve) => match reserve.clone().try_into() {
+						Ok(reserve) => reserve,
+						Err(()) => return Weight::zero(),
+					},
+					_ => dest,
+				};
+				let msg = Pallet::<T>::typed_transfer_message(assets, transfer_type, chain, Xcm(vec![]));
+				return Self::weigh_message(msg).max(T::WeightInfo::transfer_assets_using_type_and_then());
 			}
 			Weight::zero()
 		}
+
+		/// Returns the transfer kind and the local XCM execution weight of
+		/// transferring `currency_id`.
+		fn weigh_transfer(
+			currency_id: T::CurrencyId,
+			amount: T::Balance,
+			dest: &VersionedLocation,
+		) -> Option<(TransferKind, Weight)> {
+			let location = T::CurrencyIdConvert::convert(currency_id)?;
+			let asset = (location, amount.into()).into();
+			Self::weigh_transfer_multiasset(&asset, dest)
+		}
+
+		/// Returns the transfer kind and the local XCM execution weight of
+		/// transferring `asset`.
+		fn weigh_transfer_multiasset(
+			asset: &VersionedAsset,
+			dest: &VersionedLocation,
+		) -> Option<(TransferKind, Weight)> {
+			let asset: Asset = asset.clone().try_into().ok()?;
+			let reserve = T::ReserveProvider::reserve(&asset);
+			Self::weigh_reserve_transfer(asset.into(), reserve, dest)
+		}
+
+		/// Returns the transfer kind and the local XCM execution weight of
+		/// transferring `assets`.
+		fn weigh_transfer_multiassets(
+			assets: &VersionedAssets,
+			fee_item: &u32,
+			dest: &VersionedLocation,
+		) -> Option<(TransferKind, Weight)> {
+			let assets: Assets = assets.clone().try_into().ok()?;
+			let reserve = Pallet::<T>::get_reserve_location(&assets, fee_item);
+			Self::weigh_reserve_transfer(assets, reserve, dest)
+		}
+
+		/// Returns the transfer kind and the local XCM execution weight of
+		/// transferring `assets` of `reserve` to `dest`.
+		fn weigh_reserve_transfer(
+			assets: Assets,
+			reserve: Option<Location>,
+			dest: &VersionedLocation,
+		) -> Option<(TransferKind, Weight)> {
+			let dest: Location = dest.clone().try_into().ok()?;
+			let (transfer_kind, dest, _, reserve) = Pallet::<T>::transfer_kind(reserve, &dest).ok()?;
+			let msg = match transfer_kind {
+				SelfReserveAsset => Xcm(vec![
+					SetFeesMode { jit_withdraw: true },
+					TransferReserveAsset {
+						assets,
+						dest,
+						xcm: Xcm(vec![]),
+					},
+				]),
+				ToReserve | ToNonReserve => Xcm(vec![
+					WithdrawAsset(assets),
+					SetFeesMode { jit_withdraw: true },
+					InitiateReserveWithdraw {
+						assets: All.into(),
+						// `dest` is always (equal to) `reserve` in both cases
+						reserve,
+						xcm: Xcm(vec![]),
+					},
+				]),
+			};
+			Some((transfer_kind, Self::weigh_message(msg)))
+		}
+
+		/// Returns the local XCM execution weight of `msg`, including
+		/// `BaseXcmWeight`.
+		fn weigh_message(mut msg: Xcm<T::RuntimeCall>) -> Weight {
+			T::Weigher::weight(&mut msg, Weight::MAX)
+				.map_or(Weight::max_value(), |w| T::BaseXcmWeight::get().saturating_add(w))
+		}
 	}
 
 	impl<T: Config> XcmTransfer<T::AccountId, T::Balance, T::CurrencyId> for Pallet<T> {
@@ -1065,6 +1707,13 @@ fn half(asset: &Asset) -> Asset {
 	}
 }
 
+fn with_amount(asset: &Asset, amount: u128) -> Asset {
+	Asset {
+		fun: Fungible(amount),
+		id: asset.id.clone(),
+	}
+}
+
 fn subtract_fee(asset: &Asset, amount: u128) -> Asset {
 	let final_amount = fungible_amount(asset).checked_sub(amount).expect("fee too low; qed");
 	Asset {
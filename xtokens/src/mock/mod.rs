@@ -25,6 +25,11 @@ pub const ALICE: AccountId32 = AccountId32::new([0u8; 32]);
 pub const BOB: AccountId32 = AccountId32::new([1u8; 32]);
 pub const CHARLIE: AccountId32 = AccountId32::new([2u8; 32]);
 
+/// The pallet index of `orml_nft` in `para::Runtime`.
+pub const NFT_PALLET_INDEX: u8 = 50;
+/// The nft class created on each parachain at genesis.
+pub const NFT_CLASS: u32 = 0;
+
 parameter_types! {
 	pub KsmLocation: Location = Parent.into();
 }
@@ -263,12 +268,15 @@ decl_test_network! {
 pub type ParaTokens = orml_tokens::Pallet<para::Runtime>;
 pub type ParaXTokens = orml_xtokens::Pallet<para::Runtime>;
 pub type AssetHubBalances = pallet_balances::Pallet<asset_hub::Runtime>;
+pub type RelayBalances = pallet_balances::Pallet<relay::Runtime>;
 
 pub type ParaRelativeTokens = orml_tokens::Pallet<para_relative_view::Runtime>;
 pub type ParaRelativeXTokens = orml_xtokens::Pallet<para_relative_view::Runtime>;
 
 pub type ParaTeleportTokens = orml_tokens::Pallet<para_teleport::Runtime>;
 
+pub type ParaNft = orml_nft::Pallet<para::Runtime>;
+
 pub fn para_ext(para_id: u32) -> TestExternalities {
 	use para::{MsgQueue, Runtime, System};
 
@@ -286,6 +294,7 @@ pub fn para_ext(para_id: u32) -> TestExternalities {
 	ext.execute_with(|| {
 		System::set_block_number(1);
 		MsgQueue::set_para_id(para_id.into());
+		assert_eq!(ParaNft::create_class(&ALICE, vec![], ()), Ok(NFT_CLASS));
 	});
 	ext
 }
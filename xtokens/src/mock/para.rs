@@ -1,12 +1,13 @@
 use super::{
 	AbsoluteReserveProvider, AllowTopLevelPaidExecution, Amount, Balance, CurrencyId, CurrencyIdConvert,
-	ParachainXcmRouter, RateLimiter, CHARLIE,
+	ParachainXcmRouter, RateLimiter, CHARLIE, NFT_CLASS, NFT_PALLET_INDEX,
 };
 use crate as orml_xtokens;
 
 use frame_support::{
-	construct_runtime, derive_impl, ensure, parameter_types,
-	traits::{ConstU128, ConstU32, Contains, ContainsPair, Everything, Get, Nothing},
+	construct_runtime, derive_impl, ensure, parameter_types, storage_alias,
+	traits::{ConstU128, ConstU32, Contains, ContainsPair, Everything, ExistenceRequirement, Get, Nothing},
+	Blake2_128Concat, PalletId, Twox64Concat,
 };
 use frame_system::EnsureRoot;
 use pallet_xcm::XcmPassthrough;
@@ -14,8 +15,8 @@ use parachains_common::xcm_config::ConcreteAssetFromSystem;
 use parity_scale_codec::Encode;
 use polkadot_parachain_primitives::primitives::Sibling;
 use sp_runtime::{
-	traits::{Convert, IdentityLookup},
-	AccountId32,
+	traits::{AccountIdConversion, Convert, IdentityLookup},
+	AccountId32, DispatchError,
 };
 use sp_std::{cell::RefCell, marker::PhantomData};
 use xcm::v5::{prelude::*, Weight};
@@ -29,9 +30,11 @@ use xcm_executor::{Config, XcmExecutor};
 use crate::mock::{AllTokensAreCreatedEqualToWeight, KsmLocation};
 use orml_traits::{
 	location::{Reserve, ASSET_HUB_ID},
-	parameter_type_with_key, RateLimiterError,
+	parameter_type_with_key,
+	xcm_transfer::FeeSwap,
+	MultiCurrency, RateLimiterError, WeightToFeeConverter,
 };
-use orml_xcm_support::{IsNativeConcrete, MultiCurrencyAdapter};
+use orml_xcm_support::{DisabledAssetRoutes, IsNativeConcrete, MultiCurrencyAdapter, NonFungibleAdapter};
 
 pub type AccountId = AccountId32;
 
@@ -111,6 +114,49 @@ pub type LocalAssetTransactor = MultiCurrencyAdapter<
 	CurrencyId,
 	CurrencyIdConvert,
 	(),
+	MockRateLimiter,
+	XtokensRateLimiterId,
+>;
+
+impl orml_nft::Config for Runtime {
+	type ClassId = u32;
+	type TokenId = u64;
+	type ClassData = ();
+	type TokenData = ();
+	type MaxClassMetadata = ConstU32<1>;
+	type MaxTokenMetadata = ConstU32<1>;
+}
+
+parameter_types! {
+	pub NftCheckingAccount: AccountId = PalletId(*b"orml/nft").into_account_truncating();
+}
+
+/// The nft class `NFT_CLASS` of `ParaA`, which is a derivative class on the
+/// other parachains.
+pub struct NftClassIdConvert;
+impl Convert<Location, Option<u32>> for NftClassIdConvert {
+	fn convert(location: Location) -> Option<u32> {
+		let class = GeneralIndex(NFT_CLASS.into());
+		match location.unpack() {
+			(1, [Parachain(1), PalletInstance(NFT_PALLET_INDEX), index]) if *index == class => Some(NFT_CLASS),
+			(0, [PalletInstance(NFT_PALLET_INDEX), index]) if *index == class && MsgQueue::get() == 1.into() => {
+				Some(NFT_CLASS)
+			}
+			_ => None,
+		}
+	}
+}
+
+#[storage_alias(pallet_name)]
+pub type NftDerivativeTokens = StorageDoubleMap<Nft, Twox64Concat, u32, Blake2_128Concat, u128, u64>;
+
+pub type NftTransactor = NonFungibleAdapter<
+	Runtime,
+	NftClassIdConvert,
+	LocationToAccountId,
+	NftCheckingAccount,
+	SelfLocation,
+	NftDerivativeTokens,
 >;
 
 pub type XcmRouter = ParachainXcmRouter<MsgQueue>;
@@ -146,7 +192,7 @@ pub struct XcmConfig;
 impl Config for XcmConfig {
 	type RuntimeCall = RuntimeCall;
 	type XcmSender = XcmRouter;
-	type AssetTransactor = LocalAssetTransactor;
+	type AssetTransactor = (NftTransactor, LocalAssetTransactor);
 	type OriginConverter = XcmOriginToCallOrigin;
 	type IsReserve = MultiNativeAsset<AbsoluteReserveProvider>;
 	type IsTeleporter = ConcreteAssetFromSystem<KsmLocation>;
@@ -239,6 +285,12 @@ impl Contains<Location> for ParentOrParachains {
 				| (1, [Parachain(4), Junction::AccountId32 { .. }])
 				| (1, [Parachain(100), Junction::AccountId32 { .. }])
 				| (1, [Parachain(ASSET_HUB_ID), Junction::AccountId32 { .. }])
+				| (1, [Parachain(1)])
+				| (1, [Parachain(2)])
+				| (1, [Parachain(3)])
+				| (1, [Parachain(4)])
+				| (1, [Parachain(100)])
+				| (1, [Parachain(ASSET_HUB_ID)])
 		)
 	}
 }
@@ -298,6 +350,104 @@ parameter_types! {
 	pub const XtokensRateLimiterId: u8 = 0;
 }
 
+/// Units of each currency charged per unit of weight, which `MockFeeSwap`
+/// also swaps at.
+fn fee_rate(currency_id: CurrencyId) -> Option<u128> {
+	match currency_id {
+		CurrencyId::R | CurrencyId::B => Some(1),
+		CurrencyId::A => Some(2),
+		_ => None,
+	}
+}
+
+pub struct MockFeeConverter;
+impl WeightToFeeConverter for MockFeeConverter {
+	fn convert_weight_to_fee(location: &Location, weight: Weight) -> Option<u128> {
+		let currency_id = <CurrencyIdConvert as Convert<Location, Option<CurrencyId>>>::convert(location.clone())?;
+		fee_rate(currency_id).map(|rate| rate.saturating_mul(weight.ref_time() as u128))
+	}
+}
+
+pub struct MockFeeSwap;
+impl FeeSwap<AccountId, CurrencyId, Balance> for MockFeeSwap {
+	fn swap_for_exact_target(
+		who: &AccountId,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		max_supply_amount: Balance,
+		target_amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		let supply_rate = fee_rate(supply_currency_id).ok_or(DispatchError::Other("no rate"))?;
+		let target_rate = fee_rate(target_currency_id).ok_or(DispatchError::Other("no rate"))?;
+		let supply_amount = target_amount.saturating_mul(supply_rate).div_ceil(target_rate);
+		ensure!(supply_amount <= max_supply_amount, DispatchError::Other("slippage"));
+
+		Tokens::withdraw(supply_currency_id, who, supply_amount, ExistenceRequirement::AllowDeath)?;
+		Tokens::deposit(target_currency_id, who, target_amount)?;
+		Ok(supply_amount)
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct XtokensBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl orml_xtokens::BenchmarkHelper<AccountId, CurrencyId, Balance> for XtokensBenchmarkHelper {
+	fn transfer_case(path: orml_xtokens::TransferPath) -> Option<(CurrencyId, Balance, Location)> {
+		use orml_xtokens::TransferPath;
+
+		// benchmarks run as `ParaA`, sending to `BOB` on `ParaB`
+		let currency_id = match path {
+			TransferPath::SelfReserve => CurrencyId::A,
+			TransferPath::ToReserve => CurrencyId::B,
+			TransferPath::ToNonReserve => CurrencyId::R,
+		};
+		Some((currency_id, 500, sibling_b_bob()))
+	}
+
+	fn multi_transfer_case(path: orml_xtokens::TransferPath) -> Option<(Vec<(CurrencyId, Balance)>, Location)> {
+		use orml_xtokens::TransferPath;
+
+		Some(match path {
+			TransferPath::SelfReserve => (vec![(CurrencyId::A, 500), (CurrencyId::A1, 500)], sibling_b_bob()),
+			TransferPath::ToReserve => (vec![(CurrencyId::B, 500), (CurrencyId::B1, 500)], sibling_b_bob()),
+			// reserved on `ParaB`, sent to `ParaC`
+			TransferPath::ToNonReserve => (
+				vec![(CurrencyId::B, 500), (CurrencyId::B1, 500)],
+				(
+					Parent,
+					Parachain(3),
+					Junction::AccountId32 {
+						network: None,
+						id: super::BOB.into(),
+					},
+				)
+					.into(),
+			),
+		})
+	}
+
+	fn fee_swap_case() -> Option<CurrencyId> {
+		Some(CurrencyId::A)
+	}
+
+	fn fund(who: &AccountId, currency_id: CurrencyId, amount: Balance) {
+		frame_support::assert_ok!(<Tokens as MultiCurrency<AccountId>>::deposit(currency_id, who, amount));
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+fn sibling_b_bob() -> Location {
+	(
+		Parent,
+		Parachain(2),
+		Junction::AccountId32 {
+			network: None,
+			id: super::BOB.into(),
+		},
+	)
+		.into()
+}
+
 impl orml_xtokens::Config for Runtime {
 	type Balance = Balance;
 	type CurrencyId = CurrencyId;
@@ -305,6 +455,7 @@ impl orml_xtokens::Config for Runtime {
 	type AccountIdToLocation = AccountIdToLocation;
 	type SelfLocation = SelfLocation;
 	type LocationsFilter = ParentOrParachains;
+	type TeleportFilter = ConcreteAssetFromSystem<KsmLocation>;
 	type MinXcmFee = ParachainMinFee;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
@@ -314,10 +465,26 @@ impl orml_xtokens::Config for Runtime {
 	type ReserveProvider = AbsoluteReserveProvider;
 	type RateLimiter = MockRateLimiter;
 	type RateLimiterId = XtokensRateLimiterId;
+	type AssetRoutes = DisabledAssetRoutes;
+	type FeeConverter = MockFeeConverter;
+	type FeeSwap = MockFeeSwap;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = XtokensBenchmarkHelper;
+}
+
+parameter_types! {
+	pub const TransactDescendOrigin: InteriorLocation = Here;
 }
 
 impl orml_xcm::Config for Runtime {
 	type SovereignOrigin = EnsureRoot<AccountId>;
+	type ResponseOrigin = pallet_xcm::EnsureResponse<Everything>;
+	type TransactDescendOrigin = TransactDescendOrigin;
+	type RuntimeCall = RuntimeCall;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 }
 
 impl orml_xcm_mock_message_queue::Config for Runtime {
@@ -339,5 +506,7 @@ construct_runtime!(
 
 		PolkadotXcm: pallet_xcm,
 		OrmlXcm: orml_xcm,
+
+		Nft: orml_nft = 50,
 	}
 );
@@ -15,7 +15,7 @@ use parachains_common::xcm_config::ConcreteAssetFromSystem;
 use polkadot_parachain_primitives::primitives::Sibling;
 use sp_runtime::{
 	traits::{Convert, IdentityLookup},
-	AccountId32, BoundedVec,
+	AccountId32, BoundedVec, Perbill,
 };
 use sp_std::marker::PhantomData;
 use xcm::v5::{prelude::*, Weight};
@@ -27,7 +27,7 @@ use xcm_builder::{
 use xcm_executor::{Config, XcmExecutor};
 
 use crate::mock::{AllTokensAreCreatedEqualToWeight, KsmLocation};
-use orml_traits::{location::Reserve, parameter_type_with_key};
+use orml_traits::{location::Reserve, parameter_type_with_key, xcm_transfer::RouteHop};
 use orml_xcm_support::{IsNativeConcrete, MultiCurrencyAdapter};
 
 pub type AccountId = AccountId32;
@@ -371,6 +371,42 @@ parameter_type_with_key! {
 	};
 }
 
+fn route_hop(chain: Location, fee_share: u32, teleport: bool) -> RouteHop {
+	RouteHop {
+		chain,
+		fee_share: Perbill::from_percent(fee_share),
+		weight_limit: WeightLimit::Unlimited,
+		teleport,
+	}
+}
+
+parameter_type_with_key! {
+	pub AssetRoutes: |location: Location| -> Option<Vec<RouteHop>> {
+		let currency_id = RelativeCurrencyIdConvert::convert(location.clone())?;
+		let asset_hub = Location::new(1, [Parachain(ASSET_HUB_ID)]);
+		let para_a = Location::new(1, [Parachain(1)]);
+		let para_b = Location::new(1, [Parachain(2)]);
+		match currency_id {
+			// asset hub teleports relay chain asset to the relay chain, which is a reserve of it
+			CurrencyId::R => Some(vec![route_hop(asset_hub, 20, true), route_hop(Location::parent(), 20, false)]),
+			// parachain 1 cannot execute anything with this weight limit
+			CurrencyId::A => Some(vec![RouteHop {
+				weight_limit: WeightLimit::Limited(Weight::from_parts(1, 1)),
+				..route_hop(para_a, 50, false)
+			}]),
+			// the first hop is not the reserve of A1
+			CurrencyId::A1 => Some(vec![route_hop(para_b, 50, false)]),
+			// parachain 1 holds derivatives of B, which it can only send back to parachain 2
+			CurrencyId::B => Some(vec![route_hop(para_b, 20, false), route_hop(para_a, 20, false)]),
+			// parachain 1 holds derivatives of B1, which it cannot teleport
+			CurrencyId::B1 => Some(vec![route_hop(para_b, 20, false), route_hop(para_a, 20, true)]),
+			// nothing left for the dest chain
+			CurrencyId::B2 => Some(vec![route_hop(para_b, 100, false)]),
+			_ => None,
+		}
+	};
+}
+
 impl orml_xtokens::Config for Runtime {
 	type Balance = Balance;
 	type CurrencyId = CurrencyId;
@@ -378,6 +414,7 @@ impl orml_xtokens::Config for Runtime {
 	type AccountIdToLocation = AccountIdToLocation;
 	type SelfLocation = SelfLocation;
 	type LocationsFilter = ParentOrParachains;
+	type TeleportFilter = ConcreteAssetFromSystem<KsmLocation>;
 	type MinXcmFee = ParachainMinFee;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
@@ -387,10 +424,26 @@ impl orml_xtokens::Config for Runtime {
 	type ReserveProvider = RelativeReserveProvider;
 	type RateLimiter = ();
 	type RateLimiterId = ();
+	type AssetRoutes = AssetRoutes;
+	type FeeConverter = ();
+	type FeeSwap = ();
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+parameter_types! {
+	pub const TransactDescendOrigin: InteriorLocation = Here;
 }
 
 impl orml_xcm::Config for Runtime {
 	type SovereignOrigin = EnsureRoot<AccountId>;
+	type ResponseOrigin = pallet_xcm::EnsureResponse<Everything>;
+	type TransactDescendOrigin = TransactDescendOrigin;
+	type RuntimeCall = RuntimeCall;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 }
 
 impl orml_xcm_mock_message_queue::Config for Runtime {
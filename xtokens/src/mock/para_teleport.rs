@@ -28,7 +28,7 @@ use crate::mock::{
 	teleport_currency_adapter::MultiTeleportCurrencyAdapter, AllTokensAreCreatedEqualToWeight, KsmLocation,
 };
 use orml_traits::parameter_type_with_key;
-use orml_xcm_support::{DisabledParachainFee, IsNativeConcrete, MultiNativeAsset};
+use orml_xcm_support::{DisabledAssetRoutes, DisabledParachainFee, IsNativeConcrete, MultiNativeAsset};
 
 pub type AccountId = AccountId32;
 
@@ -227,6 +227,7 @@ impl orml_xtokens::Config for Runtime {
 	type AccountIdToLocation = AccountIdToLocation;
 	type SelfLocation = SelfLocation;
 	type LocationsFilter = ParentOrParachains;
+	type TeleportFilter = ConcreteAssetFromSystem<KsmLocation>;
 	type MinXcmFee = DisabledParachainFee;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
@@ -236,10 +237,26 @@ impl orml_xtokens::Config for Runtime {
 	type ReserveProvider = AbsoluteReserveProvider;
 	type RateLimiter = ();
 	type RateLimiterId = ();
+	type AssetRoutes = DisabledAssetRoutes;
+	type FeeConverter = ();
+	type FeeSwap = ();
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+parameter_types! {
+	pub const TransactDescendOrigin: InteriorLocation = Here;
 }
 
 impl orml_xcm::Config for Runtime {
 	type SovereignOrigin = EnsureRoot<AccountId>;
+	type ResponseOrigin = pallet_xcm::EnsureResponse<Everything>;
+	type TransactDescendOrigin = TransactDescendOrigin;
+	type RuntimeCall = RuntimeCall;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 }
 
 impl orml_xcm_mock_message_queue::Config for Runtime {
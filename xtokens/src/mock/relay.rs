@@ -6,7 +6,6 @@ use frame_support::{
 use frame_system::EnsureRoot;
 use sp_runtime::{traits::IdentityLookup, AccountId32};
 
-use crate::mock::KsmLocation;
 use crate::{Weight, ASSET_HUB_ID};
 use cumulus_primitives_core::ParaId;
 use polkadot_runtime_parachains::{
@@ -59,6 +58,7 @@ impl configuration::Config for Runtime {
 }
 
 parameter_types! {
+	pub KsmLocation: Location = Here.into();
 	pub const KusamaNetwork: NetworkId = NetworkId::Kusama;
 	pub UniversalLocation: InteriorLocation = [GlobalConsensus(KusamaNetwork::get())].into();
 }
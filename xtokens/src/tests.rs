@@ -2,13 +2,14 @@
 
 use super::*;
 use frame_support::traits::ContainsPair;
-use frame_support::{assert_err, assert_noop, assert_ok, traits::Currency};
+use frame_support::{assert_err, assert_noop, assert_ok, parameter_types, traits::Currency};
 use mock::*;
-use orml_traits::{ConcreteFungibleAsset, MultiCurrency};
-use orml_xcm_support::MultiNativeAsset;
+use orml_traits::{ConcreteFungibleAsset, Happened, MultiCurrency};
+use orml_xcm_support::{DepositToAlternative, MultiNativeAsset, OnDepositFail};
 use parity_scale_codec::Encode;
-use polkadot_parachain_primitives::primitives::Sibling;
+use polkadot_parachain_primitives::primitives::{Id as ParaId, Sibling};
 use sp_runtime::{traits::AccountIdConversion, AccountId32};
+use std::cell::RefCell;
 use xcm::{v5::OriginKind::SovereignAccount, VersionedXcm};
 use xcm_simulator::TestExt;
 
@@ -28,6 +29,10 @@ fn sibling_d_account() -> AccountId32 {
 	Sibling::from(4).into_account_truncating()
 }
 
+fn para_a_account() -> AccountId32 {
+	ParaId::from(1).into_account_truncating()
+}
+
 // Not used in any unit tests, but it's super helpful for debugging. Let's
 // keep it here.
 #[allow(dead_code)]
@@ -338,6 +343,127 @@ fn send_sibling_asset_to_reserve_sibling_with_fee() {
 	});
 }
 
+#[test]
+fn send_sibling_asset_to_reserve_sibling_with_fee_swap() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::B, &ALICE, 450));
+		assert_ok!(ParaTokens::deposit(CurrencyId::A, &ALICE, 1_000));
+	});
+
+	ParaB::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::B, &sibling_a_account(), 1_000));
+	});
+
+	ParaA::execute_with(|| {
+		let fee_location = CurrencyIdConvert::convert(CurrencyId::B).unwrap();
+		let supply_location = CurrencyIdConvert::convert(CurrencyId::A).unwrap();
+		assert_eq!(ParaXTokens::quote_fee(&supply_location, &fee_location, 50), Some(100));
+
+		assert_ok!(ParaXTokens::transfer_with_fee_swap(
+			Some(ALICE).into(),
+			CurrencyId::B,
+			450,
+			50,
+			CurrencyId::A,
+			None,
+			Box::new(
+				(
+					Parent,
+					Parachain(2),
+					Junction::AccountId32 {
+						network: None,
+						id: BOB.into(),
+					},
+				)
+					.into()
+			),
+			WeightLimit::Unlimited
+		));
+
+		// 50 B of fee is bought with 100 A
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &ALICE), 900);
+		assert_eq!(ParaTokens::free_balance(CurrencyId::B, &ALICE), 0);
+		para::System::assert_has_event(para::RuntimeEvent::XTokens(crate::Event::FeeSwapped {
+			sender: ALICE,
+			supply_currency_id: CurrencyId::A,
+			supply_amount: 100,
+			fee: (fee_location, 50u128).into(),
+		}));
+	});
+
+	ParaB::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(CurrencyId::B, &sibling_a_account()), 500);
+		assert_eq!(ParaTokens::free_balance(CurrencyId::B, &BOB), 450);
+	});
+}
+
+#[test]
+fn transfer_with_fee_swap_checks_supply() {
+	TestNet::reset();
+
+	let dest: VersionedLocation = (
+		Parent,
+		Parachain(2),
+		Junction::AccountId32 {
+			network: None,
+			id: BOB.into(),
+		},
+	)
+		.into();
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::B, &ALICE, 450));
+		assert_ok!(ParaTokens::deposit(CurrencyId::A, &ALICE, 1_000));
+		assert_ok!(ParaTokens::deposit(CurrencyId::B1, &ALICE, 1_000));
+
+		assert_noop!(
+			ParaXTokens::transfer_with_fee_swap(
+				Some(ALICE).into(),
+				CurrencyId::B,
+				450,
+				0,
+				CurrencyId::A,
+				None,
+				Box::new(dest.clone()),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::ZeroFee
+		);
+
+		// no fee rate of `B1`
+		assert_noop!(
+			ParaXTokens::transfer_with_fee_swap(
+				Some(ALICE).into(),
+				CurrencyId::B,
+				450,
+				50,
+				CurrencyId::B1,
+				None,
+				Box::new(dest.clone()),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::FeeQuoteUnavailable
+		);
+
+		// 100 A is needed
+		assert_noop!(
+			ParaXTokens::transfer_with_fee_swap(
+				Some(ALICE).into(),
+				CurrencyId::B,
+				450,
+				50,
+				CurrencyId::A,
+				Some(99),
+				Box::new(dest),
+				WeightLimit::Unlimited
+			),
+			DispatchError::Other("slippage")
+		);
+	});
+}
+
 #[test]
 fn send_sibling_asset_to_reserve_sibling_with_distinct_fee() {
 	TestNet::reset();
@@ -1603,72 +1729,113 @@ fn send_relay_chain_asset_to_relative_view_sibling() {
 }
 
 #[test]
-fn unsupported_multilocation_should_be_filtered() {
+fn send_relay_chain_asset_through_route() {
 	TestNet::reset();
 
-	ParaB::execute_with(|| {
-		assert_ok!(ParaTokens::deposit(CurrencyId::B, &ALICE, 1_000));
-		assert_ok!(ParaTokens::deposit(CurrencyId::B1, &ALICE, 1_000));
-		assert_noop!(
-			ParaXTokens::transfer(
-				Some(ALICE).into(),
-				CurrencyId::B,
-				500,
-				Box::new(
-					(
-						Parent,
-						Parachain(5), // parachain 4 is not supported list.
+	AssetHub::execute_with(|| {
+		let _ = AssetHubBalances::deposit_creating(&sibling_d_account(), 1000);
+	});
+
+	ParaD::execute_with(|| {
+		assert_ok!(ParaRelativeXTokens::transfer(
+			Some(ALICE).into(),
+			CurrencyId::R,
+			500,
+			Box::new(
+				Location::new(
+					1,
+					[
+						Parachain(1),
 						Junction::AccountId32 {
 							network: None,
 							id: BOB.into(),
-						},
-					)
-						.into()
-				),
-				WeightLimit::Unlimited
+						}
+					]
+				)
+				.into()
 			),
-			Error::<para::Runtime>::NotSupportedLocation
-		);
+			WeightLimit::Unlimited
+		));
+		assert_eq!(ParaRelativeTokens::free_balance(CurrencyId::R, &ALICE), 500);
+	});
 
-		assert_noop!(
-			ParaXTokens::transfer_multicurrencies(
-				Some(ALICE).into(),
-				vec![(CurrencyId::B1, 50), (CurrencyId::B, 450)],
-				0,
-				Box::new(
-					(
-						Parent,
-						Parachain(5),
+	// first hop: the reserve, which teleports to the relay chain
+	AssetHub::execute_with(|| {
+		assert_eq!(AssetHubBalances::free_balance(sibling_d_account()), 500);
+	});
+
+	// second hop: the relay chain, which is a reserve after the teleport
+	Relay::execute_with(|| {
+		assert_eq!(RelayBalances::free_balance(para_a_account()), 400);
+	});
+
+	ParaA::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &BOB), 350);
+	});
+}
+
+#[test]
+fn route_is_cut_short_at_dest() {
+	TestNet::reset();
+
+	ParaB::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::B, &sibling_d_account(), 1_000));
+	});
+
+	ParaD::execute_with(|| {
+		assert_ok!(ParaRelativeTokens::deposit(CurrencyId::B, &ALICE, 1_000));
+
+		assert_ok!(ParaRelativeXTokens::transfer(
+			Some(ALICE).into(),
+			CurrencyId::B,
+			500,
+			Box::new(
+				Location::new(
+					1,
+					[
+						Parachain(1),
 						Junction::AccountId32 {
 							network: None,
 							id: BOB.into(),
-						},
-					)
-						.into()
-				),
-				WeightLimit::Unlimited
+						}
+					]
+				)
+				.into()
 			),
-			Error::<para::Runtime>::NotSupportedLocation
-		);
+			WeightLimit::Unlimited
+		));
+		assert_eq!(ParaRelativeTokens::free_balance(CurrencyId::B, &ALICE), 500);
+	});
+
+	// the reserve gets its share of the fee, the dest chain the rest
+	ParaB::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(CurrencyId::B, &sibling_d_account()), 500);
+		assert_eq!(ParaTokens::free_balance(CurrencyId::B, &sibling_a_account()), 450);
+	});
+
+	ParaA::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(CurrencyId::B, &BOB), 400);
 	});
 }
 
 #[test]
-fn send_with_sufficient_weight_limit() {
+fn route_cut_short_to_nothing_goes_through_reserve() {
 	TestNet::reset();
 
-	ParaA::execute_with(|| {
-		assert_ok!(ParaTokens::deposit(CurrencyId::A, &ALICE, 1_000));
+	AssetHub::execute_with(|| {
+		let _ = AssetHubBalances::deposit_creating(&sibling_d_account(), 1000);
+	});
 
-		assert_ok!(ParaXTokens::transfer(
+	ParaD::execute_with(|| {
+		assert_ok!(ParaRelativeXTokens::transfer(
 			Some(ALICE).into(),
-			CurrencyId::A,
+			CurrencyId::R,
 			500,
 			Box::new(
 				Location::new(
 					1,
 					[
-						Parachain(2),
+						Parachain(1000),
 						Junction::AccountId32 {
 							network: None,
 							id: BOB.into(),
@@ -1677,28 +1844,28 @@ fn send_with_sufficient_weight_limit() {
 				)
 				.into()
 			),
-			WeightLimit::Limited(50.into()),
+			WeightLimit::Unlimited
 		));
-
-		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &ALICE), 500);
-		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &sibling_b_account()), 500);
 	});
 
-	ParaB::execute_with(|| {
-		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &BOB), 450);
+	AssetHub::execute_with(|| {
+		assert_eq!(AssetHubBalances::free_balance(sibling_d_account()), 500);
+		assert_eq!(AssetHubBalances::free_balance(&BOB), 450);
 	});
 }
 
 #[test]
-fn send_with_insufficient_weight_limit() {
+fn disabled_routes_go_through_reserve() {
 	TestNet::reset();
 
-	ParaA::execute_with(|| {
-		assert_ok!(ParaTokens::deposit(CurrencyId::A, &ALICE, 1_000));
+	AssetHub::execute_with(|| {
+		let _ = AssetHubBalances::deposit_creating(&sibling_a_account(), 1000);
+	});
 
+	ParaA::execute_with(|| {
 		assert_ok!(ParaXTokens::transfer(
 			Some(ALICE).into(),
-			CurrencyId::A,
+			CurrencyId::R,
 			500,
 			Box::new(
 				Location::new(
@@ -1713,61 +1880,357 @@ fn send_with_insufficient_weight_limit() {
 				)
 				.into()
 			),
-			WeightLimit::Limited(1.into()),
+			WeightLimit::Unlimited
 		));
+	});
 
-		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &ALICE), 500);
-		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &sibling_b_account()), 500);
+	AssetHub::execute_with(|| {
+		assert_eq!(AssetHubBalances::free_balance(sibling_a_account()), 500);
+		assert_eq!(AssetHubBalances::free_balance(sibling_b_account()), 450);
 	});
 
 	ParaB::execute_with(|| {
-		// no funds should arrive - message will have failed
-		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &BOB), 0);
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &BOB), 400);
 	});
 }
 
 #[test]
-fn send_relay_chain_asset_to_relay_chain_at_rate_limit() {
+fn route_uses_weight_limit_of_hop() {
 	TestNet::reset();
 
-	AssetHub::execute_with(|| {
-		let _ = AssetHubBalances::deposit_creating(&sibling_a_account(), 4000);
-		assert_eq!(AssetHubBalances::free_balance(&sibling_a_account()), 4000);
-		assert_eq!(AssetHubBalances::free_balance(&ALICE), 1000);
-		assert_eq!(AssetHubBalances::free_balance(&BOB), 0);
-	});
-
 	ParaA::execute_with(|| {
-		use crate::tests::para::R_ACCUMULATION;
+		assert_ok!(ParaTokens::deposit(CurrencyId::A, &sibling_d_account(), 1_000));
+	});
 
-		assert_ok!(ParaTokens::deposit(CurrencyId::R, &CHARLIE, 3000));
-		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &ALICE), 1000);
-		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &CHARLIE), 3000);
-		assert_eq!(R_ACCUMULATION.with(|v| *v.borrow()), 0);
+	ParaD::execute_with(|| {
+		assert_ok!(ParaRelativeTokens::deposit(CurrencyId::A, &ALICE, 1_000));
 
-		// Rate limiter allowed CHARLIE's transfer, and reward accumulation
-		assert_ok!(ParaXTokens::transfer(
-			Some(CHARLIE).into(),
-			CurrencyId::R,
-			1800,
+		assert_ok!(ParaRelativeXTokens::transfer(
+			Some(ALICE).into(),
+			CurrencyId::A,
+			500,
 			Box::new(
 				Location::new(
 					1,
-					[Junction::AccountId32 {
-						network: None,
-						id: CHARLIE.into(),
-					}]
+					[
+						Parachain(2),
+						Junction::AccountId32 {
+							network: None,
+							id: BOB.into(),
+						}
+					]
 				)
 				.into()
 			),
 			WeightLimit::Unlimited
 		));
-		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &CHARLIE), 1200);
-		assert_eq!(R_ACCUMULATION.with(|v| *v.borrow()), 1800);
+	});
 
-		// Rate limiter refused CHARLIE's transfer, because the amount will exceed the
-		// limit
-		assert_noop!(
+	// the hop rejects the message, which cannot be executed within its weight limit
+	ParaA::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &sibling_d_account()), 1_000);
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &sibling_b_account()), 0);
+	});
+
+	ParaB::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &BOB), 0);
+	});
+}
+
+#[test]
+fn route_not_starting_at_reserve_should_fail() {
+	TestNet::reset();
+
+	ParaD::execute_with(|| {
+		assert_ok!(ParaRelativeTokens::deposit(CurrencyId::A1, &ALICE, 1_000));
+
+		assert_noop!(
+			ParaRelativeXTokens::transfer(
+				Some(ALICE).into(),
+				CurrencyId::A1,
+				500,
+				Box::new(
+					Location::new(
+						1,
+						[
+							Parachain(3),
+							Junction::AccountId32 {
+								network: None,
+								id: BOB.into(),
+							}
+						]
+					)
+					.into()
+				),
+				WeightLimit::Unlimited
+			),
+			Error::<para_relative_view::Runtime>::InvalidRoute
+		);
+	});
+}
+
+#[test]
+fn route_forwarding_derivatives_to_non_reserve_should_fail() {
+	TestNet::reset();
+
+	ParaD::execute_with(|| {
+		assert_ok!(ParaRelativeTokens::deposit(CurrencyId::B, &ALICE, 1_000));
+
+		assert_noop!(
+			ParaRelativeXTokens::transfer(
+				Some(ALICE).into(),
+				CurrencyId::B,
+				500,
+				Box::new(
+					Location::new(
+						1,
+						[
+							Parachain(3),
+							Junction::AccountId32 {
+								network: None,
+								id: BOB.into(),
+							}
+						]
+					)
+					.into()
+				),
+				WeightLimit::Unlimited
+			),
+			Error::<para_relative_view::Runtime>::InvalidRoute
+		);
+	});
+}
+
+#[test]
+fn route_teleporting_derivatives_should_fail() {
+	TestNet::reset();
+
+	ParaD::execute_with(|| {
+		assert_ok!(ParaRelativeTokens::deposit(CurrencyId::B1, &ALICE, 1_000));
+
+		assert_noop!(
+			ParaRelativeXTokens::transfer(
+				Some(ALICE).into(),
+				CurrencyId::B1,
+				500,
+				Box::new(
+					Location::new(
+						1,
+						[
+							Parachain(3),
+							Junction::AccountId32 {
+								network: None,
+								id: BOB.into(),
+							}
+						]
+					)
+					.into()
+				),
+				WeightLimit::Unlimited
+			),
+			Error::<para_relative_view::Runtime>::InvalidRoute
+		);
+	});
+}
+
+#[test]
+fn route_without_fee_left_for_dest_should_fail() {
+	TestNet::reset();
+
+	ParaD::execute_with(|| {
+		assert_ok!(ParaRelativeTokens::deposit(CurrencyId::B2, &ALICE, 1_000));
+
+		assert_noop!(
+			ParaRelativeXTokens::transfer(
+				Some(ALICE).into(),
+				CurrencyId::B2,
+				500,
+				Box::new(
+					Location::new(
+						1,
+						[
+							Parachain(1),
+							Junction::AccountId32 {
+								network: None,
+								id: BOB.into(),
+							}
+						]
+					)
+					.into()
+				),
+				WeightLimit::Unlimited
+			),
+			Error::<para_relative_view::Runtime>::InvalidRoute
+		);
+	});
+}
+
+#[test]
+fn unsupported_multilocation_should_be_filtered() {
+	TestNet::reset();
+
+	ParaB::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::B, &ALICE, 1_000));
+		assert_ok!(ParaTokens::deposit(CurrencyId::B1, &ALICE, 1_000));
+		assert_noop!(
+			ParaXTokens::transfer(
+				Some(ALICE).into(),
+				CurrencyId::B,
+				500,
+				Box::new(
+					(
+						Parent,
+						Parachain(5), // parachain 4 is not supported list.
+						Junction::AccountId32 {
+							network: None,
+							id: BOB.into(),
+						},
+					)
+						.into()
+				),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::NotSupportedLocation
+		);
+
+		assert_noop!(
+			ParaXTokens::transfer_multicurrencies(
+				Some(ALICE).into(),
+				vec![(CurrencyId::B1, 50), (CurrencyId::B, 450)],
+				0,
+				Box::new(
+					(
+						Parent,
+						Parachain(5),
+						Junction::AccountId32 {
+							network: None,
+							id: BOB.into(),
+						},
+					)
+						.into()
+				),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::NotSupportedLocation
+		);
+	});
+}
+
+#[test]
+fn send_with_sufficient_weight_limit() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::A, &ALICE, 1_000));
+
+		assert_ok!(ParaXTokens::transfer(
+			Some(ALICE).into(),
+			CurrencyId::A,
+			500,
+			Box::new(
+				Location::new(
+					1,
+					[
+						Parachain(2),
+						Junction::AccountId32 {
+							network: None,
+							id: BOB.into(),
+						}
+					]
+				)
+				.into()
+			),
+			WeightLimit::Limited(50.into()),
+		));
+
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &ALICE), 500);
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &sibling_b_account()), 500);
+	});
+
+	ParaB::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &BOB), 450);
+	});
+}
+
+#[test]
+fn send_with_insufficient_weight_limit() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::A, &ALICE, 1_000));
+
+		assert_ok!(ParaXTokens::transfer(
+			Some(ALICE).into(),
+			CurrencyId::A,
+			500,
+			Box::new(
+				Location::new(
+					1,
+					[
+						Parachain(2),
+						Junction::AccountId32 {
+							network: None,
+							id: BOB.into(),
+						}
+					]
+				)
+				.into()
+			),
+			WeightLimit::Limited(1.into()),
+		));
+
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &ALICE), 500);
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &sibling_b_account()), 500);
+	});
+
+	ParaB::execute_with(|| {
+		// no funds should arrive - message will have failed
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &BOB), 0);
+	});
+}
+
+#[test]
+fn send_relay_chain_asset_to_relay_chain_at_rate_limit() {
+	TestNet::reset();
+
+	AssetHub::execute_with(|| {
+		let _ = AssetHubBalances::deposit_creating(&sibling_a_account(), 4000);
+		assert_eq!(AssetHubBalances::free_balance(&sibling_a_account()), 4000);
+		assert_eq!(AssetHubBalances::free_balance(&ALICE), 1000);
+		assert_eq!(AssetHubBalances::free_balance(&BOB), 0);
+	});
+
+	ParaA::execute_with(|| {
+		use crate::tests::para::R_ACCUMULATION;
+
+		assert_ok!(ParaTokens::deposit(CurrencyId::R, &CHARLIE, 3000));
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &ALICE), 1000);
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &CHARLIE), 3000);
+		assert_eq!(R_ACCUMULATION.with(|v| *v.borrow()), 0);
+
+		// Rate limiter allowed CHARLIE's transfer, and reward accumulation
+		assert_ok!(ParaXTokens::transfer(
+			Some(CHARLIE).into(),
+			CurrencyId::R,
+			1800,
+			Box::new(
+				Location::new(
+					1,
+					[Junction::AccountId32 {
+						network: None,
+						id: CHARLIE.into(),
+					}]
+				)
+				.into()
+			),
+			WeightLimit::Unlimited
+		));
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &CHARLIE), 1200);
+		assert_eq!(R_ACCUMULATION.with(|v| *v.borrow()), 1800);
+
+		// Rate limiter refused CHARLIE's transfer, because the amount will exceed the
+		// limit
+		assert_noop!(
 			ParaXTokens::transfer(
 				Some(CHARLIE).into(),
 				CurrencyId::R,
@@ -1830,10 +2293,132 @@ fn send_relay_chain_asset_to_relay_chain_at_rate_limit() {
 		assert_eq!(R_ACCUMULATION.with(|v| *v.borrow()), 2000);
 	});
 
-	AssetHub::execute_with(|| {
-		assert_eq!(AssetHubBalances::free_balance(&sibling_a_account()), 1799);
-		assert_eq!(AssetHubBalances::free_balance(&ALICE), 1151);
-		assert_eq!(AssetHubBalances::free_balance(&CHARLIE), 1900);
+	AssetHub::execute_with(|| {
+		assert_eq!(AssetHubBalances::free_balance(&sibling_a_account()), 1799);
+		assert_eq!(AssetHubBalances::free_balance(&ALICE), 1151);
+		assert_eq!(AssetHubBalances::free_balance(&CHARLIE), 1900);
+	});
+}
+
+#[test]
+fn receive_relay_chain_asset_at_rate_limit() {
+	TestNet::reset();
+
+	AssetHub::execute_with(|| {
+		let _ = AssetHubBalances::deposit_creating(&sibling_a_account(), 4000);
+	});
+
+	ParaA::execute_with(|| {
+		use crate::tests::para::R_ACCUMULATION;
+
+		assert_ok!(ParaTokens::deposit(CurrencyId::R, &ALICE, 3000));
+		assert_eq!(R_ACCUMULATION.with(|v| *v.borrow()), 0);
+
+		assert_ok!(ParaXTokens::transfer(
+			Some(ALICE).into(),
+			CurrencyId::R,
+			500,
+			Box::new(
+				Location::new(
+					1,
+					[
+						Parachain(2),
+						Junction::AccountId32 {
+							network: None,
+							id: CHARLIE.into(),
+						}
+					]
+				)
+				.into()
+			),
+			WeightLimit::Unlimited
+		));
+	});
+
+	// Rate limiter allowed the deposit to CHARLIE, and reward accumulation
+	ParaB::execute_with(|| {
+		use crate::tests::para::R_ACCUMULATION;
+
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &CHARLIE), 400);
+		assert_eq!(R_ACCUMULATION.with(|v| *v.borrow()), 400);
+	});
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaXTokens::transfer(
+			Some(ALICE).into(),
+			CurrencyId::R,
+			2000,
+			Box::new(
+				Location::new(
+					1,
+					[
+						Parachain(2),
+						Junction::AccountId32 {
+							network: None,
+							id: CHARLIE.into(),
+						}
+					]
+				)
+				.into()
+			),
+			WeightLimit::Unlimited
+		));
+	});
+
+	// Rate limiter refused the deposit to CHARLIE, so the assets are trapped
+	ParaB::execute_with(|| {
+		use crate::tests::para::R_ACCUMULATION;
+
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &CHARLIE), 400);
+		assert_eq!(R_ACCUMULATION.with(|v| *v.borrow()), 400);
+		assert!(para::System::events().iter().any(|r| {
+			matches!(
+				r.event,
+				para::RuntimeEvent::PolkadotXcm(pallet_xcm::Event::<para::Runtime>::AssetsTrapped { .. })
+			)
+		}));
+	});
+}
+
+#[test]
+fn deposit_to_alternative_should_report_rate_limited_deposits() {
+	thread_local! {
+		static RATE_LIMITED: RefCell<Vec<(CurrencyId, AccountId32, Balance)>> = const { RefCell::new(vec![]) };
+	}
+
+	pub struct RecordRateLimited;
+	impl Happened<(CurrencyId, AccountId32, Balance)> for RecordRateLimited {
+		fn happened(t: &(CurrencyId, AccountId32, Balance)) {
+			RATE_LIMITED.with(|v| v.borrow_mut().push(t.clone()));
+		}
+	}
+
+	parameter_types! {
+		pub Alternative: AccountId32 = AccountId32::new([9u8; 32]);
+	}
+
+	type Handler = DepositToAlternative<Alternative, ParaTokens, CurrencyId, AccountId32, Balance, RecordRateLimited>;
+
+	TestNet::reset();
+
+	ParaB::execute_with(|| {
+		assert_ok!(Handler::on_deposit_rate_limited(CurrencyId::R, &CHARLIE, 100));
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &Alternative::get()), 100);
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &CHARLIE), 0);
+		assert_eq!(
+			RATE_LIMITED.with(|v| v.borrow().clone()),
+			vec![(CurrencyId::R, CHARLIE, 100)]
+		);
+
+		// the default handler leaves the assets trapped
+		assert_eq!(
+			<() as OnDepositFail<CurrencyId, AccountId32, Balance>>::on_deposit_rate_limited(
+				CurrencyId::R,
+				&CHARLIE,
+				100
+			),
+			Err(XcmError::FailedToTransactAsset("RateLimited"))
+		);
 	});
 }
 
@@ -1938,6 +2523,345 @@ fn nfts_cannot_be_fee_assets() {
 const PARACHAIN: Junction = Parachain(1);
 const GENERAL_INDEX: Junction = GeneralIndex(1);
 
+fn deposit_to(who: AccountId32) -> Box<VersionedXcm<()>> {
+	Box::new(VersionedXcm::from(Xcm(vec![DepositAsset {
+		assets: AllCounted(1).into(),
+		beneficiary: Junction::AccountId32 {
+			network: None,
+			id: who.into(),
+		}
+		.into(),
+	}])))
+}
+
+#[test]
+fn send_relay_chain_asset_to_asset_hub_using_destination_reserve_type() {
+	TestNet::reset();
+
+	AssetHub::execute_with(|| {
+		let _ = AssetHubBalances::deposit_creating(&sibling_a_account(), 1_000);
+	});
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaXTokens::transfer_assets_using_type_and_then(
+			Some(ALICE).into(),
+			Box::new(Assets::from((Location::parent(), 500u128)).into()),
+			0,
+			Box::new(Location::new(1, [Parachain(ASSET_HUB_ID)]).into()),
+			Box::new(TransferType::DestinationReserve),
+			None,
+			deposit_to(BOB),
+			WeightLimit::Unlimited
+		));
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &ALICE), 500);
+	});
+
+	AssetHub::execute_with(|| {
+		assert_eq!(AssetHubBalances::free_balance(sibling_a_account()), 500);
+		assert_eq!(AssetHubBalances::free_balance(&BOB), 450);
+	});
+}
+
+#[test]
+fn send_relay_chain_asset_to_sibling_using_remote_reserve_type() {
+	TestNet::reset();
+
+	AssetHub::execute_with(|| {
+		let _ = AssetHubBalances::deposit_creating(&sibling_a_account(), 1_000);
+	});
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaXTokens::transfer_assets_using_type_and_then(
+			Some(ALICE).into(),
+			Box::new(Assets::from((Location::parent(), 500u128)).into()),
+			0,
+			Box::new(Location::new(1, [Parachain(2)]).into()),
+			Box::new(TransferType::RemoteReserve(
+				Location::new(1, [Parachain(ASSET_HUB_ID)]).into()
+			)),
+			None,
+			deposit_to(BOB),
+			WeightLimit::Unlimited
+		));
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &ALICE), 500);
+	});
+
+	AssetHub::execute_with(|| {
+		assert_eq!(AssetHubBalances::free_balance(sibling_a_account()), 500);
+		assert_eq!(AssetHubBalances::free_balance(sibling_b_account()), 450);
+	});
+
+	ParaB::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &BOB), 400);
+	});
+}
+
+#[test]
+fn send_relay_chain_asset_to_sibling_using_remote_reserve_type_with_reserve_fee() {
+	TestNet::reset();
+
+	AssetHub::execute_with(|| {
+		let _ = AssetHubBalances::deposit_creating(&sibling_a_account(), 1_000);
+	});
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaXTokens::transfer_assets_using_type_and_then(
+			Some(ALICE).into(),
+			Box::new(Assets::from((Location::parent(), 500u128)).into()),
+			0,
+			Box::new(Location::new(1, [Parachain(3)]).into()),
+			Box::new(TransferType::RemoteReserve(
+				Location::new(1, [Parachain(ASSET_HUB_ID)]).into()
+			)),
+			Some(60),
+			deposit_to(BOB),
+			WeightLimit::Unlimited
+		));
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &ALICE), 500);
+	});
+
+	AssetHub::execute_with(|| {
+		assert_eq!(AssetHubBalances::free_balance(sibling_a_account()), 500);
+		assert_eq!(AssetHubBalances::free_balance(sibling_c_account()), 450);
+	});
+
+	ParaC::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &BOB), 400);
+	});
+}
+
+#[test]
+fn remote_reserve_fees_are_enforced_on_both_legs() {
+	TestNet::reset();
+
+	let transfer = |reserve_fee| {
+		ParaXTokens::transfer_assets_using_type_and_then(
+			Some(ALICE).into(),
+			Box::new(Assets::from((Location::parent(), 500u128)).into()),
+			0,
+			Box::new(Location::new(1, [Parachain(3)]).into()),
+			Box::new(TransferType::RemoteReserve(
+				Location::new(1, [Parachain(ASSET_HUB_ID)]).into(),
+			)),
+			reserve_fee,
+			deposit_to(BOB),
+			WeightLimit::Unlimited,
+		)
+	};
+
+	ParaA::execute_with(|| {
+		// the reserve fee is below `MinXcmFee` of asset hub
+		assert_noop!(transfer(Some(40)), Error::<para::Runtime>::FeeNotEnough);
+		// the rest of the fee is below `MinXcmFee` of the dest
+		assert_noop!(transfer(Some(460)), Error::<para::Runtime>::FeeNotEnough);
+		// no fee is left for either chain
+		assert_noop!(transfer(Some(0)), Error::<para::Runtime>::InvalidReserveFee);
+		assert_noop!(transfer(Some(500)), Error::<para::Runtime>::InvalidReserveFee);
+		assert_noop!(transfer(Some(501)), Error::<para::Runtime>::InvalidReserveFee);
+
+		// there's no reserve fee without a remote reserve
+		assert_noop!(
+			ParaXTokens::transfer_assets_using_type_and_then(
+				Some(ALICE).into(),
+				Box::new(Assets::from((Location::parent(), 500u128)).into()),
+				0,
+				Box::new(Location::new(1, [Parachain(ASSET_HUB_ID)]).into()),
+				Box::new(TransferType::DestinationReserve),
+				Some(100),
+				deposit_to(BOB),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::InvalidReserveFee
+		);
+
+		assert_ok!(transfer(Some(450)));
+	});
+}
+
+#[test]
+fn send_self_parachain_asset_to_sibling_using_local_reserve_type() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::A, &ALICE, 1_000));
+
+		assert_ok!(ParaXTokens::transfer_assets_using_type_and_then(
+			Some(ALICE).into(),
+			Box::new(Assets::from((CurrencyIdConvert::convert(CurrencyId::A).unwrap(), 500u128)).into()),
+			0,
+			Box::new(Location::new(1, [Parachain(2)]).into()),
+			Box::new(TransferType::LocalReserve),
+			None,
+			deposit_to(BOB),
+			WeightLimit::Unlimited
+		));
+
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &ALICE), 500);
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &sibling_b_account()), 500);
+	});
+
+	ParaB::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &BOB), 450);
+	});
+}
+
+#[test]
+fn transfer_assets_using_type_and_then_checks() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		// dest is not supported
+		assert_noop!(
+			ParaXTokens::transfer_assets_using_type_and_then(
+				Some(ALICE).into(),
+				Box::new(Assets::from((Location::parent(), 500u128)).into()),
+				0,
+				Box::new(Location::new(1, [Parachain(5)]).into()),
+				Box::new(TransferType::DestinationReserve),
+				None,
+				deposit_to(BOB),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::NotSupportedLocation
+		);
+
+		// dest is not a chain
+		assert_noop!(
+			ParaXTokens::transfer_assets_using_type_and_then(
+				Some(ALICE).into(),
+				Box::new(Assets::from((Location::parent(), 500u128)).into()),
+				0,
+				Box::new(
+					Location::new(
+						1,
+						[
+							Parachain(2),
+							Junction::AccountId32 {
+								network: None,
+								id: BOB.into(),
+							}
+						]
+					)
+					.into()
+				),
+				Box::new(TransferType::DestinationReserve),
+				None,
+				deposit_to(BOB),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::InvalidDest
+		);
+
+		// half of the fee is below `MinXcmFee` of asset hub
+		assert_noop!(
+			ParaXTokens::transfer_assets_using_type_and_then(
+				Some(ALICE).into(),
+				Box::new(Assets::from((Location::parent(), 80u128)).into()),
+				0,
+				Box::new(Location::new(1, [Parachain(2)]).into()),
+				Box::new(TransferType::RemoteReserve(
+					Location::new(1, [Parachain(ASSET_HUB_ID)]).into()
+				)),
+				None,
+				deposit_to(BOB),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::FeeNotEnough
+		);
+
+		// the reserve of relay asset is asset hub, not the dest
+		assert_noop!(
+			ParaXTokens::transfer_assets_using_type_and_then(
+				Some(ALICE).into(),
+				Box::new(Assets::from((Location::parent(), 500u128)).into()),
+				0,
+				Box::new(Location::new(1, [Parachain(2)]).into()),
+				Box::new(TransferType::DestinationReserve),
+				None,
+				deposit_to(BOB),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::InvalidTransferType
+		);
+
+		// relay asset is not reserved by self chain
+		assert_noop!(
+			ParaXTokens::transfer_assets_using_type_and_then(
+				Some(ALICE).into(),
+				Box::new(Assets::from((Location::parent(), 500u128)).into()),
+				0,
+				Box::new(Location::new(1, [Parachain(ASSET_HUB_ID)]).into()),
+				Box::new(TransferType::LocalReserve),
+				None,
+				deposit_to(BOB),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::InvalidTransferType
+		);
+
+		// the remote reserve is not the reserve of relay asset
+		assert_noop!(
+			ParaXTokens::transfer_assets_using_type_and_then(
+				Some(ALICE).into(),
+				Box::new(Assets::from((Location::parent(), 500u128)).into()),
+				0,
+				Box::new(Location::new(1, [Parachain(2)]).into()),
+				Box::new(TransferType::RemoteReserve(Location::new(1, [Parachain(3)]).into())),
+				None,
+				deposit_to(BOB),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::InvalidTransferType
+		);
+
+		// the remote reserve is not supported
+		assert_noop!(
+			ParaXTokens::transfer_assets_using_type_and_then(
+				Some(ALICE).into(),
+				Box::new(Assets::from((Location::parent(), 500u128)).into()),
+				0,
+				Box::new(Location::new(1, [Parachain(2)]).into()),
+				Box::new(TransferType::RemoteReserve(Location::new(1, [Parachain(5)]).into())),
+				None,
+				deposit_to(BOB),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::NotSupportedLocation
+		);
+
+		// self asset can't be teleported
+		assert_noop!(
+			ParaXTokens::transfer_assets_using_type_and_then(
+				Some(ALICE).into(),
+				Box::new(Assets::from((CurrencyIdConvert::convert(CurrencyId::A).unwrap(), 500u128)).into()),
+				0,
+				Box::new(Location::new(1, [Parachain(2)]).into()),
+				Box::new(TransferType::Teleport),
+				None,
+				deposit_to(BOB),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::InvalidTransferType
+		);
+
+		// rate limited
+		assert_ok!(ParaTokens::deposit(CurrencyId::R, &CHARLIE, 3000));
+		assert_noop!(
+			ParaXTokens::transfer_assets_using_type_and_then(
+				Some(CHARLIE).into(),
+				Box::new(Assets::from((Location::parent(), 2001u128)).into()),
+				0,
+				Box::new(Location::new(1, [Parachain(ASSET_HUB_ID)]).into()),
+				Box::new(TransferType::DestinationReserve),
+				None,
+				deposit_to(CHARLIE),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::RateLimited
+		);
+	});
+}
+
 fn concrete_fungible(id: Location) -> Asset {
 	(id, 1).into()
 }
@@ -2058,3 +2982,100 @@ fn multi_native_asset() {
 		));
 	});
 }
+
+fn para_a_nft(token_id: u64) -> Asset {
+	(
+		Location::new(
+			1,
+			[
+				Parachain(1),
+				PalletInstance(NFT_PALLET_INDEX),
+				GeneralIndex(NFT_CLASS.into()),
+			],
+		),
+		AssetInstance::Index(token_id.into()),
+	)
+		.into()
+}
+
+fn transfer_nft_with_fee(from: AccountId32, nft: Asset, fee: Asset, dest: Location) -> DispatchResult {
+	let assets: Assets = vec![nft, fee.clone()].into();
+	let fee_item = assets.inner().iter().position(|asset| *asset == fee).unwrap() as u32;
+	ParaXTokens::transfer_multiassets(
+		Some(from).into(),
+		Box::new(assets.into()),
+		fee_item,
+		Box::new(dest.into()),
+		WeightLimit::Unlimited,
+	)
+}
+
+#[test]
+fn send_nft_to_sibling_and_back() {
+	TestNet::reset();
+
+	let a_location = CurrencyIdConvert::convert(CurrencyId::A).unwrap();
+	let mut token_id = 0;
+	ParaA::execute_with(|| {
+		assert_ok!(ParaNft::mint(&ALICE, NFT_CLASS, vec![], ()));
+		token_id = ParaNft::mint(&ALICE, NFT_CLASS, vec![], ()).unwrap();
+		assert_ok!(ParaTokens::deposit(CurrencyId::A, &ALICE, 1_000));
+
+		assert_ok!(transfer_nft_with_fee(
+			ALICE,
+			para_a_nft(token_id),
+			(a_location.clone(), 500).into(),
+			Location::new(
+				1,
+				[
+					Parachain(2),
+					Junction::AccountId32 {
+						network: None,
+						id: BOB.into(),
+					},
+				],
+			),
+		));
+
+		assert!(ParaNft::is_owner(&sibling_b_account(), (NFT_CLASS, token_id)));
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &sibling_b_account()), 500);
+	});
+
+	ParaB::execute_with(|| {
+		// the derivative token is minted, with an id of its own
+		let derivative_id = para::NftDerivativeTokens::get(NFT_CLASS, u128::from(token_id)).unwrap();
+		assert_ne!(derivative_id, token_id);
+		assert!(ParaNft::is_owner(&BOB, (NFT_CLASS, derivative_id)));
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &BOB), 450);
+
+		assert_ok!(transfer_nft_with_fee(
+			BOB,
+			para_a_nft(token_id),
+			(a_location, 300).into(),
+			Location::new(
+				1,
+				[
+					Parachain(1),
+					Junction::AccountId32 {
+						network: None,
+						id: ALICE.into(),
+					},
+				],
+			),
+		));
+
+		assert!(ParaNft::tokens(NFT_CLASS, derivative_id).is_none());
+		assert!(!para::NftDerivativeTokens::contains_key(
+			NFT_CLASS,
+			u128::from(token_id)
+		));
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &BOB), 150);
+	});
+
+	ParaA::execute_with(|| {
+		assert!(ParaNft::is_owner(&ALICE, (NFT_CLASS, token_id)));
+		assert!(!ParaNft::is_owner(&sibling_b_account(), (NFT_CLASS, token_id)));
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &sibling_b_account()), 200);
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &ALICE), 750);
+	});
+}
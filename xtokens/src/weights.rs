@@ -0,0 +1,111 @@
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for orml_xtokens.
+pub trait WeightInfo {
+	fn transfer_self_reserve() -> Weight;
+	fn transfer_to_reserve() -> Weight;
+	fn transfer_to_non_reserve() -> Weight;
+	fn transfer_multiasset_self_reserve() -> Weight;
+	fn transfer_multiasset_to_reserve() -> Weight;
+	fn transfer_multiasset_to_non_reserve() -> Weight;
+	fn transfer_with_fee() -> Weight;
+	fn transfer_multiasset_with_fee() -> Weight;
+	fn transfer_multicurrencies_self_reserve() -> Weight;
+	fn transfer_multicurrencies_to_reserve() -> Weight;
+	fn transfer_multicurrencies_to_non_reserve() -> Weight;
+	fn transfer_multiassets_self_reserve() -> Weight;
+	fn transfer_multiassets_to_reserve() -> Weight;
+	fn transfer_multiassets_to_non_reserve() -> Weight;
+	fn transfer_assets_using_type_and_then() -> Weight;
+	fn transfer_with_fee_swap() -> Weight;
+}
+
+/// Default weights.
+impl WeightInfo for () {
+	fn transfer_self_reserve() -> Weight {
+		Weight::from_parts(47_531_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn transfer_to_reserve() -> Weight {
+		Weight::from_parts(27_273_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn transfer_to_non_reserve() -> Weight {
+		Weight::from_parts(27_415_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn transfer_multiasset_self_reserve() -> Weight {
+		Weight::from_parts(46_839_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn transfer_multiasset_to_reserve() -> Weight {
+		Weight::from_parts(27_759_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn transfer_multiasset_to_non_reserve() -> Weight {
+		Weight::from_parts(27_596_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn transfer_with_fee() -> Weight {
+		Weight::from_parts(26_983_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn transfer_multiasset_with_fee() -> Weight {
+		Weight::from_parts(26_945_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn transfer_multicurrencies_self_reserve() -> Weight {
+		Weight::from_parts(77_811_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+	}
+	fn transfer_multicurrencies_to_reserve() -> Weight {
+		Weight::from_parts(42_278_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn transfer_multicurrencies_to_non_reserve() -> Weight {
+		Weight::from_parts(46_190_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn transfer_multiassets_self_reserve() -> Weight {
+		Weight::from_parts(76_277_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+	}
+	fn transfer_multiassets_to_reserve() -> Weight {
+		Weight::from_parts(43_975_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn transfer_multiassets_to_non_reserve() -> Weight {
+		Weight::from_parts(44_403_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn transfer_assets_using_type_and_then() -> Weight {
+		Weight::from_parts(28_671_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn transfer_with_fee_swap() -> Weight {
+		Weight::from_parts(50_629_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+}